@@ -0,0 +1,215 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Automatic Speed Calibration
+// ROMs were written for interpreters anywhere from a few hundred
+// to several thousand instructions per second. Most games pace
+// themselves with the delay timer, spinning on FX07 until it runs
+// out, and sit in FX0A on menus. How much of each frame goes into
+// that idling says whether the CPU speed suits the ROM:
+//
+//   - a timer-paced ROM that hardly ever gets to idle can't finish
+//     its frame's work in time and stutters, so the speed goes up;
+//   - a ROM that idles most of every frame has speed to spare, and
+//     any stretch it paces by instruction count instead (animations,
+//     scrolling) runs too fast, so the speed comes down.
+//
+// Windows spent mostly in FX0A (title screens, menus) or without
+// setting the delay timer (ROMs paced by instruction count alone)
+// say nothing about speed and leave it alone. The speed moves in
+// whole instructions per frame.
+// ───────────────────────────────────────────────────────────────
+
+use crate::chip8::constants::TIMER_HZ;
+use crate::chip8::cpu::Chip8;
+
+// Frames measured before each adjustment
+const WINDOW_FRAMES: u64 = 120;
+
+// Cycles one pass of a delay timer wait loop takes: FX07, a skip on
+// the result and a jump back
+const POLL_LOOP_CYCLES: u64 = 3;
+
+// Share of cycles spent idling below which the speed goes up, and above
+// which it comes down
+const STARVED_IDLE: f64 = 0.10;
+const SPARE_IDLE: f64 = 0.60;
+
+// Bounds on the tuned speed, in instructions per frame (300Hz to 4800Hz)
+const MIN_PER_FRAME: u64 = 5;
+const MAX_PER_FRAME: u64 = 80;
+
+// Counters at the start of the window, taken from the machine's stats
+#[derive(Clone, Copy, Default)]
+struct Counters {
+    frames: u64,
+    instructions: u64,
+    timer_reads: u64,
+    timer_writes: u64,
+}
+
+impl Counters {
+    fn of(chip8: &Chip8) -> Self {
+        let count = |pattern: &str| chip8.stats.opcode_counts.get(pattern).copied().unwrap_or(0);
+        Self {
+            frames: chip8.stats.frames,
+            instructions: chip8.stats.instructions,
+            timer_reads: count("FX07"),
+            timer_writes: count("FX15"),
+        }
+    }
+}
+
+pub struct SpeedTuner {
+    cpu_hz: u64,
+    start: Counters,
+
+    // Frames in the window that ended blocked in FX0A. The real-time loop
+    // only polls the keypad once a frame while FX0A waits, so key waits
+    // are measured in frames rather than cycles.
+    key_wait_frames: u64,
+}
+
+impl SpeedTuner {
+
+    pub fn new(cpu_hz: u64) -> Self {
+        Self { cpu_hz, start: Counters::default(), key_wait_frames: 0 }
+    }
+
+    pub fn cpu_hz(&self) -> u64 {
+        self.cpu_hz
+    }
+
+    // Take over a speed chosen elsewhere (a speed slider, a new ROM's
+    // saved settings) and measure from the next frame
+    pub fn set_cpu_hz(&mut self, cpu_hz: u64, chip8: &Chip8) {
+        self.cpu_hz = cpu_hz;
+        self.start = Counters::of(chip8);
+        self.key_wait_frames = 0;
+    }
+
+    // Call once per 60Hz frame. Returns the new speed in Hz when it changes.
+    pub fn observe_frame(&mut self, chip8: &Chip8) -> Option<u64> {
+        let now: Counters = Counters::of(chip8);
+
+        // The stats start over when the ROM is reloaded
+        if now.frames < self.start.frames || now.instructions < self.start.instructions {
+            self.start = now;
+            self.key_wait_frames = 0;
+            return None;
+        }
+
+        if chip8.waiting_for_key().is_some() {
+            self.key_wait_frames += 1;
+        }
+
+        let frames: u64 = now.frames - self.start.frames;
+        if frames < WINDOW_FRAMES {
+            return None;
+        }
+        let window: Counters = Counters {
+            frames,
+            instructions: now.instructions - self.start.instructions,
+            timer_reads: now.timer_reads - self.start.timer_reads,
+            timer_writes: now.timer_writes - self.start.timer_writes,
+        };
+        let key_wait_frames: u64 = std::mem::take(&mut self.key_wait_frames);
+        self.start = now;
+
+        if window.instructions == 0 || key_wait_frames * 2 > window.frames {
+            return None;
+        }
+        if window.timer_writes == 0 {
+            return None;
+        }
+
+        // Share of the window spent in FX0A, plus the share of the rest spent
+        // polling the delay timer. A game reading the timer once a frame is
+        // not waiting on it.
+        let key_wait: f64 = key_wait_frames as f64 / window.frames as f64;
+        let polling: u64 = window.timer_reads.saturating_sub(window.frames) * POLL_LOOP_CYCLES;
+        let polling: f64 = polling.min(window.instructions) as f64 / window.instructions as f64;
+        let idle: f64 = key_wait + (1.0 - key_wait) * polling;
+
+        let per_frame: u64 = self.cpu_hz / TIMER_HZ;
+        let per_frame: u64 = if idle < STARVED_IDLE {
+            per_frame + (per_frame / 4).max(1)
+        } else if idle > SPARE_IDLE {
+            per_frame.saturating_sub((per_frame / 8).max(1))
+        } else {
+            return None;
+        };
+
+        let cpu_hz: u64 = per_frame.clamp(MIN_PER_FRAME, MAX_PER_FRAME) * TIMER_HZ;
+        if cpu_hz == self.cpu_hz {
+            return None;
+        }
+        self.cpu_hz = cpu_hz;
+        Some(cpu_hz)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 6001: V0 = 1; F015: delay = 1; 6100: V1 = 0; 7101 + 31ww + 1206: count
+    // V1 up to `work`; F207 + 3200 + 120C: wait out the delay; 1202: again
+    fn timer_paced(work: u8) -> Vec<u8> {
+        vec![
+            0x60, 0x01, 0xF0, 0x15, 0x61, 0x00, 0x71, 0x01, 0x31, work, 0x12, 0x06, 0xF2, 0x07, 0x32, 0x00, 0x12,
+            0x0C, 0x12, 0x02,
+        ]
+    }
+
+    // Run one measuring window the way the real-time loop does: `cpu_hz`
+    // worth of cycles a frame, or a single keypad poll while FX0A waits
+    fn run_window(rom: &[u8], cpu_hz: u64) -> Option<u64> {
+        let mut chip8: Chip8 = Chip8::new();
+        chip8.load_rom(rom).unwrap();
+        let mut tuner: SpeedTuner = SpeedTuner::new(cpu_hz);
+
+        let mut changed: Option<u64> = None;
+        for _ in 0..WINDOW_FRAMES {
+            if chip8.waiting_for_key().is_some() {
+                chip8.cycle();
+            } else {
+                for _ in 0..cpu_hz / TIMER_HZ {
+                    chip8.cycle();
+                }
+            }
+            chip8.tick_timers();
+            changed = changed.or(tuner.observe_frame(&chip8));
+        }
+
+        changed
+    }
+
+    #[test]
+    fn starved_rom_speeds_up() {
+        // ~190 cycles of work a frame never fit in 11; a quarter more is 13
+        assert_eq!(run_window(&timer_paced(60), 660), Some(780));
+    }
+
+    #[test]
+    fn rom_with_spare_time_slows_down() {
+        // ~10 cycles of work a frame, then 40 spent spinning on the delay
+        // timer; an eighth less is 44
+        assert_eq!(run_window(&timer_paced(2), 3000), Some(2640));
+    }
+
+    #[test]
+    fn menu_window_leaves_speed_alone() {
+        // 6001: V0 = 1; F015: delay = 1; F10A: wait for a key
+        assert_eq!(run_window(&[0x60, 0x01, 0xF0, 0x15, 0xF1, 0x0A], 660), None);
+    }
+
+    #[test]
+    fn rom_paced_by_instruction_count_leaves_speed_alone() {
+        // 7001: V0 += 1; 1200: again
+        assert_eq!(run_window(&[0x70, 0x01, 0x12, 0x00], 660), None);
+    }
+
+    #[test]
+    fn speed_stays_within_bounds() {
+        assert_eq!(run_window(&timer_paced(250), MAX_PER_FRAME * TIMER_HZ), None);
+    }
+}
@@ -0,0 +1,91 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Batch Runs
+// Runs a whole ROM collection headlessly across worker threads,
+// each ROM on its own machine, and gathers every compatibility
+// result, statistics and coverage figure into one report. Results
+// come back in input order however the threads were scheduled.
+// ───────────────────────────────────────────────────────────────
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::chip8::quirks::Quirks;
+use crate::compat::{self, Column, CompatResult};
+
+// Run every (name, rom) pair for `frames` frames on up to `jobs` threads
+pub fn run_batch(
+    roms: &[(String, Vec<u8>)],
+    seed: u64,
+    quirks: Quirks,
+    frames: u64,
+    jobs: usize,
+) -> Vec<CompatResult> {
+    let next: AtomicUsize = AtomicUsize::new(0);
+    let slots: Mutex<Vec<Option<CompatResult>>> = Mutex::new(roms.iter().map(|_| None).collect());
+
+    // Workers claim ROMs one at a time, so a slow ROM never holds up a queue
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, roms.len().max(1)) {
+            scope.spawn(|| loop {
+                let index: usize = next.fetch_add(1, Ordering::Relaxed);
+                let Some((name, rom)) = roms.get(index) else {
+                    break;
+                };
+
+                let result: CompatResult = compat::run_rom(name, rom, seed, quirks, frames);
+
+                if let Ok(mut slots) = slots.lock() {
+                    slots[index] = Some(result);
+                }
+            });
+        }
+    });
+
+    slots.into_inner().unwrap_or_default().into_iter().flatten().collect()
+}
+
+// ===============================================================
+// Report
+// ===============================================================
+
+// Statistics and coverage columns added to the compatibility table
+const COLUMNS: [Column; 4] = [
+    ("CYCLES", 10, |result| result.stats.cycles.to_string()),
+    ("SPRITES", 9, |result| result.stats.sprites_drawn.to_string()),
+    ("FORMS", 7, |result| result.stats.opcode_counts.len().to_string()),
+    ("COVER", 7, |result| match result.rom_len {
+        0 => "-".to_string(),
+        len => format!("{}%", result.bytes_executed * 100 / len),
+    }),
+];
+
+// The compatibility table with statistics and coverage, a summary line,
+// then how many ROMs used each opcode form
+pub fn write_report<W: Write>(results: &[CompatResult], output: &mut W) -> io::Result<()> {
+    compat::write_rows(results, &COLUMNS, output)?;
+
+    let cycles: u64 = results.iter().map(|result| result.stats.cycles).sum();
+    writeln!(output)?;
+    writeln!(output, "{}, {} cycles in total", compat::summary(results), cycles)?;
+
+    // (ROMs that executed the form, executions across all of them)
+    let mut forms: BTreeMap<&'static str, (usize, u64)> = BTreeMap::new();
+    for result in results {
+        for (&pattern, &count) in result.stats.opcode_counts.iter() {
+            let entry: &mut (usize, u64) = forms.entry(pattern).or_default();
+            entry.0 += 1;
+            entry.1 += count;
+        }
+    }
+
+    writeln!(output)?;
+    writeln!(output, "{:<8}{:>6}{:>14}", "OPCODE", "ROMS", "EXECUTED")?;
+    for (pattern, (roms, executed)) in forms {
+        writeln!(output, "{:<8}{:>6}{:>14}", pattern, roms, executed)?;
+    }
+
+    Ok(())
+}
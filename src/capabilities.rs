@@ -0,0 +1,114 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Capabilities
+// What this build of the emulator supports: core version, machine
+// profiles, quirks, memory size and the frontends compiled in. Tools
+// and files that depend on any of these can check compatibility up
+// front instead of failing halfway through a run.
+// ───────────────────────────────────────────────────────────────
+
+use std::io::{self, Write};
+
+use crate::chip8::constants::MEMORY_SIZE;
+use crate::chip8::quirks::Quirks;
+use crate::conformance::PROFILES;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    // Crate version of the core
+    pub version: &'static str,
+
+    // Machine profile names, as the conformance scorecard lists them
+    pub profiles: Vec<&'static str>,
+
+    // Quirk names, as in Quirks
+    pub quirks: Vec<&'static str>,
+
+    // Bytes of addressable RAM
+    pub max_memory: usize,
+
+    // Savestate format version; None while there are no savestates
+    pub savestate_version: Option<u32>,
+
+    // Cargo features of this build, frontends first
+    pub features: Vec<&'static str>,
+}
+
+impl Capabilities {
+
+    // Capabilities of the running build
+    pub fn current() -> Self {
+        let features: [(&'static str, bool); 9] = [
+            ("sdl", cfg!(feature = "sdl")),
+            ("minifb", cfg!(feature = "minifb")),
+            ("wgpu", cfg!(feature = "wgpu")),
+            ("gui", cfg!(feature = "gui")),
+            ("terminal", cfg!(feature = "terminal")),
+            ("tui", cfg!(feature = "tui")),
+            ("gamepad", cfg!(feature = "gamepad")),
+            ("testing", cfg!(feature = "testing")),
+            ("tracing", cfg!(feature = "tracing")),
+        ];
+
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            profiles: PROFILES.iter().map(|profile| profile.name).collect(),
+            quirks: Quirks::NAMES.to_vec(),
+            max_memory: MEMORY_SIZE,
+            savestate_version: None,
+            features: features.iter().filter(|&&(_, enabled)| enabled).map(|&(name, _)| name).collect(),
+        }
+    }
+
+    pub fn has_profile(&self, name: &str) -> bool {
+        self.profiles.iter().any(|profile| profile.eq_ignore_ascii_case(name))
+    }
+
+    pub fn has_quirk(&self, name: &str) -> bool {
+        self.quirks.contains(&name)
+    }
+
+    pub fn has_feature(&self, name: &str) -> bool {
+        self.features.contains(&name)
+    }
+
+    // Everything in `required` this build lacks, described one per entry;
+    // empty when it can run whatever asked
+    pub fn missing(&self, required: &Capabilities) -> Vec<String> {
+        let mut missing: Vec<String> = Vec::new();
+
+        missing.extend(required.profiles.iter().filter(|name| !self.has_profile(name)).map(|name| format!("profile {}", name)));
+        missing.extend(required.quirks.iter().filter(|name| !self.has_quirk(name)).map(|name| format!("quirk {}", name)));
+        missing.extend(required.features.iter().filter(|name| !self.has_feature(name)).map(|name| format!("feature {}", name)));
+
+        if required.max_memory > self.max_memory {
+            missing.push(format!("{} bytes of memory (have {})", required.max_memory, self.max_memory));
+        }
+        if let Some(version) = required.savestate_version
+            && self.savestate_version != Some(version)
+        {
+            missing.push(format!("savestate version {}", version));
+        }
+
+        missing
+    }
+
+    pub fn write_json<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        writeln!(output, "{{")?;
+        writeln!(output, "  \"version\": \"{}\",", self.version)?;
+        writeln!(output, "  \"profiles\": [{}],", quoted_list(&self.profiles))?;
+        writeln!(output, "  \"quirks\": [{}],", quoted_list(&self.quirks))?;
+        writeln!(output, "  \"max_memory\": {},", self.max_memory)?;
+
+        match self.savestate_version {
+            Some(version) => writeln!(output, "  \"savestate_version\": {},", version)?,
+            None => writeln!(output, "  \"savestate_version\": null,")?,
+        }
+
+        writeln!(output, "  \"features\": [{}]", quoted_list(&self.features))?;
+        writeln!(output, "}}")
+    }
+}
+
+fn quoted_list(names: &[&str]) -> String {
+    names.iter().map(|name| format!("\"{}\"", name)).collect::<Vec<String>>().join(", ")
+}
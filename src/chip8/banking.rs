@@ -0,0 +1,181 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — ROM Banking
+// Experimental peripheral for prototyping homebrew larger than the
+// 3.5KB of program space. The ROM's first 3KB sit at 0x200..0xE00 as
+// usual; the rest is cut into 512-byte banks, any one of which is
+// mapped into 0xE00..0x1000. Like the host ports, a before-hook
+// watches the bank register ahead of every instruction, so a bank
+// the ROM selects is in place for its next instruction. Off unless
+// attached; no real CHIP-8 has it.
+//
+//   0x1D0  bank select: write a bank number to map it in; numbers
+//          past the last bank are ignored and read back the current one
+//   0x1D1  last bank number
+//   0x1D2..0x1E0  reserved, left alone
+//
+// Banks are ROM: bytes the program writes into the window last only
+// until the next switch. Resets map bank 0 back in.
+// ───────────────────────────────────────────────────────────────
+
+use crate::chip8::constants::{MEMORY_SIZE, PROGRAM_START};
+use crate::chip8::cpu::Chip8;
+use crate::chip8::error::Chip8Error;
+use crate::chip8::hooks::{HookAction, HookId};
+
+// Bank register address and the number of bytes banking reserves
+pub const BANK_SELECT: u16 = 0x1D0;
+pub const BANKING_LEN: usize = 16;
+
+// Where banks are mapped in, and how large each one is
+pub const WINDOW_START: u16 = 0xE00;
+pub const BANK_SIZE: usize = MEMORY_SIZE - WINDOW_START as usize;
+
+// ROM bytes that are always mapped, below the window
+const FIXED_LEN: usize = (WINDOW_START - PROGRAM_START) as usize;
+
+// Largest ROM the bank register can address
+pub const MAX_BANKED_ROM: usize = FIXED_LEN + 256 * BANK_SIZE;
+
+const LAST_BANK: usize = BANK_SELECT as usize + 1;
+
+// Loads ROMs with their banks, replacing the banks of the previous one
+#[derive(Default)]
+pub struct Banking {
+    hook: Option<HookId>,
+}
+
+impl Banking {
+
+    // Load `rom` into `chip8` with bank 0 mapped in. A ROM larger than
+    // MAX_BANKED_ROM leaves the machine and its banks as they were.
+    pub fn load(&mut self, chip8: &mut Chip8, rom: &[u8]) -> Result<(), Chip8Error> {
+        if rom.len() > MAX_BANKED_ROM {
+            return Err(Chip8Error::TooLarge { len: rom.len(), max: MAX_BANKED_ROM });
+        }
+
+        if let Some(id) = self.hook.take() {
+            chip8.remove_hook(id);
+        }
+
+        let mut banks: Vec<[u8; BANK_SIZE]> = rom
+            .get(FIXED_LEN..)
+            .unwrap_or_default()
+            .chunks(BANK_SIZE)
+            .map(|chunk| {
+                let mut bank: [u8; BANK_SIZE] = [0; BANK_SIZE];
+                bank[..chunk.len()].copy_from_slice(chunk);
+                bank
+            })
+            .collect();
+        if banks.is_empty() {
+            banks.push([0; BANK_SIZE]);
+        }
+
+        chip8.load_rom(&rom[..rom.len().min(FIXED_LEN + BANK_SIZE)])?;
+        self.hook = Some(attach(chip8, banks));
+        Ok(())
+    }
+}
+
+fn attach(chip8: &mut Chip8, banks: Vec<[u8; BANK_SIZE]>) -> HookId {
+    let window: usize = WINDOW_START as usize;
+    let last_bank: u8 = (banks.len() - 1) as u8;
+    let mut mapped: u8 = 0;
+
+    chip8.on_opcode(0, 0, move |chip8, _| {
+        let select: u8 = chip8.memory[BANK_SELECT as usize];
+        if select != mapped {
+            match banks.get(select as usize) {
+                Some(bank) => {
+                    chip8.memory[window..].copy_from_slice(bank);
+                    mapped = select;
+                }
+                None => chip8.memory[BANK_SELECT as usize] = mapped,
+            }
+        }
+
+        chip8.memory[LAST_BANK] = last_bank;
+        HookAction::Continue
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Fixed program followed by three banks, each filled with its number
+    fn banked_rom(program: &[u8]) -> Vec<u8> {
+        let mut rom: Vec<u8> = program.to_vec();
+        rom.resize(FIXED_LEN, 0);
+        for bank in 0..3 {
+            rom.extend([bank; BANK_SIZE]);
+        }
+        rom
+    }
+
+    fn window(chip8: &Chip8) -> &[u8] {
+        &chip8.memory[WINDOW_START as usize..]
+    }
+
+    #[test]
+    fn selected_bank_is_mapped_for_the_next_instruction() {
+        // A1D0: I = bank select; 6002: V0 = 2; F055: select bank 2
+        let mut chip8: Chip8 = Chip8::new();
+        Banking::default().load(&mut chip8, &banked_rom(&[0xA1, 0xD0, 0x60, 0x02, 0xF0, 0x55, 0x12, 0x06])).unwrap();
+
+        for _ in 0..3 {
+            chip8.cycle();
+        }
+        assert!(window(&chip8).iter().all(|&byte| byte == 0));
+        assert_eq!(chip8.memory[LAST_BANK], 2);
+
+        chip8.cycle();
+        assert!(window(&chip8).iter().all(|&byte| byte == 2));
+    }
+
+    #[test]
+    fn missing_bank_is_ignored() {
+        // A1D0: I = bank select; 6001: V0 = 1; F055: select bank 1;
+        // 6009: V0 = 9; F055: select bank 9
+        let program: [u8; 12] = [0xA1, 0xD0, 0x60, 0x01, 0xF0, 0x55, 0x60, 0x09, 0xF0, 0x55, 0x12, 0x0A];
+        let mut chip8: Chip8 = Chip8::new();
+        Banking::default().load(&mut chip8, &banked_rom(&program)).unwrap();
+
+        for _ in 0..6 {
+            chip8.cycle();
+        }
+        assert_eq!(chip8.memory[BANK_SELECT as usize], 1);
+        assert!(window(&chip8).iter().all(|&byte| byte == 1));
+    }
+
+    #[test]
+    fn reset_maps_bank_zero_back_in() {
+        let program: [u8; 8] = [0xA1, 0xD0, 0x60, 0x02, 0xF0, 0x55, 0x12, 0x06];
+        let mut chip8: Chip8 = Chip8::new();
+        Banking::default().load(&mut chip8, &banked_rom(&program)).unwrap();
+        for _ in 0..4 {
+            chip8.cycle();
+        }
+
+        chip8.reset();
+        chip8.cycle();
+        assert!(window(&chip8).iter().all(|&byte| byte == 0));
+
+        // Selecting bank 2 again after the reset still maps it
+        for _ in 0..3 {
+            chip8.cycle();
+        }
+        assert!(window(&chip8).iter().all(|&byte| byte == 2));
+    }
+
+    #[test]
+    fn loading_another_rom_replaces_the_banks() {
+        let mut chip8: Chip8 = Chip8::new();
+        let mut banking: Banking = Banking::default();
+        banking.load(&mut chip8, &banked_rom(&[0x12, 0x00])).unwrap();
+        banking.load(&mut chip8, &[0x12, 0x00]).unwrap();
+
+        chip8.cycle();
+        assert_eq!(chip8.memory[LAST_BANK], 0);
+    }
+}
@@ -60,5 +60,9 @@ pub const NUM_KEYS: usize = 16;
 // TIMERS
 // =========================
 
+pub const CPU_HZ: u64 = 700;
 pub const TIMER_HZ: u64 = 60;
 
+// Instructions executed per 60Hz frame in headless runs
+pub const CYCLES_PER_FRAME: u64 = CPU_HZ / TIMER_HZ;
+
@@ -3,7 +3,19 @@
 // Represents the complete state of the CHIP-8 virtual machine.
 // ───────────────────────────────────────────────────────────────
 
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+
 use crate::chip8::constants::*;
+use crate::chip8::display::{DirtyRows, DisplayBuffer, BLANK_DISPLAY};
+use crate::chip8::hash::Fnv1a;
+use crate::chip8::error::Chip8Error;
+use crate::chip8::extensions::Extensions;
+use crate::chip8::hooks::{HookAction, HookId, Hooks};
+use crate::chip8::instruction::{DecodedFields, Instruction};
+use crate::chip8::quirks::Quirks;
+use crate::chip8::schedule::{Schedule, ScheduleId};
+use crate::chip8::stats::{KeyWait, Stats};
 
 // ===============================================================
 // Full CHIP-8 machine state
@@ -28,8 +40,21 @@ pub struct Chip8 {
     // Stack pointer
     pub sp: u8,
 
-    // 64x32 monochrome display buffer
-    pub display: [[bool; DISPLAY_WIDTH]; DISPLAY_HEIGHT],
+    // 64x32 monochrome display buffer (back buffer: DXYN draws land here)
+    pub display: DisplayBuffer,
+
+    // Last completed frame, latched from `display` on every 60Hz tick
+    front_buffer: DisplayBuffer,
+
+    // Set when a new frame has been latched and not yet taken
+    frame_ready: bool,
+
+    // Rows of `display` that DXYN or 00E0 wrote since the last latch.
+    // Writing `display` directly bypasses this; mark_display_dirty covers it.
+    dirty_rows: DirtyRows,
+
+    // Rows of the front buffer that latches changed since the last take_dirty_rows
+    front_dirty_rows: DirtyRows,
 
     // 16-key hexadecimal keypad state
     pub keys: [bool; NUM_KEYS],
@@ -40,32 +65,30 @@ pub struct Chip8 {
 
     // FX0A key-wait state: Some(x) means waiting for a key, storing into VX
     waiting_for_key: Option<u8>,
-}
 
-// ===============================================================
-// Decoded Opcode Representation
-// ===============================================================
+    // Seeded RNG backing CXNN, so runs are reproducible from a seed
+    rng: StdRng,
 
-pub struct DecodedFields {
-    pub first_nibble: u8,
-    pub x: u8,
-    pub y: u8,
-    pub n: u8,
-    pub nn: u8,
-    pub nnn: u16,
-}
+    // Seed of `rng`, so a reset replays the same CXNN sequence
+    seed: u64,
 
-impl DecodedFields {
-    pub fn new(opcode: u16) -> Self {
-        Self {
-            first_nibble: ((opcode & 0xF000) >> 12) as u8,
-            x:            ((opcode & 0x0F00) >> 8)  as u8,
-            y:            ((opcode & 0x00F0) >> 4)  as u8,
-            n:            (opcode & 0x000F)         as u8,
-            nn:           (opcode & 0x00FF)         as u8,
-            nnn:           opcode & 0x0FFF,
-        }
-    }
+    // Copy of the loaded ROM, restored into memory on reset
+    rom: Vec<u8>,
+
+    // Platform behaviour variants
+    pub quirks: Quirks,
+
+    // Execution counters for the session
+    pub stats: Stats,
+
+    // Callbacks run around matching opcodes
+    hooks: Hooks,
+
+    // One-shot callbacks at exact cycle or frame counts
+    schedule: Schedule,
+
+    // User-defined instructions in otherwise-invalid opcode space
+    extensions: Extensions,
 }
 
 // ===============================================================
@@ -78,10 +101,22 @@ impl Default for Chip8 {
     }
 }
 
+// Machines are built and run on worker threads (see `batch`), so a field
+// that is not Send must not slip in
+const _: () = {
+    const fn assert_send<T: Send>() {}
+    assert_send::<Chip8>();
+};
+
 impl Chip8 {
 
-    // Initialize a new Chip8 instance with default state
+    // Initialize a new Chip8 instance with default state and a random seed
     pub fn new() -> Self {
+        Self::with_seed(rand::random())
+    }
+
+    // Initialize a new Chip8 instance whose CXNN sequence is fixed by `seed`
+    pub fn with_seed(seed: u64) -> Self {
         let mut chip8: Chip8 = Self {
             memory: [0; MEMORY_SIZE],
             v: [0; NUM_REGISTERS],
@@ -89,11 +124,23 @@ impl Chip8 {
             pc: PROGRAM_START,
             stack: [0; STACK_SIZE],
             sp: 0,
-            display: [[false; DISPLAY_WIDTH]; DISPLAY_HEIGHT],
+            display: BLANK_DISPLAY,
+            front_buffer: BLANK_DISPLAY,
+            frame_ready: false,
+            dirty_rows: DirtyRows::default(),
+            front_dirty_rows: DirtyRows::ALL,
             keys: [false; NUM_KEYS],
             delay_timer: 0,
             sound_timer: 0,
             waiting_for_key: None,
+            rng: StdRng::seed_from_u64(seed),
+            seed,
+            rom: Vec::new(),
+            quirks: Quirks::default(),
+            stats: Stats::default(),
+            hooks: Hooks::default(),
+            schedule: Schedule::default(),
+            extensions: Extensions::default(),
         };
 
         for (index, &byte) in FONT_SET.iter().enumerate() {
@@ -103,95 +150,426 @@ impl Chip8 {
         chip8
     }
 
-    // Load a ROM into memory starting at 0x200
-    pub fn load_rom(&mut self, data: &[u8]) {
-        let start: usize = PROGRAM_START as usize;
-        let end: usize = start + data.len();
+    // Load a ROM into memory starting at 0x200 and reset the machine.
+    // Loading again later (e.g. after re-reading the file) is a hard reset.
+    // A ROM that does not fit in program space leaves the machine as it was.
+    pub fn load_rom(&mut self, data: &[u8]) -> Result<(), Chip8Error> {
+        let max: usize = MEMORY_SIZE - PROGRAM_START as usize;
+        if data.len() > max {
+            return Err(Chip8Error::TooLarge { len: data.len(), max });
+        }
 
-        if end > MEMORY_SIZE {
-            panic!("ROM too large to fit in memory");
+        self.rom = data.to_vec();
+        self.reset();
+        Ok(())
+    }
+
+    // Soft reset: restore power-on state (RAM, registers, stack, display,
+    // timers, keypad, RNG) with the loaded ROM back in place. Quirks, hooks,
+    // extensions, scheduled callbacks and session stats are kept.
+    pub fn reset(&mut self) {
+        self.memory = [0; MEMORY_SIZE];
+        for (index, &byte) in FONT_SET.iter().enumerate() {
+            self.memory[FONT_START as usize + index] = byte;
         }
 
-        self.memory[start..end].copy_from_slice(data);
+        let start: usize = PROGRAM_START as usize;
+        self.memory[start..start + self.rom.len()].copy_from_slice(&self.rom);
+
+        self.v = [0; NUM_REGISTERS];
+        self.i = 0;
+        self.pc = PROGRAM_START;
+        self.stack = [0; STACK_SIZE];
+        self.sp = 0;
+        self.display = BLANK_DISPLAY;
+        self.front_buffer = BLANK_DISPLAY;
+        self.frame_ready = false;
+        self.dirty_rows = DirtyRows::default();
+        self.front_dirty_rows = DirtyRows::ALL;
+        self.keys = [false; NUM_KEYS];
+        self.delay_timer = 0;
+        self.sound_timer = 0;
+        self.waiting_for_key = None;
+        self.rng = StdRng::seed_from_u64(self.seed);
     }
 
-    // Decrement timers (should be called at 60Hz externally)
+    // Decrement timers (should be called at 60Hz externally).
+    // This is also the frame boundary: the back buffer is latched for presentation.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
     pub fn tick_timers(&mut self) {
     if self.delay_timer > 0 {
         self.delay_timer -= 1;
     }
 
+    self.stats.record_sound_frame(self.sound_timer > 0);
+
     if self.sound_timer > 0 {
         self.sound_timer -= 1;
     }
+
+    self.latch_frame();
+
+    for callback in self.schedule.take_due_frames(self.stats.frames) {
+        callback(self);
+    }
 }
 
+    // Put `frame` on the display and latch it as the completed frame, without
+    // running the timers. For host screens shown while the machine is stopped.
+    pub fn show_frame(&mut self, frame: &DisplayBuffer) {
+        self.display = *frame;
+        self.mark_display_dirty();
+        self.latch_frame();
+    }
+
+    // Copy the display to the front buffer for take_frame; at every 60Hz tick,
+    // and after each draw with the immediate_display quirk
+    fn latch_frame(&mut self) {
+        // Only rows that were written can differ, and only those that do count
+        for row in self.dirty_rows.iter() {
+            if self.display[row] != self.front_buffer[row] {
+                self.front_dirty_rows.mark(row);
+            }
+        }
+        self.dirty_rows = DirtyRows::default();
+
+        self.front_buffer = self.display;
+        self.frame_ready = true;
+    }
+
+    // Seed the machine was created with
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    // Return the frame completed at the last 60Hz tick, once per frame.
+    // Frontends present this instead of `display` to never show a half-drawn sprite.
+    pub fn take_frame(&mut self) -> Option<&DisplayBuffer> {
+        if !self.frame_ready {
+            return None;
+        }
+
+        self.frame_ready = false;
+        Some(&self.front_buffer)
+    }
+
+    // Last completed frame, whether or not it has already been taken
+    pub fn frame(&self) -> &DisplayBuffer {
+        &self.front_buffer
+    }
+
+    // Rows of the completed frame that changed since the last call, so a
+    // frontend can redraw just those. Everything is dirty after a reset.
+    pub fn take_dirty_rows(&mut self) -> DirtyRows {
+        std::mem::take(&mut self.front_dirty_rows)
+    }
+
+    // For code that writes `display` directly: have the next latch compare
+    // every row instead of only the ones DXYN and 00E0 touched
+    pub fn mark_display_dirty(&mut self) {
+        self.dirty_rows = DirtyRows::ALL;
+    }
+
+    // Register FX0A is waiting to fill, if the machine is blocked on a key press
+    pub fn waiting_for_key(&self) -> Option<u8> {
+        self.waiting_for_key
+    }
+
+    // Raw opcode at PC without fetching it, if PC leaves room for one
+    pub fn opcode_at_pc(&self) -> Option<u16> {
+        let pc: usize = self.pc as usize;
+        self.memory.get(pc..pc + 2).map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    // True while no instruction can make progress: blocked in FX0A, or halted
+    // on a jump to itself. Only timers and input change anything from here.
+    pub fn is_idle(&self) -> bool {
+        self.waiting_for_key.is_some() || self.is_halted()
+    }
+
+    // True when the program has ended by jumping to itself
+    pub fn is_halted(&self) -> bool {
+        self.opcode_at_pc()
+            .is_some_and(|opcode| Instruction::decode(opcode) == Instruction::Jump { nnn: self.pc })
+    }
+
+    // Run one 60Hz frame: CYCLES_PER_FRAME instructions followed by a timer tick
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+    pub fn run_frame(&mut self) {
+        for _ in 0..CYCLES_PER_FRAME {
+            self.cycle();
+        }
+
+        self.tick_timers();
+    }
+
+    // Hash of the complete machine state (FNV-1a, stable across builds)
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher: Fnv1a = Fnv1a::new();
+
+        hasher.write(&self.memory);
+        hasher.write(&self.v);
+        hasher.write_u16(self.i);
+        hasher.write_u16(self.pc);
+        for &address in self.stack.iter() {
+            hasher.write_u16(address);
+        }
+        hasher.write_u8(self.sp);
+        for row in self.display.iter() {
+            for &pixel in row.iter() {
+                hasher.write_u8(pixel as u8);
+            }
+        }
+        for &pressed in self.keys.iter() {
+            hasher.write_u8(pressed as u8);
+        }
+        hasher.write_u8(self.delay_timer);
+        hasher.write_u8(self.sound_timer);
+        hasher.write_u8(self.waiting_for_key.unwrap_or(0xFF));
+
+        hasher.finish()
+    }
+
+    // Hash of the display buffer alone, for comparing visible output
+    pub fn display_hash(&self) -> u64 {
+        let mut hasher: Fnv1a = Fnv1a::new();
+
+        for row in self.display.iter() {
+            for &pixel in row.iter() {
+                hasher.write_u8(pixel as u8);
+            }
+        }
+
+        hasher.finish()
+    }
+
     // ===========================================================
     // Fetch Stage
     // ===========================================================
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
     pub fn fetch(&mut self) -> u16 {
-        let high_byte: u16 = self.memory[self.pc as usize] as u16;
-        let low_byte: u16  = self.memory[(self.pc + 1) as usize] as u16;
+        let high_byte: u16 = self.memory[wrap(self.pc as usize)] as u16;
+        let low_byte: u16  = self.memory[wrap(self.pc as usize + 1)] as u16;
 
         let opcode: u16 = (high_byte << 8) | low_byte;
 
-        self.pc += 2;
+        self.pc = wrap(self.pc as usize + 2) as u16;
 
         opcode
     }
 
+    // Address of the instruction a before-hook is running for. PC already
+    // points past it, and may have wrapped to the start of memory.
+    pub fn instruction_address(&self) -> u16 {
+        wrap(self.pc as usize + MEMORY_SIZE - 2) as u16
+    }
+
     // ===========================================================
     // Execution Cycle
     // ===========================================================
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
     pub fn cycle(&mut self) {
+        self.step();
+
+        for callback in self.schedule.take_due_cycles(self.stats.cycles) {
+            callback(self);
+        }
+    }
+
+    // One instruction, or one more cycle of an FX0A wait
+    fn step(&mut self) {
+        self.stats.cycles += 1;
+
         // FX0A — block until any key is pressed, then store it in VX
         if let Some(vx) = self.waiting_for_key {
+            self.stats.current_key_wait += 1;
+
             for (key_index, &pressed) in self.keys.iter().enumerate() {
                 if pressed {
                     self.v[vx as usize] = key_index as u8;
                     self.waiting_for_key = None;
                 }
             }
+
+            if self.waiting_for_key.is_none() {
+                let frames: u64 = self.stats.frames - self.stats.current_key_wait_start;
+                self.stats.key_waits.push(KeyWait { cycles: self.stats.current_key_wait, frames });
+                self.stats.current_key_wait = 0;
+            }
             return;
         }
 
         let opcode: u16 = self.fetch();
-        let decoded: DecodedFields = DecodedFields::new(opcode);
-
-        match decoded.first_nibble {
-            
-            // System instructions (0x0NNN) and special cases
-            0x0 => {
-                match opcode {
-                    // Clear display
-                    0x00E0 => {
-                        self.display = [[false; DISPLAY_WIDTH]; DISPLAY_HEIGHT];
-                    }
-                    // Return from subroutine
-                    0x00EE => {
-                        if self.sp == 0 {
-                            eprintln!("Stack underflow on 0x00EE");
-                            return;
-                        }
-                        self.sp -= 1;
-                        self.pc = self.stack[self.sp as usize];
-                    }
-                    // 0x0NNN (call RCA 1802 program) — not used by modern ROMs, intentionally ignored
-                    _ => {
-                        eprintln!("Unknown opcode: {:#06X}", opcode);
-                    }
+        let instruction: Instruction = Instruction::decode(opcode);
+
+        self.stats.instructions += 1;
+        *self.stats.opcode_counts.entry(self.pattern_of(opcode)).or_insert(0) += 1;
+
+        if self.hooks.is_empty() {
+            self.execute(instruction);
+            return;
+        }
+
+        // Hooks get `&mut Chip8`, so run them from a detached copy of the registry
+        let mut hooks: Hooks = self.hooks.detach();
+
+        if hooks.run_before(self, opcode, instruction) == HookAction::Continue {
+            self.execute(instruction);
+        }
+        hooks.run_after(self, opcode, instruction);
+
+        hooks.absorb(std::mem::take(&mut self.hooks));
+        self.hooks = hooks;
+    }
+
+    // ===========================================================
+    // Opcode Hooks
+    // ===========================================================
+
+    // Run `hook` before every instruction whose opcode satisfies
+    // `opcode & mask == value`. Returning HookAction::Skip suppresses
+    // the built-in behaviour, letting the hook override the opcode.
+    pub fn on_opcode<F>(&mut self, value: u16, mask: u16, hook: F) -> HookId
+    where
+        F: FnMut(&mut Chip8, Instruction) -> HookAction + Send + 'static,
+    {
+        self.hooks.add_before(value, mask, Box::new(hook))
+    }
+
+    // Run `hook` after every matching instruction has executed
+    pub fn after_opcode<F>(&mut self, value: u16, mask: u16, hook: F) -> HookId
+    where
+        F: FnMut(&mut Chip8, Instruction) + Send + 'static,
+    {
+        self.hooks.add_after(value, mask, Box::new(hook))
+    }
+
+    // Unregister a hook. Hooks cannot remove themselves (or each other)
+    // while they are running; this returns false in that case, and an
+    // extension the hook backs keeps its mnemonic.
+    pub fn remove_hook(&mut self, id: HookId) -> bool {
+        let removed: bool = self.hooks.remove(id);
+        if removed {
+            self.extensions.remove(id);
+        }
+        removed
+    }
+
+    // ===========================================================
+    // Scheduled Callbacks
+    // ===========================================================
+
+    // Run `callback` once, right after the session's `cycle`-th cycle
+    // (`stats.cycles`). A moment already passed runs after the next cycle.
+    pub fn at_cycle<F>(&mut self, cycle: u64, callback: F) -> ScheduleId
+    where
+        F: FnOnce(&mut Chip8) + Send + 'static,
+    {
+        self.schedule.at_cycle(cycle, Box::new(callback))
+    }
+
+    // Run `callback` once, right after the session's `frame`-th timer tick
+    // (`stats.frames`). A moment already passed runs after the next tick.
+    pub fn at_frame<F>(&mut self, frame: u64, callback: F) -> ScheduleId
+    where
+        F: FnOnce(&mut Chip8) + Send + 'static,
+    {
+        self.schedule.at_frame(frame, Box::new(callback))
+    }
+
+    // Drop a callback that has not run yet; returns false if it already ran
+    pub fn cancel_scheduled(&mut self, id: ScheduleId) -> bool {
+        self.schedule.cancel(id)
+    }
+
+    // ===========================================================
+    // Custom Opcode Extensions
+    // ===========================================================
+
+    // Give otherwise-invalid opcodes matching `pattern` (e.g. "5XY1") a
+    // behaviour and a mnemonic (e.g. "SWAP VX, VY"). Fails if the pattern
+    // touches a built-in instruction or an existing extension. The returned
+    // id unregisters it again through `remove_hook`.
+    pub fn register_extension<F>(
+        &mut self,
+        pattern: &'static str,
+        mnemonic: &'static str,
+        mut handler: F,
+    ) -> Result<HookId, Chip8Error>
+    where
+        F: FnMut(&mut Chip8, DecodedFields) + Send + 'static,
+    {
+        let (value, mask) = self.extensions.claim(pattern)?;
+
+        let id: HookId = self.on_opcode(value, mask, move |chip8, instruction| {
+            if let Instruction::Invalid { opcode } = instruction {
+                handler(chip8, DecodedFields::new(opcode));
+            }
+            HookAction::Skip
+        });
+        self.extensions.add(pattern, mnemonic, value, mask, id);
+
+        Ok(id)
+    }
+
+    // Opcode pattern ("8XY4", or a registered extension's) used for stats
+    pub fn pattern_of(&self, opcode: u16) -> &'static str {
+        match self.extensions.find(opcode) {
+            Some(extension) => extension.pattern,
+            None => Instruction::decode(opcode).spec().pattern,
+        }
+    }
+
+    // Disassemble `opcode`, including registered extensions
+    pub fn disassemble(&self, opcode: u16) -> String {
+        match self.extensions.find(opcode) {
+            Some(extension) => extension.disassemble(opcode),
+            None => Instruction::decode(opcode).to_string(),
+        }
+    }
+
+    // ===========================================================
+    // Execute Stage
+    // ===========================================================
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+    pub fn execute(&mut self, instruction: Instruction) {
+        match instruction {
+
+            // 0x0NNN (call RCA 1802 program) — not used by modern ROMs, intentionally ignored
+            Instruction::Sys { nnn } => {
+                eprintln!("Unknown opcode: {:#06X}", nnn);
+            }
+
+            // Clear display
+            Instruction::ClearScreen => {
+                self.display = BLANK_DISPLAY;
+                self.dirty_rows = DirtyRows::ALL;
+
+                if self.quirks.immediate_display {
+                    self.latch_frame();
+                }
+            }
+
+            // Return from subroutine
+            Instruction::Return => {
+                if self.sp == 0 {
+                    eprintln!("Stack underflow on 0x00EE");
+                    return;
                 }
+                self.sp -= 1;
+                self.pc = self.stack[self.sp as usize];
             }
 
             // Jump to address NNN
-            0x1 => {
-                self.pc = decoded.nnn;
+            Instruction::Jump { nnn } => {
+                self.pc = nnn;
             }
 
             // Call subroutine at NNN
-            0x2 => {
+            Instruction::Call { nnn } => {
                 if self.sp as usize >= STACK_SIZE {
                     eprintln!("Stack overflow on 0x2NNN");
                     return;
@@ -200,164 +578,150 @@ impl Chip8 {
                 self.stack[self.sp as usize] = self.pc;
                 self.sp += 1;
 
-                self.pc = decoded.nnn;
+                self.pc = nnn;
             }
 
             // Skip next instruction if VX == NN
-            0x3 => {
-                if self.v[decoded.x as usize] == decoded.nn {
+            Instruction::SkipEqImm { x, nn } => {
+                if self.v[x as usize] == nn {
                     self.pc += 2;
                 }
             }
 
             // Skip next instruction if VX != NN
-            0x4 => {
-                if self.v[decoded.x as usize] != decoded.nn {
+            Instruction::SkipNeImm { x, nn } => {
+                if self.v[x as usize] != nn {
                     self.pc += 2;
                 }
             }
 
-            // Skip next instruction if VX == VY (only if N == 0)
-            0x5 => {
-                if decoded.n == 0 {
-                    if self.v[decoded.x as usize] == self.v[decoded.y as usize] {
-                        self.pc += 2;
-                    }
-                } else {
-                    eprintln!("Invalid opcode: {:#06X}", opcode);
+            // Skip next instruction if VX == VY
+            Instruction::SkipEqReg { x, y } => {
+                if self.v[x as usize] == self.v[y as usize] {
+                    self.pc += 2;
                 }
             }
 
             // Set VX to NN
-            0x6 => {
-
-                self.v[decoded.x as usize] = decoded.nn;
+            Instruction::LoadImm { x, nn } => {
+                self.v[x as usize] = nn;
             }
 
             // VX += NN (wrapping)
-            0x7 => {
-                self.v[decoded.x as usize] =
-                    self.v[decoded.x as usize].wrapping_add(decoded.nn);
+            Instruction::AddImm { x, nn } => {
+                self.v[x as usize] = self.v[x as usize].wrapping_add(nn);
             }
 
-            // Arithmetic and bitwise operations between VX and VY
-            0x8 => {
-                match decoded.n {
-
-                    // VX is set to the value of VY
-                    0x0 => {
-                        self.v[decoded.x as usize] =
-                            self.v[decoded.y as usize];
-                    }
-
-                    // VX is set to VX OR VY
-                    0x1 => {
-                        self.v[decoded.x as usize] |=
-                            self.v[decoded.y as usize];
-                    }
+            // VX is set to the value of VY
+            Instruction::Move { x, y } => {
+                self.v[x as usize] = self.v[y as usize];
+            }
 
-                    // VX is set to VX AND VY
-                    0x2 => {
-                        
-                        self.v[decoded.x as usize] &=
-                            self.v[decoded.y as usize];
-                    }
+            // VX is set to VX OR VY
+            Instruction::Or { x, y } => {
+                self.v[x as usize] |= self.v[y as usize];
+            }
 
-                    // VX is set to VX XOR VY
-                    0x3 => {
-                        
-                        self.v[decoded.x as usize] ^=
-                            self.v[decoded.y as usize];
-                    }
+            // VX is set to VX AND VY
+            Instruction::And { x, y } => {
+                self.v[x as usize] &= self.v[y as usize];
+            }
 
-                    // VX += VY, VF = carry
-                    0x4 => {
-                        let (result, carry) =
-                            self.v[decoded.x as usize]
-                                .overflowing_add(self.v[decoded.y as usize]);
+            // VX is set to VX XOR VY
+            Instruction::Xor { x, y } => {
+                self.v[x as usize] ^= self.v[y as usize];
+            }
 
-                        self.v[decoded.x as usize] = result;
-                        self.v[0xF] = if carry { 1 } else { 0 };
-                    }
+            // VX += VY, VF = carry
+            Instruction::AddReg { x, y } => {
+                let (result, carry) =
+                    self.v[x as usize].overflowing_add(self.v[y as usize]);
 
-                    // VX -= VY, VF = NOT borrow
-                    0x5 => {
-                        let (result, borrow) =
-                            self.v[decoded.x as usize]
-                                .overflowing_sub(self.v[decoded.y as usize]);
+                self.v[x as usize] = result;
+                self.v[0xF] = if carry { 1 } else { 0 };
+            }
 
-                        self.v[decoded.x as usize] = result;
-                        self.v[0xF] = if borrow { 0 } else { 1 };
-                    }
+            // VX -= VY, VF = NOT borrow
+            Instruction::Sub { x, y } => {
+                let (result, borrow) =
+                    self.v[x as usize].overflowing_sub(self.v[y as usize]);
 
-                    // VX >>= 1, VF = least significant bit before shift
-                    0x6 => {
-                        let lsb = self.v[decoded.x as usize] & 0x1;
-                        self.v[0xF] = lsb;
-                        self.v[decoded.x as usize] >>= 1;
-                    }
+                self.v[x as usize] = result;
+                self.v[0xF] = if borrow { 0 } else { 1 };
+            }
 
-                    // VX = VY - VX, VF = NOT borrow
-                    0x7 => {
-                        let (result, borrow) =
-                            self.v[decoded.y as usize]
-                                .overflowing_sub(self.v[decoded.x as usize]);
+            // VX >>= 1, VF = least significant bit before shift
+            Instruction::ShiftRight { x, .. } => {
+                let lsb = self.v[x as usize] & 0x1;
+                self.v[0xF] = lsb;
+                self.v[x as usize] >>= 1;
+            }
 
-                        self.v[decoded.x as usize] = result;
-                        self.v[0xF] = if borrow { 0 } else { 1 };
-                    }
+            // VX = VY - VX, VF = NOT borrow
+            Instruction::SubReversed { x, y } => {
+                let (result, borrow) =
+                    self.v[y as usize].overflowing_sub(self.v[x as usize]);
 
-                    // VX <<= 1, VF = most significant bit before shift
-                    0xE => {
-                        let msb: u8 = (self.v[decoded.x as usize] & 0x80) >> 7;
-                        self.v[0xF] = msb;
-                        self.v[decoded.x as usize] <<= 1;
-                    }
+                self.v[x as usize] = result;
+                self.v[0xF] = if borrow { 0 } else { 1 };
+            }
 
-                    _ => {
-                        eprintln!("Invalid 8XYN opcode: {:#06X}", opcode);
-                    }
-                }
+            // VX <<= 1, VF = most significant bit before shift
+            Instruction::ShiftLeft { x, .. } => {
+                let msb: u8 = (self.v[x as usize] & 0x80) >> 7;
+                self.v[0xF] = msb;
+                self.v[x as usize] <<= 1;
             }
 
-            // Skip next instruction if VX != VY (only if N == 0)
-            0x9 => {
-                if decoded.n == 0 {
-                    if self.v[decoded.x as usize] != self.v[decoded.y as usize] {
-                        self.pc += 2;
-                    }
-                } else {
-                    eprintln!("Invalid opcode: {:#06X}", opcode);
+            // Skip next instruction if VX != VY
+            Instruction::SkipNeReg { x, y } => {
+                if self.v[x as usize] != self.v[y as usize] {
+                    self.pc += 2;
                 }
             }
 
             // Set I to NNN
-            0xA => {
-                self.i = decoded.nnn;
+            Instruction::LoadIndex { nnn } => {
+                self.i = nnn;
             }
 
             // Jump to address NNN + V0
-            0xB => {
-                self.pc = decoded.nnn + self.v[0] as u16;
+            Instruction::JumpOffset { nnn } => {
+                self.pc = nnn + self.v[0] as u16;
             }
 
             // VX = random byte AND NN
-            0xC => {
-                let random: u8 = rand::random();
-                self.v[decoded.x as usize] = random & decoded.nn;
+            Instruction::Random { x, nn } => {
+                let random: u8 = self.rng.random();
+                self.v[x as usize] = random & nn;
             }
 
             // Display/draw sprite at (VX, VY) with height N
-            0xD => {
-                let x_pos: usize = self.v[decoded.x as usize] as usize;
-                let y_pos: usize = self.v[decoded.y as usize] as usize;
-                let height: usize = decoded.n as usize;
+            Instruction::Draw { x, y, n } => {
+                #[cfg(feature = "tracing")]
+                let _draw_span = tracing::trace_span!("draw").entered();
+
+                let x_pos: usize = self.v[x as usize] as usize;
+                let y_pos: usize = self.v[y as usize] as usize;
+                let height: usize = n as usize;
 
-                self.v[0xF] = 0;
+                let mut collided_rows: u8 = 0;
 
                 for row in 0..height {
+                    // Rows running past the bottom edge count as clipped;
+                    // under the row-count quirk they are not drawn either
+                    let row_clipped: bool = (y_pos % DISPLAY_HEIGHT) + row >= DISPLAY_HEIGHT;
+                    if self.quirks.collision_row_count && row_clipped {
+                        collided_rows += 1;
+                        continue;
+                    }
+
                     let sprite_byte: u8 =
-                        self.memory[(self.i + row as u16) as usize];
+                        self.memory[wrap(self.i as usize + row)];
+
+                    self.dirty_rows.mark((y_pos + row) % DISPLAY_HEIGHT);
+
+                    let mut row_collided: bool = false;
 
                     for bit in 0..8 {
                         let sprite_pixel: bool =
@@ -368,112 +732,366 @@ impl Chip8 {
                             let y: usize = (y_pos + row) % DISPLAY_HEIGHT;
 
                             if self.display[y][x] {
-                                self.v[0xF] = 1;
+                                row_collided = true;
                             }
 
                             self.display[y][x] ^= true;
                         }
                     }
+
+                    if row_collided {
+                        collided_rows += 1;
+                    }
+                }
+
+                self.v[0xF] = if self.quirks.collision_row_count {
+                    collided_rows
+                } else {
+                    (collided_rows > 0) as u8
+                };
+
+                self.stats.sprites_drawn += 1;
+                if collided_rows > 0 {
+                    self.stats.sprite_collisions += 1;
+                }
+
+                if self.quirks.immediate_display {
+                    self.latch_frame();
                 }
             }
 
-            // Key input instructions
-            0xE => {
-                let key: usize = self.v[decoded.x as usize] as usize;
+            // EX9E — Skip next instruction if key VX is pressed
+            Instruction::SkipKeyDown { x } => {
+                let key: usize = self.v[x as usize] as usize;
 
-                match decoded.nn {
-                    0x9E => {
-                        if key < NUM_KEYS && self.keys[key] {
-                            self.pc += 2;
-                        }
-                    }
-                    0xA1 => {
-                        if key < NUM_KEYS && !self.keys[key] {
-                            self.pc += 2;
-                        }
-                    }
-                    _ => {
-                        eprintln!("Invalid EX opcode: {:#06X}", opcode);
-                    }
+                if key < NUM_KEYS && self.keys[key] {
+                    self.pc += 2;
                 }
             }
 
-            // Miscellaneous instructions
-            0xF => {
-                match decoded.nn {
+            // EXA1 — Skip next instruction if key VX is not pressed
+            Instruction::SkipKeyUp { x } => {
+                let key: usize = self.v[x as usize] as usize;
 
-                    // FX07 — VX = delay_timer
-                    0x07 => {
-                        self.v[decoded.x as usize] = self.delay_timer;
-                    }
+                if key < NUM_KEYS && !self.keys[key] {
+                    self.pc += 2;
+                }
+            }
 
-                    // FX0A — Wait for key press, store key index in VX (blocking)
-                    0x0A => {
-                        if let Some(vx) = self.waiting_for_key {
-                            for (key_index, &pressed) in self.keys.iter().enumerate() {
-                                if pressed {
-                                    self.v[vx as usize] = key_index as u8;
-                                    self.waiting_for_key = None;
-                                    break;
-                                }
-                            }
-                            return;
-                        }
-                    }
+            // FX07 — VX = delay_timer
+            Instruction::LoadDelay { x } => {
+                self.v[x as usize] = self.delay_timer;
+            }
 
-                    // FX15 — delay_timer = VX
-                    0x15 => {
-                        self.delay_timer = self.v[decoded.x as usize];
-                    }
+            // FX0A — Wait for key press, store key index in VX (blocking)
+            Instruction::WaitKey { x } => {
+                self.waiting_for_key = Some(x);
+                self.stats.current_key_wait_start = self.stats.frames;
+            }
 
-                    // FX18 — sound_timer = VX
-                    0x18 => {
-                        self.sound_timer = self.v[decoded.x as usize];
-                    }
+            // FX15 — delay_timer = VX
+            Instruction::SetDelay { x } => {
+                self.delay_timer = self.v[x as usize];
+            }
 
-                    // FX1E — I += VX
-                    0x1E => {
-                        self.i = self.i.wrapping_add(self.v[decoded.x as usize] as u16);
-                    }
+            // FX18 — sound_timer = VX
+            Instruction::SetSound { x } => {
+                self.sound_timer = self.v[x as usize];
+            }
 
-                    // FX29 — Set I to font character location
-                    0x29 => {
-                        let digit: u16 = (self.v[decoded.x as usize] & 0x0F) as u16;
-                        self.i = FONT_START + digit * 5;
-                    }
+            // FX1E — I += VX
+            Instruction::AddIndex { x } => {
+                self.i = self.i.wrapping_add(self.v[x as usize] as u16);
+            }
 
-                    // FX33 — Store BCD representation of VX at I, I+1, I+2
-                    0x33 => {
-                        let value: u8 = self.v[decoded.x as usize];
+            // FX29 — Set I to font character location
+            Instruction::LoadFont { x } => {
+                let digit: u16 = (self.v[x as usize] & 0x0F) as u16;
+                self.i = FONT_START + digit * 5;
+            }
 
-                        self.memory[self.i as usize]     = value / 100;
-                        self.memory[self.i as usize + 1] = (value % 100) / 10;
-                        self.memory[self.i as usize + 2] = value % 10;
-                    }
+            // FX33 — Store BCD representation of VX at I, I+1, I+2
+            Instruction::StoreBcd { x } => {
+                let value: u8 = self.v[x as usize];
 
-                    // FX55 — Store V0..VX in memory starting at I
-                    0x55 => {
-                        for idx in 0..=decoded.x as usize {
-                            self.memory[self.i as usize + idx] = self.v[idx];
-                        }
-                    }
+                self.memory[wrap(self.i as usize)]     = value / 100;
+                self.memory[wrap(self.i as usize + 1)] = (value % 100) / 10;
+                self.memory[wrap(self.i as usize + 2)] = value % 10;
+            }
 
-                    // FX65 — Load V0..VX from memory starting at I
-                    0x65 => {
-                        for idx in 0..=decoded.x as usize {
-                            self.v[idx] = self.memory[self.i as usize + idx];
-                        }
-                    }
+            // FX55 — Store V0..VX in memory starting at I
+            Instruction::StoreRegs { x } => {
+                for idx in 0..=x as usize {
+                    self.memory[wrap(self.i as usize + idx)] = self.v[idx];
+                }
+            }
 
-                    _ => {
-                        eprintln!("Invalid FX opcode: {:#06X}", opcode);
-                    }
+            // FX65 — Load V0..VX from memory starting at I
+            Instruction::LoadRegs { x } => {
+                for idx in 0..=x as usize {
+                    self.v[idx] = self.memory[wrap(self.i as usize + idx)];
                 }
             }
 
-            _ => {
-                eprintln!("Unknown opcode: {:#06X}", opcode);
+            Instruction::Invalid { opcode } => {
+                eprintln!("Invalid opcode: {:#06X}", opcode);
+            }
+        }
+    }
+}
+
+// Memory index for `address`, wrapping at the end of RAM so an I or PC
+// near 0xFFF reads and writes the start of memory instead of panicking
+pub fn wrap(address: usize) -> usize {
+    address % MEMORY_SIZE
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::chip8::testing::{assert_display_matches, assert_pixel, assert_reg, run_until};
+
+    fn machine(rom: &[u8]) -> Chip8 {
+        let mut chip8: Chip8 = Chip8::new();
+        chip8.load_rom(rom).unwrap();
+        chip8
+    }
+
+    #[test]
+    fn key_wait_blocks_until_a_key_is_pressed() {
+        // F30A: wait for a key into V3; 6401: V4 = 1
+        let mut chip8: Chip8 = machine(&[0xF3, 0x0A, 0x64, 0x01]);
+        run_until(&mut chip8, 10, |chip8| chip8.waiting_for_key().is_some());
+
+        for _ in 0..100 {
+            chip8.cycle();
+        }
+        assert_eq!(chip8.pc, 0x202);
+        assert_reg(&chip8, 4, 0);
+
+        chip8.keys[0xB] = true;
+        run_until(&mut chip8, 10, |chip8| chip8.pc == 0x204);
+
+        assert_reg(&chip8, 3, 0xB);
+        assert_reg(&chip8, 4, 1);
+        assert_eq!(chip8.stats.key_waits.len(), 1);
+    }
+
+    #[test]
+    fn add_and_subtract_set_carry_and_borrow() {
+        // 60F0, 6120: V0 = 0xF0, V1 = 0x20; 8014: V0 += V1; 6205, 6307: V2 = 5, V3 = 7; 8235: V2 -= V3
+        let mut chip8: Chip8 = machine(&[0x60, 0xF0, 0x61, 0x20, 0x80, 0x14, 0x62, 0x05, 0x63, 0x07, 0x82, 0x35]);
+
+        run_until(&mut chip8, 10, |chip8| chip8.pc == 0x206);
+        assert_reg(&chip8, 0, 0x10);
+        assert_reg(&chip8, 0xF, 1);
+
+        run_until(&mut chip8, 10, |chip8| chip8.pc == 0x20C);
+        assert_reg(&chip8, 2, 0xFE);
+        assert_reg(&chip8, 0xF, 0);
+    }
+
+    #[test]
+    fn redrawing_a_sprite_erases_it_and_reports_a_collision() {
+        // 6000: V0 = 0; F029: I = glyph "0"; D005: draw it at (0, 0)
+        let mut chip8: Chip8 = machine(&[0x60, 0x00, 0xF0, 0x29, 0xD0, 0x05, 0xD0, 0x05]);
+
+        run_until(&mut chip8, 10, |chip8| chip8.pc == 0x206);
+        assert_pixel(&chip8, 0, 0, true);
+        assert_pixel(&chip8, 1, 1, false);
+        assert_reg(&chip8, 0xF, 0);
+
+        run_until(&mut chip8, 10, |chip8| chip8.pc == 0x208);
+        assert_pixel(&chip8, 0, 0, false);
+        assert_reg(&chip8, 0xF, 1);
+    }
+
+    #[test]
+    fn clear_screen_blanks_the_display() {
+        // 6000: V0 = 0; F029: I = glyph "0"; D005: draw it; 00E0: clear
+        let mut chip8: Chip8 = machine(&[0x60, 0x00, 0xF0, 0x29, 0xD0, 0x05, 0x00, 0xE0]);
+        run_until(&mut chip8, 10, |chip8| chip8.pc == 0x208);
+
+        assert_display_matches(&chip8, "
+........
+........
+");
+    }
+
+    #[test]
+    fn bcd_near_end_of_memory_wraps() {
+        // AFFF: I = 0xFFF; 60FB: V0 = 251; F033: BCD of V0
+        let mut chip8: Chip8 = machine(&[0xAF, 0xFF, 0x60, 0xFB, 0xF0, 0x33]);
+        run_until(&mut chip8, 10, |chip8| chip8.pc == 0x206);
+
+        assert_eq!(chip8.memory[0xFFF], 2);
+        assert_eq!(chip8.memory[0x000], 5);
+        assert_eq!(chip8.memory[0x001], 1);
+    }
+
+    #[test]
+    fn register_spill_near_end_of_memory_wraps() {
+        // AFFE: I = 0xFFE; 6011, 6122, 6233: V0..V2; F255: store V0..V2;
+        // 6000, 6100, 6200: clear them; F265: load V0..V2
+        let mut chip8: Chip8 = machine(&[
+            0xAF, 0xFE, 0x60, 0x11, 0x61, 0x22, 0x62, 0x33, 0xF2, 0x55, 0x60, 0x00, 0x61, 0x00, 0x62, 0x00, 0xF2, 0x65,
+        ]);
+        run_until(&mut chip8, 20, |chip8| chip8.pc == 0x212);
+
+        assert_eq!(chip8.memory[0xFFE], 0x11);
+        assert_eq!(chip8.memory[0xFFF], 0x22);
+        assert_eq!(chip8.memory[0x000], 0x33);
+        assert_reg(&chip8, 0, 0x11);
+        assert_reg(&chip8, 1, 0x22);
+        assert_reg(&chip8, 2, 0x33);
+    }
+
+    #[test]
+    fn sprite_near_end_of_memory_wraps() {
+        // AFFF: I = 0xFFF; D002: draw 2 rows at (V0, V0)
+        let mut chip8: Chip8 = machine(&[0xAF, 0xFF, 0xD0, 0x02]);
+        chip8.memory[0xFFF] = 0xF0;
+        chip8.memory[0x000] = 0xC0;
+        run_until(&mut chip8, 10, |chip8| chip8.pc == 0x204);
+
+        assert_display_matches(&chip8, "
+####....
+##......
+........
+");
+    }
+
+    // 6000: V0 = 0; 611E: V1 = 30; F029: I = glyph "0" (F0 90 90 90 F0); D015: draw it at (0, 30)
+    const DRAW_AT_BOTTOM: [u8; 8] = [0x60, 0x00, 0x61, 0x1E, 0xF0, 0x29, 0xD0, 0x15];
+
+    #[test]
+    fn rows_past_bottom_edge_wrap_to_the_top() {
+        let mut chip8: Chip8 = machine(&DRAW_AT_BOTTOM);
+        run_until(&mut chip8, 10, |chip8| chip8.pc == 0x208);
+
+        assert_reg(&chip8, 0xF, 0);
+        assert_display_matches(&chip8, "
+#..#....
+#..#....
+####....
+");
+        assert!(chip8.display[30][0] && chip8.display[31][0]);
+    }
+
+    #[test]
+    fn row_count_quirk_clips_rows_past_bottom_edge() {
+        let mut chip8: Chip8 = machine(&DRAW_AT_BOTTOM);
+        chip8.quirks.collision_row_count = true;
+        run_until(&mut chip8, 10, |chip8| chip8.pc == 0x208);
+
+        assert_reg(&chip8, 0xF, 3);
+        assert_display_matches(&chip8, "
+........
+........
+........
+");
+        assert!(chip8.display[30][0] && chip8.display[31][0]);
+    }
+
+    #[test]
+    fn fetch_past_end_of_memory_wraps() {
+        let mut chip8: Chip8 = machine(&[]);
+        chip8.memory[0xFFE..].copy_from_slice(&[0x60, 0x42]);
+        chip8.memory[..2].copy_from_slice(&[0x61, 0x43]);
+        chip8.pc = 0xFFE;
+        run_until(&mut chip8, 10, |chip8| chip8.pc == 0x002);
+
+        assert_reg(&chip8, 0, 0x42);
+        assert_reg(&chip8, 1, 0x43);
+    }
+
+    #[test]
+    fn rom_larger_than_program_space_is_rejected() {
+        let mut chip8: Chip8 = machine(&[0x12, 0x00]);
+        let rom: Vec<u8> = vec![0xAA; MEMORY_SIZE - PROGRAM_START as usize + 1];
+
+        assert!(matches!(chip8.load_rom(&rom), Err(Chip8Error::TooLarge { len, .. }) if len == rom.len()));
+        assert_eq!(chip8.memory[PROGRAM_START as usize], 0x12);
+    }
+
+    #[test]
+    fn extension_removed_from_a_running_hook_stays_registered() {
+        let mut chip8: Chip8 = machine(&[0x51, 0x21]);
+        let swap: HookId = chip8.register_extension("5XY1", "SWAP VX, VY", |_, _| {}).unwrap();
+
+        let removed: Arc<Mutex<Option<bool>>> = Arc::new(Mutex::new(None));
+        let result: Arc<Mutex<Option<bool>>> = Arc::clone(&removed);
+        chip8.on_opcode(0, 0, move |chip8, _| {
+            *result.lock().unwrap() = Some(chip8.remove_hook(swap));
+            HookAction::Continue
+        });
+        chip8.cycle();
+
+        assert_eq!(*removed.lock().unwrap(), Some(false));
+        assert_eq!(chip8.disassemble(0x5121), "SWAP V1, V2");
+
+        assert!(chip8.remove_hook(swap));
+        assert_eq!(chip8.disassemble(0x5121), "DW 0x5121");
+    }
+
+    #[test]
+    fn skipping_hook_suppresses_the_instruction() {
+        // 6042: V0 = 0x42; 6143: V1 = 0x43
+        let mut chip8: Chip8 = machine(&[0x60, 0x42, 0x61, 0x43]);
+        chip8.on_opcode(0x6000, 0xFF00, |_, _| HookAction::Skip);
+        chip8.on_opcode(0x6000, 0xF000, |_, _| HookAction::Continue);
+        chip8.cycle();
+        chip8.cycle();
+
+        assert_reg(&chip8, 0, 0);
+        assert_reg(&chip8, 1, 0x43);
+        assert_eq!(chip8.pc, 0x204);
+    }
+
+    #[test]
+    fn after_hook_sees_the_result() {
+        // 6005: V0 = 5; 7003: V0 += 3
+        let mut chip8: Chip8 = machine(&[0x60, 0x05, 0x70, 0x03]);
+        let seen: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
+        let log: Arc<Mutex<Vec<u8>>> = Arc::clone(&seen);
+        chip8.after_opcode(0x7000, 0xF000, move |chip8, _| log.lock().unwrap().push(chip8.v[0]));
+        chip8.cycle();
+        chip8.cycle();
+
+        assert_eq!(*seen.lock().unwrap(), [8]);
+    }
+
+    #[test]
+    fn hook_registered_from_a_hook_survives_with_a_unique_id() {
+        // 7001: V0 += 1; 1200: again
+        let mut chip8: Chip8 = machine(&[0x70, 0x01, 0x12, 0x00]);
+        let runs: Arc<Mutex<u32>> = Arc::new(Mutex::new(0));
+        let inner_runs: Arc<Mutex<u32>> = Arc::clone(&runs);
+        let inner_id: Arc<Mutex<Option<HookId>>> = Arc::new(Mutex::new(None));
+        let registered: Arc<Mutex<Option<HookId>>> = Arc::clone(&inner_id);
+
+        let outer: HookId = chip8.on_opcode(0x7000, 0xF000, move |chip8, _| {
+            let mut registered = registered.lock().unwrap();
+            if registered.is_none() {
+                let runs: Arc<Mutex<u32>> = Arc::clone(&inner_runs);
+                *registered = Some(chip8.after_opcode(0, 0, move |_, _| *runs.lock().unwrap() += 1));
             }
+            HookAction::Continue
+        });
+        for _ in 0..4 {
+            chip8.cycle();
         }
+        let later: HookId = chip8.on_opcode(0, 0, |_, _| HookAction::Continue);
+
+        // Takes effect from the instruction after the one that registered it
+        assert_eq!(*runs.lock().unwrap(), 3);
+
+        let inner: HookId = inner_id.lock().unwrap().unwrap();
+        assert!(outer != inner && inner != later && outer != later);
+        assert!(chip8.remove_hook(inner));
+        assert!(chip8.remove_hook(later));
+        assert!(chip8.remove_hook(outer));
     }
-}
\ No newline at end of file
+}
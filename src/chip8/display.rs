@@ -0,0 +1,91 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Display
+// Display buffer type shared by the core and frontends, and the
+// per-row dirty flags that let frontends redraw only what changed.
+// ───────────────────────────────────────────────────────────────
+
+use std::ops::Range;
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH};
+
+// 64x32 monochrome framebuffer, indexed as [y][x]
+pub type DisplayBuffer = [[bool; DISPLAY_WIDTH]; DISPLAY_HEIGHT];
+
+pub const BLANK_DISPLAY: DisplayBuffer = [[false; DISPLAY_WIDTH]; DISPLAY_HEIGHT];
+
+// One bit per display row, set for the rows that changed
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct DirtyRows(u32);
+
+const _: () = assert!(DISPLAY_HEIGHT <= 32);
+
+impl DirtyRows {
+
+    pub const ALL: DirtyRows = DirtyRows(u32::MAX >> (32 - DISPLAY_HEIGHT));
+
+    pub fn mark(&mut self, row: usize) {
+        self.0 |= 1 << row;
+    }
+
+    pub fn contains(self, row: usize) -> bool {
+        self.0 & (1 << row) != 0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn union(self, other: DirtyRows) -> DirtyRows {
+        DirtyRows(self.0 | other.0)
+    }
+
+    // Dirty row indices, top to bottom
+    pub fn iter(self) -> impl Iterator<Item = usize> {
+        (0..DISPLAY_HEIGHT).filter(move |&row| self.contains(row))
+    }
+
+    // Smallest run of rows covering every dirty one, for one rectangular upload
+    pub fn span(self) -> Option<Range<usize>> {
+        match self.0 {
+            0 => None,
+            bits => Some(bits.trailing_zeros() as usize..32 - bits.leading_zeros() as usize),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marked_rows_are_dirty() {
+        let mut dirty: DirtyRows = DirtyRows::default();
+        assert!(dirty.is_empty());
+
+        dirty.mark(3);
+        dirty.mark(DISPLAY_HEIGHT - 1);
+
+        assert!(dirty.contains(3) && dirty.contains(DISPLAY_HEIGHT - 1));
+        assert!(!dirty.contains(0) && !dirty.contains(4));
+        assert_eq!(dirty.iter().collect::<Vec<usize>>(), [3, DISPLAY_HEIGHT - 1]);
+        assert_eq!(dirty.span(), Some(3..DISPLAY_HEIGHT));
+    }
+
+    #[test]
+    fn union_keeps_rows_from_both() {
+        let mut top: DirtyRows = DirtyRows::default();
+        top.mark(0);
+        let mut bottom: DirtyRows = DirtyRows::default();
+        bottom.mark(9);
+
+        assert_eq!(top.union(bottom).iter().collect::<Vec<usize>>(), [0, 9]);
+        assert_eq!(top.union(DirtyRows::default()), top);
+        assert_eq!(DirtyRows::default().span(), None);
+    }
+
+    #[test]
+    fn all_covers_every_row() {
+        assert_eq!(DirtyRows::ALL.iter().count(), DISPLAY_HEIGHT);
+        assert_eq!(DirtyRows::ALL.span(), Some(0..DISPLAY_HEIGHT));
+    }
+}
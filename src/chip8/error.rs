@@ -0,0 +1,77 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Errors
+// Error type for fallible operations on emulator state and files.
+// ───────────────────────────────────────────────────────────────
+
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum Chip8Error {
+    // Underlying file or stream error
+    Io(io::Error),
+
+    // Malformed memory image (line is 1-based)
+    InvalidImage { line: usize, reason: &'static str },
+
+    // Custom opcode pattern that cannot be registered
+    InvalidExtension { pattern: &'static str, reason: &'static str },
+
+    // Malformed input macro file (line is 1-based)
+    InvalidMacro { line: usize, reason: &'static str },
+
+    // Malformed keymap file (line is 1-based)
+    InvalidKeymap { line: usize, reason: &'static str },
+
+    // Malformed session file (line is 1-based)
+    InvalidSession { line: usize, reason: &'static str },
+
+    // Malformed per-ROM settings file (line is 1-based)
+    InvalidRomSettings { line: usize, reason: &'static str },
+
+    // Memory image or ROM longer than the `max` bytes it may fill
+    TooLarge { len: usize, max: usize },
+
+    // SRAM save file does not match the configured region's length
+    SramSizeMismatch { expected: usize, found: usize },
+}
+
+impl fmt::Display for Chip8Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Chip8Error::Io(err) => write!(f, "{}", err),
+            Chip8Error::InvalidImage { line, reason } => {
+                write!(f, "invalid memory image at line {}: {}", line, reason)
+            }
+            Chip8Error::InvalidExtension { pattern, reason } => {
+                write!(f, "invalid opcode extension {}: {}", pattern, reason)
+            }
+            Chip8Error::InvalidMacro { line, reason } => {
+                write!(f, "invalid input macro at line {}: {}", line, reason)
+            }
+            Chip8Error::InvalidKeymap { line, reason } => {
+                write!(f, "invalid keymap at line {}: {}", line, reason)
+            }
+            Chip8Error::InvalidSession { line, reason } => {
+                write!(f, "invalid session file at line {}: {}", line, reason)
+            }
+            Chip8Error::InvalidRomSettings { line, reason } => {
+                write!(f, "invalid ROM settings at line {}: {}", line, reason)
+            }
+            Chip8Error::TooLarge { len, max } => {
+                write!(f, "{} bytes is more than the {} bytes that fit", len, max)
+            }
+            Chip8Error::SramSizeMismatch { expected, found } => {
+                write!(f, "SRAM file holds {} bytes but the region is {} bytes", found, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Chip8Error {}
+
+impl From<io::Error> for Chip8Error {
+    fn from(err: io::Error) -> Self {
+        Chip8Error::Io(err)
+    }
+}
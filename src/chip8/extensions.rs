@@ -0,0 +1,159 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Custom Opcode Extensions
+// Lets embedders claim otherwise-invalid opcode space (e.g. 5XY1,
+// 9XY1) for experimental instructions. Each extension has its own
+// pattern and mnemonic, so stats and disassembly show it by name
+// instead of as an invalid opcode.
+// ───────────────────────────────────────────────────────────────
+
+use crate::chip8::error::Chip8Error;
+use crate::chip8::hooks::HookId;
+use crate::chip8::instruction::{DecodedFields, Instruction};
+
+pub struct Extension {
+    // Opcode pattern such as "5XY1": hex digits are fixed, X/Y/N are operands
+    pub pattern: &'static str,
+
+    // Mnemonic template using the same operand names, e.g. "SWAP VX, VY"
+    pub mnemonic: &'static str,
+
+    value: u16,
+    mask: u16,
+
+    // Before-hook that runs the handler
+    hook: HookId,
+}
+
+impl Extension {
+    pub fn matches(&self, opcode: u16) -> bool {
+        opcode & self.mask == self.value
+    }
+
+    // Fill the mnemonic's VX/VY/N/NN/NNN operands from `opcode`
+    pub fn disassemble(&self, opcode: u16) -> String {
+        let fields: DecodedFields = DecodedFields::new(opcode);
+        let mut text: String = String::new();
+        let mut word: String = String::new();
+
+        // Trailing space flushes the last word
+        for ch in self.mnemonic.chars().chain([' ']) {
+            if ch.is_ascii_alphanumeric() {
+                word.push(ch);
+                continue;
+            }
+
+            match word.as_str() {
+                "VX" => text.push_str(&format!("V{:X}", fields.x)),
+                "VY" => text.push_str(&format!("V{:X}", fields.y)),
+                "N" => text.push_str(&fields.n.to_string()),
+                "NN" => text.push_str(&format!("{:#04X}", fields.nn)),
+                "NNN" => text.push_str(&format!("{:#05X}", fields.nnn)),
+                _ => text.push_str(&word),
+            }
+            word.clear();
+            text.push(ch);
+        }
+
+        text.pop();
+        text
+    }
+}
+
+#[derive(Default)]
+pub struct Extensions {
+    registered: Vec<Extension>,
+}
+
+impl Extensions {
+
+    // Validate `pattern` and return its (value, mask). The pattern may only
+    // cover opcodes the built-in decoder rejects, and must not overlap an
+    // extension registered earlier.
+    pub fn claim(&self, pattern: &'static str) -> Result<(u16, u16), Chip8Error> {
+        let invalid = |reason: &'static str| Chip8Error::InvalidExtension { pattern, reason };
+
+        if pattern.len() != 4 {
+            return Err(invalid("pattern must be four characters"));
+        }
+
+        let mut value: u16 = 0;
+        let mut mask: u16 = 0;
+
+        for ch in pattern.chars() {
+            value <<= 4;
+            mask <<= 4;
+
+            match ch {
+                'X' | 'Y' | 'N' => {}
+                _ => {
+                    let digit: u32 = ch.to_digit(16).ok_or_else(|| invalid("expected a hex digit or X/Y/N"))?;
+                    value |= digit as u16;
+                    mask |= 0xF;
+                }
+            }
+        }
+
+        let collides_with_builtin: bool = (0..=0xFFFFu16)
+            .filter(|&opcode| opcode & mask == value)
+            .any(|opcode| !matches!(Instruction::decode(opcode), Instruction::Invalid { .. }));
+        if collides_with_builtin {
+            return Err(invalid("overlaps a built-in instruction"));
+        }
+
+        let overlaps_extension: bool = self
+            .registered
+            .iter()
+            .any(|other| (value ^ other.value) & mask & other.mask == 0);
+        if overlaps_extension {
+            return Err(invalid("overlaps an existing extension"));
+        }
+
+        Ok((value, mask))
+    }
+
+    pub fn add(&mut self, pattern: &'static str, mnemonic: &'static str, value: u16, mask: u16, hook: HookId) {
+        self.registered.push(Extension { pattern, mnemonic, value, mask, hook });
+    }
+
+    // Forget the extension backed by `hook`, if any
+    pub fn remove(&mut self, hook: HookId) {
+        self.registered.retain(|extension| extension.hook != hook);
+    }
+
+    pub fn find(&self, opcode: u16) -> Option<&Extension> {
+        self.registered.iter().find(|extension| extension.matches(opcode))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chip8::cpu::Chip8;
+
+    fn rejection(claimed: Result<impl Sized, Chip8Error>) -> Option<&'static str> {
+        match claimed {
+            Err(Chip8Error::InvalidExtension { reason, .. }) => Some(reason),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn invalid_opcode_space_can_be_claimed() {
+        assert_eq!(Extensions::default().claim("5XY1").ok(), Some((0x5001, 0xF00F)));
+    }
+
+    #[test]
+    fn built_in_instructions_cannot_be_claimed() {
+        assert_eq!(rejection(Extensions::default().claim("5XY0")), Some("overlaps a built-in instruction"));
+    }
+
+    #[test]
+    fn overlapping_extensions_are_rejected() {
+        let mut chip8: Chip8 = Chip8::new();
+        chip8.register_extension("5XYE", "EXT VX, VY", |_, _| {}).unwrap();
+
+        let overlapping = chip8.register_extension("5X0E", "SEND VX", |_, _| {});
+        assert_eq!(rejection(overlapping), Some("overlaps an existing extension"));
+        assert!(chip8.register_extension("5XYD", "EXT VX, VY", |_, _| {}).is_ok());
+    }
+}
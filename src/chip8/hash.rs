@@ -0,0 +1,45 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — State Hashing
+// 64-bit FNV-1a. Unlike std's DefaultHasher its output is fixed
+// across platforms and Rust versions, so hashes can be compared
+// between runs, builds and emulators.
+// ───────────────────────────────────────────────────────────────
+
+const FNV_OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01B3;
+
+pub struct Fnv1a {
+    state: u64,
+}
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fnv1a {
+    pub fn new() -> Self {
+        Self { state: FNV_OFFSET_BASIS }
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state ^= byte as u64;
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.write(&[value]);
+    }
+
+    // Big-endian, matching how CHIP-8 stores 16-bit values in memory
+    pub fn write_u16(&mut self, value: u16) {
+        self.write(&value.to_be_bytes());
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.state
+    }
+}
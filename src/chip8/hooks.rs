@@ -0,0 +1,116 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Opcode Hooks
+// Callbacks keyed by opcode pattern that run around instruction
+// execution, for counters, loggers and experimental overrides
+// without touching `cycle()`.
+// ───────────────────────────────────────────────────────────────
+
+use crate::chip8::cpu::Chip8;
+use crate::chip8::instruction::Instruction;
+
+// What the interpreter should do after the before-hooks ran
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum HookAction {
+    // Execute the instruction normally
+    Continue,
+
+    // Skip the built-in behaviour (the hook has emulated it itself)
+    Skip,
+}
+
+// Handle returned on registration, used to remove the hook again
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct HookId(u64);
+
+pub type BeforeHook = Box<dyn FnMut(&mut Chip8, Instruction) -> HookAction + Send>;
+pub type AfterHook = Box<dyn FnMut(&mut Chip8, Instruction) + Send>;
+
+// Matches opcodes where `opcode & mask == value`
+struct OpcodePattern {
+    value: u16,
+    mask: u16,
+}
+
+impl OpcodePattern {
+    fn matches(&self, opcode: u16) -> bool {
+        opcode & self.mask == self.value & self.mask
+    }
+}
+
+#[derive(Default)]
+pub struct Hooks {
+    before: Vec<(HookId, OpcodePattern, BeforeHook)>,
+    after: Vec<(HookId, OpcodePattern, AfterHook)>,
+    next_id: u64,
+}
+
+impl Hooks {
+
+    pub fn is_empty(&self) -> bool {
+        self.before.is_empty() && self.after.is_empty()
+    }
+
+    fn allocate_id(&mut self) -> HookId {
+        self.next_id += 1;
+        HookId(self.next_id)
+    }
+
+    pub fn add_before(&mut self, value: u16, mask: u16, hook: BeforeHook) -> HookId {
+        let id: HookId = self.allocate_id();
+        self.before.push((id, OpcodePattern { value, mask }, hook));
+        id
+    }
+
+    pub fn add_after(&mut self, value: u16, mask: u16, hook: AfterHook) -> HookId {
+        let id: HookId = self.allocate_id();
+        self.after.push((id, OpcodePattern { value, mask }, hook));
+        id
+    }
+
+    // Returns true if a hook with this id was registered
+    pub fn remove(&mut self, id: HookId) -> bool {
+        let count: usize = self.before.len() + self.after.len();
+
+        self.before.retain(|(hook_id, _, _)| *hook_id != id);
+        self.after.retain(|(hook_id, _, _)| *hook_id != id);
+
+        self.before.len() + self.after.len() != count
+    }
+
+    // Run every matching before-hook; any Skip suppresses normal execution
+    pub fn run_before(&mut self, chip8: &mut Chip8, opcode: u16, instruction: Instruction) -> HookAction {
+        let mut action: HookAction = HookAction::Continue;
+
+        for (_, pattern, hook) in self.before.iter_mut() {
+            if pattern.matches(opcode) && hook(chip8, instruction) == HookAction::Skip {
+                action = HookAction::Skip;
+            }
+        }
+
+        action
+    }
+
+    pub fn run_after(&mut self, chip8: &mut Chip8, opcode: u16, instruction: Instruction) {
+        for (_, pattern, hook) in self.after.iter_mut() {
+            if pattern.matches(opcode) {
+                hook(chip8, instruction);
+            }
+        }
+    }
+
+    // Take all hooks out for dispatch, leaving an empty registry that keeps
+    // allocating fresh ids for hooks registered while they run
+    pub fn detach(&mut self) -> Hooks {
+        let next_id: u64 = self.next_id;
+        let hooks: Hooks = std::mem::take(self);
+        self.next_id = next_id;
+        hooks
+    }
+
+    // Move hooks registered in `other` (e.g. from inside a running hook) into self
+    pub fn absorb(&mut self, mut other: Hooks) {
+        self.before.append(&mut other.before);
+        self.after.append(&mut other.after);
+        self.next_id = self.next_id.max(other.next_id);
+    }
+}
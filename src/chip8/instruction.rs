@@ -0,0 +1,553 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Instruction Set
+// Typed decoding of raw opcodes, shared by the interpreter, the
+// disassembler and the opcode reference. Each instruction carries
+// its own pattern, mnemonic, description and the machine state it
+// reads and writes, so these can never drift from what the
+// interpreter actually executes.
+// ───────────────────────────────────────────────────────────────
+
+use std::fmt;
+
+// ===============================================================
+// Decoded Opcode Representation
+// ===============================================================
+
+pub struct DecodedFields {
+    pub first_nibble: u8,
+    pub x: u8,
+    pub y: u8,
+    pub n: u8,
+    pub nn: u8,
+    pub nnn: u16,
+}
+
+impl DecodedFields {
+    pub fn new(opcode: u16) -> Self {
+        Self {
+            first_nibble: ((opcode & 0xF000) >> 12) as u8,
+            x:            ((opcode & 0x0F00) >> 8)  as u8,
+            y:            ((opcode & 0x00F0) >> 4)  as u8,
+            n:            (opcode & 0x000F)         as u8,
+            nn:           (opcode & 0x00FF)         as u8,
+            nnn:           opcode & 0x0FFF,
+        }
+    }
+}
+
+// ===============================================================
+// Instruction Metadata
+// ===============================================================
+
+pub struct InstructionSpec {
+    // Opcode pattern, e.g. "8XY4"
+    pub pattern: &'static str,
+
+    // Mnemonic template, e.g. "ADD VX, VY"
+    pub mnemonic: &'static str,
+
+    // One-line description of the behaviour
+    pub description: &'static str,
+
+    // Names of the `Quirks` fields that change this instruction's behaviour
+    pub quirks: &'static [&'static str],
+}
+
+// Machine state an instruction reads or writes, relative to its operands
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Location {
+    Register(u8),
+
+    // V0..=V(last)
+    Registers { last: u8 },
+
+    Index,
+    ProgramCounter,
+
+    // The return address stack and its pointer
+    Stack,
+
+    DelayTimer,
+    SoundTimer,
+    Keypad,
+
+    // `len` bytes of memory starting at I
+    Memory { len: u8 },
+
+    // The sprite area at (VX, VY), 8 pixels wide and `rows` tall
+    Sprite { x: u8, y: u8, rows: u8 },
+
+    // Every display cell
+    Screen,
+}
+
+// What an instruction reads and writes. Advancing PC past the instruction
+// is implied; ProgramCounter is only listed for jumps, calls and skips.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Access {
+    pub reads: Vec<Location>,
+    pub writes: Vec<Location>,
+}
+
+// ===============================================================
+// Typed Instructions
+// ===============================================================
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Instruction {
+    Sys { nnn: u16 },
+    ClearScreen,
+    Return,
+    Jump { nnn: u16 },
+    Call { nnn: u16 },
+    SkipEqImm { x: u8, nn: u8 },
+    SkipNeImm { x: u8, nn: u8 },
+    SkipEqReg { x: u8, y: u8 },
+    LoadImm { x: u8, nn: u8 },
+    AddImm { x: u8, nn: u8 },
+    Move { x: u8, y: u8 },
+    Or { x: u8, y: u8 },
+    And { x: u8, y: u8 },
+    Xor { x: u8, y: u8 },
+    AddReg { x: u8, y: u8 },
+    Sub { x: u8, y: u8 },
+    ShiftRight { x: u8, y: u8 },
+    SubReversed { x: u8, y: u8 },
+    ShiftLeft { x: u8, y: u8 },
+    SkipNeReg { x: u8, y: u8 },
+    LoadIndex { nnn: u16 },
+    JumpOffset { nnn: u16 },
+    Random { x: u8, nn: u8 },
+    Draw { x: u8, y: u8, n: u8 },
+    SkipKeyDown { x: u8 },
+    SkipKeyUp { x: u8 },
+    LoadDelay { x: u8 },
+    WaitKey { x: u8 },
+    SetDelay { x: u8 },
+    SetSound { x: u8 },
+    AddIndex { x: u8 },
+    LoadFont { x: u8 },
+    StoreBcd { x: u8 },
+    StoreRegs { x: u8 },
+    LoadRegs { x: u8 },
+    Invalid { opcode: u16 },
+}
+
+// One representative of every instruction form, in opcode order
+pub const INSTRUCTION_FORMS: [Instruction; 36] = [
+    Instruction::Sys { nnn: 0 },
+    Instruction::ClearScreen,
+    Instruction::Return,
+    Instruction::Jump { nnn: 0 },
+    Instruction::Call { nnn: 0 },
+    Instruction::SkipEqImm { x: 0, nn: 0 },
+    Instruction::SkipNeImm { x: 0, nn: 0 },
+    Instruction::SkipEqReg { x: 0, y: 0 },
+    Instruction::LoadImm { x: 0, nn: 0 },
+    Instruction::AddImm { x: 0, nn: 0 },
+    Instruction::Move { x: 0, y: 0 },
+    Instruction::Or { x: 0, y: 0 },
+    Instruction::And { x: 0, y: 0 },
+    Instruction::Xor { x: 0, y: 0 },
+    Instruction::AddReg { x: 0, y: 0 },
+    Instruction::Sub { x: 0, y: 0 },
+    Instruction::ShiftRight { x: 0, y: 0 },
+    Instruction::SubReversed { x: 0, y: 0 },
+    Instruction::ShiftLeft { x: 0, y: 0 },
+    Instruction::SkipNeReg { x: 0, y: 0 },
+    Instruction::LoadIndex { nnn: 0 },
+    Instruction::JumpOffset { nnn: 0 },
+    Instruction::Random { x: 0, nn: 0 },
+    Instruction::Draw { x: 0, y: 0, n: 0 },
+    Instruction::SkipKeyDown { x: 0 },
+    Instruction::SkipKeyUp { x: 0 },
+    Instruction::LoadDelay { x: 0 },
+    Instruction::WaitKey { x: 0 },
+    Instruction::SetDelay { x: 0 },
+    Instruction::SetSound { x: 0 },
+    Instruction::AddIndex { x: 0 },
+    Instruction::LoadFont { x: 0 },
+    Instruction::StoreBcd { x: 0 },
+    Instruction::StoreRegs { x: 0 },
+    Instruction::LoadRegs { x: 0 },
+    Instruction::Invalid { opcode: 0 },
+];
+
+impl Instruction {
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "decode", level = "trace", skip_all))]
+    pub fn decode(opcode: u16) -> Self {
+        let decoded: DecodedFields = DecodedFields::new(opcode);
+        let DecodedFields { x, y, n, nn, nnn, .. } = decoded;
+
+        match decoded.first_nibble {
+            0x0 => match opcode {
+                0x00E0 => Instruction::ClearScreen,
+                0x00EE => Instruction::Return,
+                _ => Instruction::Sys { nnn },
+            },
+            0x1 => Instruction::Jump { nnn },
+            0x2 => Instruction::Call { nnn },
+            0x3 => Instruction::SkipEqImm { x, nn },
+            0x4 => Instruction::SkipNeImm { x, nn },
+            0x5 if n == 0 => Instruction::SkipEqReg { x, y },
+            0x6 => Instruction::LoadImm { x, nn },
+            0x7 => Instruction::AddImm { x, nn },
+            0x8 => match n {
+                0x0 => Instruction::Move { x, y },
+                0x1 => Instruction::Or { x, y },
+                0x2 => Instruction::And { x, y },
+                0x3 => Instruction::Xor { x, y },
+                0x4 => Instruction::AddReg { x, y },
+                0x5 => Instruction::Sub { x, y },
+                0x6 => Instruction::ShiftRight { x, y },
+                0x7 => Instruction::SubReversed { x, y },
+                0xE => Instruction::ShiftLeft { x, y },
+                _ => Instruction::Invalid { opcode },
+            },
+            0x9 if n == 0 => Instruction::SkipNeReg { x, y },
+            0xA => Instruction::LoadIndex { nnn },
+            0xB => Instruction::JumpOffset { nnn },
+            0xC => Instruction::Random { x, nn },
+            0xD => Instruction::Draw { x, y, n },
+            0xE => match nn {
+                0x9E => Instruction::SkipKeyDown { x },
+                0xA1 => Instruction::SkipKeyUp { x },
+                _ => Instruction::Invalid { opcode },
+            },
+            0xF => match nn {
+                0x07 => Instruction::LoadDelay { x },
+                0x0A => Instruction::WaitKey { x },
+                0x15 => Instruction::SetDelay { x },
+                0x18 => Instruction::SetSound { x },
+                0x1E => Instruction::AddIndex { x },
+                0x29 => Instruction::LoadFont { x },
+                0x33 => Instruction::StoreBcd { x },
+                0x55 => Instruction::StoreRegs { x },
+                0x65 => Instruction::LoadRegs { x },
+                _ => Instruction::Invalid { opcode },
+            },
+            _ => Instruction::Invalid { opcode },
+        }
+    }
+
+    // Static metadata describing this instruction's form
+    pub fn spec(&self) -> &'static InstructionSpec {
+        match self {
+            Instruction::Sys { .. } => &InstructionSpec {
+                pattern: "0NNN",
+                mnemonic: "SYS NNN",
+                description: "Call RCA 1802 machine code at NNN (ignored)",
+                quirks: &[],
+            },
+            Instruction::ClearScreen => &InstructionSpec {
+                pattern: "00E0",
+                mnemonic: "CLS",
+                description: "Clear the display",
+                quirks: &["immediate_display"],
+            },
+            Instruction::Return => &InstructionSpec {
+                pattern: "00EE",
+                mnemonic: "RET",
+                description: "Return from subroutine",
+                quirks: &[],
+            },
+            Instruction::Jump { .. } => &InstructionSpec {
+                pattern: "1NNN",
+                mnemonic: "JP NNN",
+                description: "Jump to NNN",
+                quirks: &[],
+            },
+            Instruction::Call { .. } => &InstructionSpec {
+                pattern: "2NNN",
+                mnemonic: "CALL NNN",
+                description: "Call subroutine at NNN",
+                quirks: &[],
+            },
+            Instruction::SkipEqImm { .. } => &InstructionSpec {
+                pattern: "3XNN",
+                mnemonic: "SE VX, NN",
+                description: "Skip next instruction if VX == NN",
+                quirks: &[],
+            },
+            Instruction::SkipNeImm { .. } => &InstructionSpec {
+                pattern: "4XNN",
+                mnemonic: "SNE VX, NN",
+                description: "Skip next instruction if VX != NN",
+                quirks: &[],
+            },
+            Instruction::SkipEqReg { .. } => &InstructionSpec {
+                pattern: "5XY0",
+                mnemonic: "SE VX, VY",
+                description: "Skip next instruction if VX == VY",
+                quirks: &[],
+            },
+            Instruction::LoadImm { .. } => &InstructionSpec {
+                pattern: "6XNN",
+                mnemonic: "LD VX, NN",
+                description: "VX = NN",
+                quirks: &[],
+            },
+            Instruction::AddImm { .. } => &InstructionSpec {
+                pattern: "7XNN",
+                mnemonic: "ADD VX, NN",
+                description: "VX += NN (wrapping, VF unchanged)",
+                quirks: &[],
+            },
+            Instruction::Move { .. } => &InstructionSpec {
+                pattern: "8XY0",
+                mnemonic: "LD VX, VY",
+                description: "VX = VY",
+                quirks: &[],
+            },
+            Instruction::Or { .. } => &InstructionSpec {
+                pattern: "8XY1",
+                mnemonic: "OR VX, VY",
+                description: "VX |= VY",
+                quirks: &[],
+            },
+            Instruction::And { .. } => &InstructionSpec {
+                pattern: "8XY2",
+                mnemonic: "AND VX, VY",
+                description: "VX &= VY",
+                quirks: &[],
+            },
+            Instruction::Xor { .. } => &InstructionSpec {
+                pattern: "8XY3",
+                mnemonic: "XOR VX, VY",
+                description: "VX ^= VY",
+                quirks: &[],
+            },
+            Instruction::AddReg { .. } => &InstructionSpec {
+                pattern: "8XY4",
+                mnemonic: "ADD VX, VY",
+                description: "VX += VY, VF = carry",
+                quirks: &[],
+            },
+            Instruction::Sub { .. } => &InstructionSpec {
+                pattern: "8XY5",
+                mnemonic: "SUB VX, VY",
+                description: "VX -= VY, VF = NOT borrow",
+                quirks: &[],
+            },
+            Instruction::ShiftRight { .. } => &InstructionSpec {
+                pattern: "8XY6",
+                mnemonic: "SHR VX",
+                description: "VX >>= 1, VF = bit shifted out",
+                quirks: &[],
+            },
+            Instruction::SubReversed { .. } => &InstructionSpec {
+                pattern: "8XY7",
+                mnemonic: "SUBN VX, VY",
+                description: "VX = VY - VX, VF = NOT borrow",
+                quirks: &[],
+            },
+            Instruction::ShiftLeft { .. } => &InstructionSpec {
+                pattern: "8XYE",
+                mnemonic: "SHL VX",
+                description: "VX <<= 1, VF = bit shifted out",
+                quirks: &[],
+            },
+            Instruction::SkipNeReg { .. } => &InstructionSpec {
+                pattern: "9XY0",
+                mnemonic: "SNE VX, VY",
+                description: "Skip next instruction if VX != VY",
+                quirks: &[],
+            },
+            Instruction::LoadIndex { .. } => &InstructionSpec {
+                pattern: "ANNN",
+                mnemonic: "LD I, NNN",
+                description: "I = NNN",
+                quirks: &[],
+            },
+            Instruction::JumpOffset { .. } => &InstructionSpec {
+                pattern: "BNNN",
+                mnemonic: "JP V0, NNN",
+                description: "Jump to NNN + V0",
+                quirks: &[],
+            },
+            Instruction::Random { .. } => &InstructionSpec {
+                pattern: "CXNN",
+                mnemonic: "RND VX, NN",
+                description: "VX = random byte AND NN",
+                quirks: &[],
+            },
+            Instruction::Draw { .. } => &InstructionSpec {
+                pattern: "DXYN",
+                mnemonic: "DRW VX, VY, N",
+                description: "Draw N-row sprite at I to (VX, VY), VF = collision",
+                quirks: &["collision_row_count", "immediate_display"],
+            },
+            Instruction::SkipKeyDown { .. } => &InstructionSpec {
+                pattern: "EX9E",
+                mnemonic: "SKP VX",
+                description: "Skip next instruction if key VX is pressed",
+                quirks: &[],
+            },
+            Instruction::SkipKeyUp { .. } => &InstructionSpec {
+                pattern: "EXA1",
+                mnemonic: "SKNP VX",
+                description: "Skip next instruction if key VX is not pressed",
+                quirks: &[],
+            },
+            Instruction::LoadDelay { .. } => &InstructionSpec {
+                pattern: "FX07",
+                mnemonic: "LD VX, DT",
+                description: "VX = delay timer",
+                quirks: &[],
+            },
+            Instruction::WaitKey { .. } => &InstructionSpec {
+                pattern: "FX0A",
+                mnemonic: "LD VX, K",
+                description: "Wait for a key press, VX = key",
+                quirks: &[],
+            },
+            Instruction::SetDelay { .. } => &InstructionSpec {
+                pattern: "FX15",
+                mnemonic: "LD DT, VX",
+                description: "Delay timer = VX",
+                quirks: &[],
+            },
+            Instruction::SetSound { .. } => &InstructionSpec {
+                pattern: "FX18",
+                mnemonic: "LD ST, VX",
+                description: "Sound timer = VX",
+                quirks: &[],
+            },
+            Instruction::AddIndex { .. } => &InstructionSpec {
+                pattern: "FX1E",
+                mnemonic: "ADD I, VX",
+                description: "I += VX",
+                quirks: &[],
+            },
+            Instruction::LoadFont { .. } => &InstructionSpec {
+                pattern: "FX29",
+                mnemonic: "LD F, VX",
+                description: "I = address of font sprite for digit VX",
+                quirks: &[],
+            },
+            Instruction::StoreBcd { .. } => &InstructionSpec {
+                pattern: "FX33",
+                mnemonic: "LD B, VX",
+                description: "Store BCD of VX at I, I+1, I+2",
+                quirks: &[],
+            },
+            Instruction::StoreRegs { .. } => &InstructionSpec {
+                pattern: "FX55",
+                mnemonic: "LD [I], VX",
+                description: "Store V0..VX in memory starting at I",
+                quirks: &[],
+            },
+            Instruction::LoadRegs { .. } => &InstructionSpec {
+                pattern: "FX65",
+                mnemonic: "LD VX, [I]",
+                description: "Load V0..VX from memory starting at I",
+                quirks: &[],
+            },
+            Instruction::Invalid { .. } => &InstructionSpec {
+                pattern: "----",
+                mnemonic: "DW NNNN",
+                description: "Not a CHIP-8 instruction (logged and skipped)",
+                quirks: &[],
+            },
+        }
+    }
+
+    // State this instruction reads and writes, for teaching and debugging views
+    pub fn access(&self) -> Access {
+        use Location::*;
+
+        let (reads, writes): (Vec<Location>, Vec<Location>) = match *self {
+            Instruction::Sys { .. } | Instruction::Invalid { .. } => (vec![], vec![]),
+            Instruction::ClearScreen => (vec![], vec![Screen]),
+            Instruction::Return => (vec![Stack], vec![Stack, ProgramCounter]),
+            Instruction::Jump { .. } => (vec![], vec![ProgramCounter]),
+            Instruction::Call { .. } => (vec![Stack], vec![Stack, ProgramCounter]),
+            Instruction::SkipEqImm { x, .. } | Instruction::SkipNeImm { x, .. } => {
+                (vec![Register(x)], vec![ProgramCounter])
+            }
+            Instruction::SkipEqReg { x, y } | Instruction::SkipNeReg { x, y } => {
+                (vec![Register(x), Register(y)], vec![ProgramCounter])
+            }
+            Instruction::LoadImm { x, .. } | Instruction::Random { x, .. } => (vec![], vec![Register(x)]),
+            Instruction::AddImm { x, .. } => (vec![Register(x)], vec![Register(x)]),
+            Instruction::Move { x, y } => (vec![Register(y)], vec![Register(x)]),
+            Instruction::Or { x, y } | Instruction::And { x, y } | Instruction::Xor { x, y } => {
+                (vec![Register(x), Register(y)], vec![Register(x)])
+            }
+            Instruction::AddReg { x, y } | Instruction::Sub { x, y } | Instruction::SubReversed { x, y } => {
+                (vec![Register(x), Register(y)], vec![Register(x), Register(0xF)])
+            }
+            Instruction::ShiftRight { x, .. } | Instruction::ShiftLeft { x, .. } => {
+                (vec![Register(x)], vec![Register(x), Register(0xF)])
+            }
+            Instruction::LoadIndex { .. } => (vec![], vec![Index]),
+            Instruction::JumpOffset { .. } => (vec![Register(0)], vec![ProgramCounter]),
+            Instruction::Draw { x, y, n } => (
+                vec![Register(x), Register(y), Index, Memory { len: n }],
+                vec![Sprite { x, y, rows: n }, Register(0xF)],
+            ),
+            Instruction::SkipKeyDown { x } | Instruction::SkipKeyUp { x } => {
+                (vec![Register(x), Keypad], vec![ProgramCounter])
+            }
+            Instruction::LoadDelay { x } => (vec![DelayTimer], vec![Register(x)]),
+            Instruction::WaitKey { x } => (vec![Keypad], vec![Register(x)]),
+            Instruction::SetDelay { x } => (vec![Register(x)], vec![DelayTimer]),
+            Instruction::SetSound { x } => (vec![Register(x)], vec![SoundTimer]),
+            Instruction::AddIndex { x } => (vec![Index, Register(x)], vec![Index]),
+            Instruction::LoadFont { x } => (vec![Register(x)], vec![Index]),
+            Instruction::StoreBcd { x } => (vec![Register(x), Index], vec![Memory { len: 3 }]),
+            Instruction::StoreRegs { x } => (vec![Registers { last: x }, Index], vec![Memory { len: x + 1 }]),
+            Instruction::LoadRegs { x } => (vec![Index, Memory { len: x + 1 }], vec![Registers { last: x }]),
+        };
+
+        Access { reads, writes }
+    }
+}
+
+// ===============================================================
+// Disassembly
+// ===============================================================
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Instruction::Sys { nnn } => write!(f, "SYS {:#05X}", nnn),
+            Instruction::ClearScreen => write!(f, "CLS"),
+            Instruction::Return => write!(f, "RET"),
+            Instruction::Jump { nnn } => write!(f, "JP {:#05X}", nnn),
+            Instruction::Call { nnn } => write!(f, "CALL {:#05X}", nnn),
+            Instruction::SkipEqImm { x, nn } => write!(f, "SE V{:X}, {:#04X}", x, nn),
+            Instruction::SkipNeImm { x, nn } => write!(f, "SNE V{:X}, {:#04X}", x, nn),
+            Instruction::SkipEqReg { x, y } => write!(f, "SE V{:X}, V{:X}", x, y),
+            Instruction::LoadImm { x, nn } => write!(f, "LD V{:X}, {:#04X}", x, nn),
+            Instruction::AddImm { x, nn } => write!(f, "ADD V{:X}, {:#04X}", x, nn),
+            Instruction::Move { x, y } => write!(f, "LD V{:X}, V{:X}", x, y),
+            Instruction::Or { x, y } => write!(f, "OR V{:X}, V{:X}", x, y),
+            Instruction::And { x, y } => write!(f, "AND V{:X}, V{:X}", x, y),
+            Instruction::Xor { x, y } => write!(f, "XOR V{:X}, V{:X}", x, y),
+            Instruction::AddReg { x, y } => write!(f, "ADD V{:X}, V{:X}", x, y),
+            Instruction::Sub { x, y } => write!(f, "SUB V{:X}, V{:X}", x, y),
+            Instruction::ShiftRight { x, .. } => write!(f, "SHR V{:X}", x),
+            Instruction::SubReversed { x, y } => write!(f, "SUBN V{:X}, V{:X}", x, y),
+            Instruction::ShiftLeft { x, .. } => write!(f, "SHL V{:X}", x),
+            Instruction::SkipNeReg { x, y } => write!(f, "SNE V{:X}, V{:X}", x, y),
+            Instruction::LoadIndex { nnn } => write!(f, "LD I, {:#05X}", nnn),
+            Instruction::JumpOffset { nnn } => write!(f, "JP V0, {:#05X}", nnn),
+            Instruction::Random { x, nn } => write!(f, "RND V{:X}, {:#04X}", x, nn),
+            Instruction::Draw { x, y, n } => write!(f, "DRW V{:X}, V{:X}, {}", x, y, n),
+            Instruction::SkipKeyDown { x } => write!(f, "SKP V{:X}", x),
+            Instruction::SkipKeyUp { x } => write!(f, "SKNP V{:X}", x),
+            Instruction::LoadDelay { x } => write!(f, "LD V{:X}, DT", x),
+            Instruction::WaitKey { x } => write!(f, "LD V{:X}, K", x),
+            Instruction::SetDelay { x } => write!(f, "LD DT, V{:X}", x),
+            Instruction::SetSound { x } => write!(f, "LD ST, V{:X}", x),
+            Instruction::AddIndex { x } => write!(f, "ADD I, V{:X}", x),
+            Instruction::LoadFont { x } => write!(f, "LD F, V{:X}", x),
+            Instruction::StoreBcd { x } => write!(f, "LD B, V{:X}", x),
+            Instruction::StoreRegs { x } => write!(f, "LD [I], V{:X}", x),
+            Instruction::LoadRegs { x } => write!(f, "LD V{:X}, [I]", x),
+            Instruction::Invalid { opcode } => write!(f, "DW {:#06X}", opcode),
+        }
+    }
+}
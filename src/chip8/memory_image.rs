@@ -0,0 +1,186 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Raw Memory Images
+// Dump and restore the 4KB RAM on its own (no registers, display
+// or timers) as a flat binary or as Intel HEX, so it can be opened
+// in hex editors or compared against other emulators.
+// ───────────────────────────────────────────────────────────────
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use crate::chip8::constants::MEMORY_SIZE;
+use crate::chip8::error::Chip8Error;
+
+// Data bytes per Intel HEX record
+const HEX_RECORD_LEN: usize = 16;
+
+const RECORD_DATA: u8 = 0x00;
+const RECORD_EOF: u8 = 0x01;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Binary,
+    IntelHex,
+}
+
+impl ImageFormat {
+    // Pick the format from the file extension: .hex/.ihex is Intel HEX, anything else binary
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("hex") || ext.eq_ignore_ascii_case("ihex") => {
+                ImageFormat::IntelHex
+            }
+            _ => ImageFormat::Binary,
+        }
+    }
+}
+
+// Write `memory` to `path` in the format implied by its extension
+pub fn save(memory: &[u8; MEMORY_SIZE], path: &Path) -> Result<(), Chip8Error> {
+    match ImageFormat::from_path(path) {
+        ImageFormat::Binary => fs::write(path, memory)?,
+        ImageFormat::IntelHex => fs::write(path, to_intel_hex(memory))?,
+    }
+
+    Ok(())
+}
+
+// Read a memory image from `path`. Bytes not covered by the image are zero.
+pub fn load(path: &Path) -> Result<[u8; MEMORY_SIZE], Chip8Error> {
+    match ImageFormat::from_path(path) {
+        ImageFormat::Binary => from_binary(&fs::read(path)?),
+        ImageFormat::IntelHex => from_intel_hex(&fs::read_to_string(path)?),
+    }
+}
+
+// Memory starting with `bytes`, the rest zero
+pub fn from_binary(bytes: &[u8]) -> Result<[u8; MEMORY_SIZE], Chip8Error> {
+    if bytes.len() > MEMORY_SIZE {
+        return Err(Chip8Error::TooLarge { len: bytes.len(), max: MEMORY_SIZE });
+    }
+
+    let mut memory: [u8; MEMORY_SIZE] = [0; MEMORY_SIZE];
+    memory[..bytes.len()].copy_from_slice(bytes);
+    Ok(memory)
+}
+
+// ===============================================================
+// Intel HEX
+// ===============================================================
+
+pub fn to_intel_hex(memory: &[u8; MEMORY_SIZE]) -> String {
+    let mut text: String = String::new();
+
+    for (index, chunk) in memory.chunks(HEX_RECORD_LEN).enumerate() {
+        let address: u16 = (index * HEX_RECORD_LEN) as u16;
+        push_record(&mut text, address, RECORD_DATA, chunk);
+    }
+    push_record(&mut text, 0, RECORD_EOF, &[]);
+
+    text
+}
+
+pub fn from_intel_hex(text: &str) -> Result<[u8; MEMORY_SIZE], Chip8Error> {
+    let mut memory: [u8; MEMORY_SIZE] = [0; MEMORY_SIZE];
+
+    for (index, raw_line) in text.lines().enumerate() {
+        let line_number: usize = index + 1;
+        let invalid = |reason: &'static str| Chip8Error::InvalidImage { line: line_number, reason };
+
+        let line: &str = raw_line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let hex: &str = line.strip_prefix(':').ok_or_else(|| invalid("missing ':'"))?;
+        if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(invalid("non-hex digit"));
+        }
+        if !hex.len().is_multiple_of(2) || hex.len() < 10 {
+            return Err(invalid("truncated record"));
+        }
+
+        let bytes: Vec<u8> = (0..hex.len())
+            .step_by(2)
+            .map(|at| u8::from_str_radix(&hex[at..at + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| invalid("non-hex digit"))?;
+
+        let checksum: u8 = bytes.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte));
+        if checksum != 0 {
+            return Err(invalid("checksum mismatch"));
+        }
+
+        let length: usize = bytes[0] as usize;
+        let address: usize = u16::from_be_bytes([bytes[1], bytes[2]]) as usize;
+        let record_type: u8 = bytes[3];
+        if bytes.len() != length + 5 {
+            return Err(invalid("length does not match data"));
+        }
+
+        match record_type {
+            RECORD_DATA => {
+                if address + length > MEMORY_SIZE {
+                    return Err(invalid("data beyond 4KB"));
+                }
+                memory[address..address + length].copy_from_slice(&bytes[4..4 + length]);
+            }
+            RECORD_EOF => return Ok(memory),
+            _ => return Err(invalid("unsupported record type")),
+        }
+    }
+
+    Ok(memory)
+}
+
+// Append one `:LLAAAATT<data>CC` record
+fn push_record(text: &mut String, address: u16, record_type: u8, data: &[u8]) {
+    let mut bytes: Vec<u8> = vec![data.len() as u8];
+    bytes.extend_from_slice(&address.to_be_bytes());
+    bytes.push(record_type);
+    bytes.extend_from_slice(data);
+
+    let sum: u8 = bytes.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte));
+    bytes.push(sum.wrapping_neg());
+
+    text.push(':');
+    for byte in bytes {
+        let _ = write!(text, "{:02X}", byte);
+    }
+    text.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intel_hex_round_trips() {
+        let mut memory: [u8; MEMORY_SIZE] = [0; MEMORY_SIZE];
+        memory[0x200..0x204].copy_from_slice(&[0x12, 0x34, 0xAB, 0xCD]);
+        memory[MEMORY_SIZE - 1] = 0xFF;
+
+        assert_eq!(from_intel_hex(&to_intel_hex(&memory)).unwrap(), memory);
+    }
+
+    #[test]
+    fn non_ascii_record_is_an_error() {
+        let text: &str = ":020000000102FB\n:0é100000102EB\n";
+
+        assert!(matches!(from_intel_hex(text), Err(Chip8Error::InvalidImage { line: 2, .. })));
+    }
+
+    #[test]
+    fn bad_checksum_is_an_error() {
+        assert!(matches!(from_intel_hex(":0200000001020C\n"), Err(Chip8Error::InvalidImage { line: 1, .. })));
+    }
+
+    #[test]
+    fn oversized_binary_is_too_large() {
+        let bytes: Vec<u8> = vec![0; MEMORY_SIZE + 1];
+
+        assert!(matches!(from_binary(&bytes), Err(Chip8Error::TooLarge { len, .. }) if len == MEMORY_SIZE + 1));
+        assert_eq!(from_binary(&[7; 3]).unwrap()[..4], [7, 7, 7, 0]);
+    }
+}
@@ -1,2 +1,21 @@
+pub mod audit;
+pub mod banking;
 pub mod cpu;
-pub mod constants;
\ No newline at end of file
+pub mod constants;
+pub mod display;
+pub mod error;
+pub mod extensions;
+pub mod hash;
+pub mod hooks;
+pub mod instruction;
+pub mod memory_image;
+pub mod ports;
+pub mod quirks;
+pub mod schedule;
+pub mod serial;
+pub mod shared;
+pub mod sram;
+pub mod state_dump;
+pub mod stats;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
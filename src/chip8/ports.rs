@@ -0,0 +1,80 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Host Ports
+// Experimental peripheral for homebrew ROMs: a cycle timer, a random
+// number source and the host's date and time, readable from the
+// unused interpreter area below the program. There is no memory bus
+// to intercept reads, so a before-hook rewrites the ports ahead of
+// every instruction. Off unless attached; no real CHIP-8 has it.
+//
+//   0x1F0..0x1F4  cycles executed, u32 big-endian (wraps)
+//   0x1F4         random byte, new for every instruction
+//   0x1F5..0x1F8  reserved, read as 0
+//   0x1F8..0x1FA  UTC year, u16 big-endian
+//   0x1FA         month 1-12     0x1FB  day 1-31
+//   0x1FC         hour 0-23      0x1FD  minute 0-59
+//   0x1FE         second 0-59    0x1FF  weekday, 0 = Sunday
+// ───────────────────────────────────────────────────────────────
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+
+use crate::chip8::cpu::Chip8;
+use crate::chip8::hooks::{HookAction, HookId};
+
+// First port address and the number of port bytes
+pub const PORTS_START: u16 = 0x1F0;
+pub const PORTS_LEN: usize = 16;
+
+// Install the ports on `chip8`. The random bytes come from their own
+// generator, seeded from the machine's seed so they do not disturb CXNN.
+pub fn attach(chip8: &mut Chip8) -> HookId {
+    let mut rng: StdRng = StdRng::seed_from_u64(chip8.seed());
+
+    chip8.on_opcode(0, 0, move |chip8, _| {
+        let start: usize = PORTS_START as usize;
+        chip8.memory[start..start + PORTS_LEN].copy_from_slice(&port_bytes(chip8.stats.cycles, rng.random()));
+        HookAction::Continue
+    })
+}
+
+fn port_bytes(cycles: u64, random: u8) -> [u8; PORTS_LEN] {
+    let mut ports: [u8; PORTS_LEN] = [0; PORTS_LEN];
+
+    ports[0..4].copy_from_slice(&(cycles as u32).to_be_bytes());
+    ports[4] = random;
+
+    let seconds: u64 = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs());
+    let days: i64 = (seconds / 86_400) as i64;
+    let (year, month, day) = civil_from_days(days);
+
+    ports[8..10].copy_from_slice(&(year as u16).to_be_bytes());
+    ports[10] = month;
+    ports[11] = day;
+    ports[12] = (seconds / 3600 % 24) as u8;
+    ports[13] = (seconds / 60 % 60) as u8;
+    ports[14] = (seconds % 60) as u8;
+
+    // 1970-01-01 was a Thursday
+    ports[15] = ((days + 4) % 7) as u8;
+
+    ports
+}
+
+// Gregorian (year, month, day) of a day count since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let shifted: i64 = days + 719_468;
+    let era: i64 = shifted.div_euclid(146_097);
+    let day_of_era: i64 = shifted.rem_euclid(146_097);
+    let year_of_era: i64 = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year: i64 = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+
+    // Months counted from March, so the leap day comes last
+    let month_index: i64 = (5 * day_of_year + 2) / 153;
+    let day: u8 = (day_of_year - (153 * month_index + 2) / 5 + 1) as u8;
+    let month: u8 = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u8;
+    let year: i64 = year_of_era + era * 400 + (month <= 2) as i64;
+
+    (year, month, day)
+}
@@ -0,0 +1,24 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Quirks
+// Behaviour differences between CHIP-8 platforms and interpreters.
+// Every quirk defaults to the original COSMAC VIP behaviour.
+// ───────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Default)]
+pub struct Quirks {
+    // DXYN clips sprite rows past the bottom edge instead of wrapping them
+    // to the top, and sets VF to the number of rows that collided or were
+    // clipped (SCHIP-style) instead of just 0/1
+    pub collision_row_count: bool,
+
+    // DXYN and 00E0 show on screen as soon as they run (SCHIP-style)
+    // instead of the frame being latched at the next 60Hz tick. Changes
+    // how much flicker erase-and-redraw sprites show.
+    pub immediate_display: bool,
+}
+
+impl Quirks {
+
+    // Every quirk by field name, for capability reports and config checks
+    pub const NAMES: [&'static str; 2] = ["collision_row_count", "immediate_display"];
+}
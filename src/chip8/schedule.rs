@@ -0,0 +1,154 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Scheduled Callbacks
+// One-shot callbacks keyed by emulated time (cycles executed or
+// frames ticked in the session) instead of wall-clock time, so a
+// script or trigger fires at exactly the same machine state in
+// real time, fast-forward, headless runs and replays.
+// ───────────────────────────────────────────────────────────────
+
+use std::collections::BTreeMap;
+
+use crate::chip8::cpu::Chip8;
+
+pub type ScheduledCallback = Box<dyn FnOnce(&mut Chip8) + Send>;
+
+// Handle returned on scheduling, used to cancel the callback again
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ScheduleId(u64);
+
+// Callbacks due at the same moment run in the order they were scheduled
+#[derive(Default)]
+pub struct Schedule {
+    by_cycle: BTreeMap<u64, Vec<(ScheduleId, ScheduledCallback)>>,
+    by_frame: BTreeMap<u64, Vec<(ScheduleId, ScheduledCallback)>>,
+    next_id: u64,
+}
+
+impl Schedule {
+
+    pub fn at_cycle(&mut self, cycle: u64, callback: ScheduledCallback) -> ScheduleId {
+        let id: ScheduleId = self.allocate_id();
+        self.by_cycle.entry(cycle).or_default().push((id, callback));
+        id
+    }
+
+    pub fn at_frame(&mut self, frame: u64, callback: ScheduledCallback) -> ScheduleId {
+        let id: ScheduleId = self.allocate_id();
+        self.by_frame.entry(frame).or_default().push((id, callback));
+        id
+    }
+
+    // Returns true if the callback was still waiting
+    pub fn cancel(&mut self, id: ScheduleId) -> bool {
+        for queue in [&mut self.by_cycle, &mut self.by_frame] {
+            for callbacks in queue.values_mut() {
+                if let Some(index) = callbacks.iter().position(|(scheduled, _)| *scheduled == id) {
+                    drop(callbacks.remove(index));
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    // Take out every cycle callback due by `cycles`, earliest first
+    pub fn take_due_cycles(&mut self, cycles: u64) -> Vec<ScheduledCallback> {
+        take_due(&mut self.by_cycle, cycles)
+    }
+
+    // Take out every frame callback due by `frames`, earliest first
+    pub fn take_due_frames(&mut self, frames: u64) -> Vec<ScheduledCallback> {
+        take_due(&mut self.by_frame, frames)
+    }
+
+    fn allocate_id(&mut self) -> ScheduleId {
+        self.next_id += 1;
+        ScheduleId(self.next_id)
+    }
+}
+
+fn take_due(queue: &mut BTreeMap<u64, Vec<(ScheduleId, ScheduledCallback)>>, now: u64) -> Vec<ScheduledCallback> {
+    if queue.first_key_value().is_none_or(|(&moment, _)| moment > now) {
+        return Vec::new();
+    }
+
+    let later: BTreeMap<u64, Vec<(ScheduleId, ScheduledCallback)>> = queue.split_off(&now.saturating_add(1));
+    let due: BTreeMap<u64, Vec<(ScheduleId, ScheduledCallback)>> = std::mem::replace(queue, later);
+
+    due.into_values().flatten().map(|(_, callback)| callback).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    // Machine spinning on 1200 with a log callbacks can write to
+    fn logged() -> (Chip8, Arc<Mutex<Vec<&'static str>>>) {
+        let mut chip8: Chip8 = Chip8::new();
+        chip8.load_rom(&[0x12, 0x00]).unwrap();
+        (chip8, Arc::new(Mutex::new(Vec::new())))
+    }
+
+    fn note(log: &Arc<Mutex<Vec<&'static str>>>, text: &'static str) -> impl FnOnce(&mut Chip8) + Send + 'static {
+        let log: Arc<Mutex<Vec<&'static str>>> = Arc::clone(log);
+        move |_| log.lock().unwrap().push(text)
+    }
+
+    #[test]
+    fn callback_runs_right_after_its_cycle() {
+        let (mut chip8, log) = logged();
+        chip8.at_cycle(3, note(&log, "third"));
+        chip8.at_cycle(4, note(&log, "fourth"));
+
+        chip8.cycle();
+        chip8.cycle();
+        assert!(log.lock().unwrap().is_empty());
+
+        chip8.cycle();
+        assert_eq!(*log.lock().unwrap(), ["third"]);
+
+        chip8.cycle();
+        assert_eq!(*log.lock().unwrap(), ["third", "fourth"]);
+    }
+
+    #[test]
+    fn callback_at_a_past_moment_runs_after_the_next_cycle() {
+        let (mut chip8, log) = logged();
+        for _ in 0..5 {
+            chip8.cycle();
+        }
+
+        chip8.at_cycle(2, note(&log, "late"));
+        chip8.at_cycle(chip8.stats.cycles, note(&log, "now"));
+        assert!(log.lock().unwrap().is_empty());
+
+        chip8.cycle();
+        assert_eq!(*log.lock().unwrap(), ["late", "now"]);
+    }
+
+    #[test]
+    fn callbacks_at_the_same_moment_run_in_scheduling_order() {
+        let (mut chip8, log) = logged();
+        chip8.at_frame(1, note(&log, "first"));
+        chip8.at_frame(1, note(&log, "second"));
+        chip8.tick_timers();
+
+        assert_eq!(*log.lock().unwrap(), ["first", "second"]);
+    }
+
+    #[test]
+    fn cancelled_callback_never_runs() {
+        let (mut chip8, log) = logged();
+        let cancelled: ScheduleId = chip8.at_cycle(1, note(&log, "cancelled"));
+        chip8.at_cycle(1, note(&log, "kept"));
+
+        assert!(chip8.cancel_scheduled(cancelled));
+        assert!(!chip8.cancel_scheduled(cancelled));
+        chip8.cycle();
+
+        assert_eq!(*log.lock().unwrap(), ["kept"]);
+    }
+}
@@ -0,0 +1,218 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Serial Link
+// Experimental peripheral that lets homebrew ROMs talk to external
+// hardware through a host serial device, using a mailbox in the
+// unused interpreter area below the host ports. Like the host
+// ports, a before-hook syncs the mailbox ahead of every instruction:
+// a byte the ROM queued with its previous instruction is sent, and
+// the next received byte is posted once the last one was taken.
+// Off unless attached; no real CHIP-8 has it.
+//
+//   0x1E0  RX ready: 1 while 0x1E1 holds a byte the ROM has not
+//          taken; write 0 to take it and get the next one
+//   0x1E1  RX byte
+//   0x1E2  TX request: write 1 to send 0x1E3; reads 0 once sent
+//   0x1E3  TX byte
+//   0x1E4  link down: 1 once the device closed or failed
+//   0x1E5..0x1F0  reserved, left alone
+//
+// Optional opcodes, claimed from invalid 5XYN space, wrap the same
+// mailbox: 5X0E (SEND VX) queues VX, 5X0F (RECV VX) takes a received
+// byte into VX and sets VF to 1, or sets VF to 0 if none is waiting.
+//
+// The device is used as the host configured it (baud rate, framing),
+// e.g. with stty.
+// ───────────────────────────────────────────────────────────────
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use crate::chip8::cpu::Chip8;
+use crate::chip8::error::Chip8Error;
+use crate::chip8::hooks::{HookAction, HookId};
+
+// First mailbox address and the number of bytes the link reserves
+pub const SERIAL_START: u16 = 0x1E0;
+pub const SERIAL_LEN: usize = 16;
+
+const RX_READY: usize = SERIAL_START as usize;
+const RX_BYTE: usize = RX_READY + 1;
+const TX_REQUEST: usize = RX_READY + 2;
+const TX_BYTE: usize = RX_READY + 3;
+const LINK_DOWN: usize = RX_READY + 4;
+
+pub struct SerialLink {
+    // Bytes received from the device, in order
+    incoming: Receiver<u8>,
+    outgoing: Box<dyn Write + Send>,
+    down: bool,
+}
+
+impl SerialLink {
+
+    // Link `incoming` bytes and an `outgoing` stream to a ROM
+    pub fn new(incoming: Receiver<u8>, outgoing: impl Write + Send + 'static) -> Self {
+        Self { incoming, outgoing: Box::new(outgoing), down: false }
+    }
+
+    // Open a host serial device (e.g. /dev/ttyUSB0). A thread reads it so
+    // the emulation never blocks waiting for a byte.
+    pub fn open(path: &Path) -> Result<Self, Chip8Error> {
+        let device: File = OpenOptions::new().read(true).write(true).open(path)?;
+        let mut reader: File = device.try_clone()?;
+
+        let (sender, incoming) = mpsc::channel();
+        thread::spawn(move || {
+            let mut buffer: [u8; 256] = [0; 256];
+            while let Ok(len @ 1..) = reader.read(&mut buffer) {
+                if buffer[..len].iter().any(|&byte| sender.send(byte).is_err()) {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self::new(incoming, device))
+    }
+
+    fn sync(&mut self, memory: &mut [u8]) {
+        if memory[TX_REQUEST] != 0 {
+            let sent = self.outgoing.write_all(&[memory[TX_BYTE]]).and_then(|()| self.outgoing.flush());
+            self.down |= sent.is_err();
+            memory[TX_REQUEST] = 0;
+        }
+
+        if memory[RX_READY] == 0 {
+            match self.incoming.try_recv() {
+                Ok(byte) => {
+                    memory[RX_BYTE] = byte;
+                    memory[RX_READY] = 1;
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => self.down = true,
+            }
+        }
+
+        memory[LINK_DOWN] = self.down as u8;
+    }
+}
+
+// Install the mailbox on `chip8`, plus SEND and RECV if `opcodes` is set
+pub fn attach(chip8: &mut Chip8, mut link: SerialLink, opcodes: bool) -> Result<Vec<HookId>, Chip8Error> {
+    let mut ids: Vec<HookId> = vec![chip8.on_opcode(0, 0, move |chip8, _| {
+        link.sync(&mut chip8.memory);
+        HookAction::Continue
+    })];
+
+    if opcodes {
+        // The mailbox hook runs first, so SEND's byte goes out before the
+        // next instruction and RECV sees the latest byte
+        ids.push(chip8.register_extension("5X0E", "SEND VX", |chip8, fields| {
+            chip8.memory[TX_BYTE] = chip8.v[fields.x as usize];
+            chip8.memory[TX_REQUEST] = 1;
+        })?);
+        ids.push(chip8.register_extension("5X0F", "RECV VX", |chip8, fields| {
+            let ready: bool = chip8.memory[RX_READY] != 0;
+            if ready {
+                chip8.v[fields.x as usize] = chip8.memory[RX_BYTE];
+                chip8.memory[RX_READY] = 0;
+            }
+            chip8.v[0xF] = ready as u8;
+        })?);
+    }
+
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::Sender;
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    // Outgoing stream whose bytes the test can read back
+    #[derive(Clone, Default)]
+    struct Sink(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Sink {
+        fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(bytes);
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn linked(rom: &[u8], opcodes: bool) -> (Chip8, Sender<u8>, Sink) {
+        let (sender, incoming) = mpsc::channel();
+        let sink: Sink = Sink::default();
+
+        let mut chip8: Chip8 = Chip8::new();
+        chip8.load_rom(rom).unwrap();
+        attach(&mut chip8, SerialLink::new(incoming, sink.clone()), opcodes).unwrap();
+
+        (chip8, sender, sink)
+    }
+
+    #[test]
+    fn byte_queued_in_the_mailbox_is_sent() {
+        // A1E2: I = TX request; 6001: V0 = 1; 6141: V1 = 'A'; F155: queue V1
+        let (mut chip8, _sender, sink) = linked(&[0xA1, 0xE2, 0x60, 0x01, 0x61, 0x41, 0xF1, 0x55, 0x12, 0x08], false);
+        for _ in 0..5 {
+            chip8.cycle();
+        }
+
+        assert_eq!(*sink.0.lock().unwrap(), b"A");
+        assert_eq!(chip8.memory[TX_REQUEST], 0);
+    }
+
+    #[test]
+    fn next_byte_is_posted_once_the_last_was_taken() {
+        // A1E0: I = RX ready; 6000: V0 = 0; F055: take the byte
+        let (mut chip8, sender, _sink) = linked(&[0xA1, 0xE0, 0x60, 0x00, 0xF0, 0x55, 0x12, 0x06], false);
+        sender.send(0x5A).unwrap();
+        sender.send(0x5B).unwrap();
+
+        chip8.cycle();
+        assert_eq!((chip8.memory[RX_READY], chip8.memory[RX_BYTE]), (1, 0x5A));
+
+        // Still untaken before F055 runs
+        chip8.cycle();
+        assert_eq!(chip8.memory[RX_BYTE], 0x5A);
+
+        chip8.cycle();
+        chip8.cycle();
+        assert_eq!((chip8.memory[RX_READY], chip8.memory[RX_BYTE]), (1, 0x5B));
+    }
+
+    #[test]
+    fn opcodes_send_and_receive_through_the_mailbox() {
+        // 6143: V1 = 'C'; 510E: SEND V1; 520F: RECV V2; 530F: RECV V3
+        let (mut chip8, sender, sink) = linked(&[0x61, 0x43, 0x51, 0x0E, 0x52, 0x0F, 0x53, 0x0F, 0x12, 0x08], true);
+        sender.send(0x7E).unwrap();
+
+        chip8.cycle();
+        chip8.cycle();
+        chip8.cycle();
+        assert_eq!((chip8.v[2], chip8.v[0xF]), (0x7E, 1));
+
+        chip8.cycle();
+        assert_eq!((chip8.v[3], chip8.v[0xF]), (0, 0));
+        assert_eq!(*sink.0.lock().unwrap(), b"C");
+        assert_eq!(chip8.disassemble(0x520F), "RECV V2");
+    }
+
+    #[test]
+    fn closed_device_reports_the_link_down() {
+        let (mut chip8, sender, _sink) = linked(&[0x12, 0x00], false);
+        drop(sender);
+
+        chip8.cycle();
+        assert_eq!(chip8.memory[LINK_DOWN], 1);
+    }
+}
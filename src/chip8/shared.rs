@@ -0,0 +1,100 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Shared Handle
+// A cloneable handle to one machine for embedders that run the
+// emulation on its own thread and inspect it from a UI thread.
+//
+// Concurrency: the machine sits behind a single mutex. Every call
+// holds the lock for its whole duration, so a reader never sees a
+// half-executed instruction or a frame in the middle of a draw.
+// The emulation thread should take the lock once per frame
+// (run_frame) rather than once per cycle, and the UI thread should
+// copy what it needs out (snapshot, or with() for anything else)
+// instead of holding the lock while it renders. Hooks and
+// scheduled callbacks run with the lock held and must not use the
+// handle themselves, or they deadlock. If a thread panics while
+// holding the lock the machine is still handed out: a CHIP-8 state
+// is always valid, so the poison is ignored.
+// ───────────────────────────────────────────────────────────────
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crate::chip8::constants::*;
+use crate::chip8::cpu::Chip8;
+use crate::chip8::display::DisplayBuffer;
+
+// Copy of the state a debugger or status bar shows, taken at a frame
+// or instruction boundary
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    pub v: [u8; NUM_REGISTERS],
+    pub i: u16,
+    pub pc: u16,
+    pub stack: [u16; STACK_SIZE],
+    pub sp: u8,
+    pub delay_timer: u8,
+    pub sound_timer: u8,
+    pub keys: [bool; NUM_KEYS],
+
+    // Last completed frame, as a frontend would show it
+    pub frame: DisplayBuffer,
+
+    // Cycles run in the session
+    pub cycles: u64,
+}
+
+#[derive(Clone)]
+pub struct SharedChip8 {
+    inner: Arc<Mutex<Chip8>>,
+}
+
+impl SharedChip8 {
+
+    pub fn new(chip8: Chip8) -> Self {
+        Self { inner: Arc::new(Mutex::new(chip8)) }
+    }
+
+    // Exclusive access until the guard is dropped; keep it short
+    pub fn lock(&self) -> MutexGuard<'_, Chip8> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // Run `f` with the machine locked
+    pub fn with<R>(&self, f: impl FnOnce(&mut Chip8) -> R) -> R {
+        f(&mut self.lock())
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        let chip8 = self.lock();
+        Snapshot {
+            v: chip8.v,
+            i: chip8.i,
+            pc: chip8.pc,
+            stack: chip8.stack,
+            sp: chip8.sp,
+            delay_timer: chip8.delay_timer,
+            sound_timer: chip8.sound_timer,
+            keys: chip8.keys,
+            frame: *chip8.frame(),
+            cycles: chip8.stats.cycles,
+        }
+    }
+
+    // One 60Hz frame under a single lock, for the emulation thread
+    pub fn run_frame(&self) {
+        self.lock().run_frame();
+    }
+
+    // Press or release a key from an input thread
+    pub fn set_key(&self, key: usize, pressed: bool) {
+        if let Some(state) = self.lock().keys.get_mut(key) {
+            *state = pressed;
+        }
+    }
+
+    // The machine back once this is the last handle, otherwise the handle
+    pub fn into_inner(self) -> Result<Chip8, Self> {
+        Arc::try_unwrap(self.inner)
+            .map(|mutex| mutex.into_inner().unwrap_or_else(PoisonError::into_inner))
+            .map_err(|inner| Self { inner })
+    }
+}
@@ -0,0 +1,79 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Battery-backed SRAM
+// Maps a small region of RAM to a file so homebrew can keep high
+// scores and settings between runs, like cartridge save RAM. The
+// region survives resets and is written back when the run ends.
+// ───────────────────────────────────────────────────────────────
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::chip8::constants::{MEMORY_SIZE, PROGRAM_START};
+use crate::chip8::cpu::Chip8;
+use crate::chip8::error::Chip8Error;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SramRegion {
+    pub start: u16,
+    pub len: u16,
+}
+
+impl SramRegion {
+    // Regions must lie in program space, so the font cannot be overwritten
+    pub fn new(start: u16, len: u16) -> Option<Self> {
+        let end: usize = start as usize + len as usize;
+        (len > 0 && start >= PROGRAM_START && end <= MEMORY_SIZE).then_some(Self { start, len })
+    }
+
+    fn range(&self) -> std::ops::Range<usize> {
+        self.start as usize..self.start as usize + self.len as usize
+    }
+}
+
+pub struct Sram {
+    region: SramRegion,
+    path: PathBuf,
+    contents: Vec<u8>,
+}
+
+impl Sram {
+
+    // Open the save file for `region`. A missing file starts out zeroed.
+    pub fn open(region: SramRegion, path: &Path) -> Result<Self, Chip8Error> {
+        let contents: Vec<u8> = match fs::read(path) {
+            Ok(bytes) if bytes.len() == region.len as usize => bytes,
+            Ok(bytes) => {
+                return Err(Chip8Error::SramSizeMismatch { expected: region.len as usize, found: bytes.len() });
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => vec![0; region.len as usize],
+            Err(err) => return Err(err.into()),
+        };
+
+        Ok(Self { region, path: path.to_path_buf(), contents })
+    }
+
+    // Default save file for a ROM: the ROM path with a .sram extension
+    pub fn path_for_rom(rom_path: &Path) -> PathBuf {
+        rom_path.with_extension("sram")
+    }
+
+    // Copy the saved contents into the machine's RAM
+    pub fn restore(&self, chip8: &mut Chip8) {
+        chip8.memory[self.region.range()].copy_from_slice(&self.contents);
+    }
+
+    // Take the region's current contents from the machine's RAM
+    pub fn capture(&mut self, chip8: &Chip8) {
+        self.contents.copy_from_slice(&chip8.memory[self.region.range()]);
+    }
+
+    pub fn save(&self) -> Result<(), Chip8Error> {
+        fs::write(&self.path, &self.contents)?;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
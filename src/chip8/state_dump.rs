@@ -0,0 +1,111 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Structured State Dump
+// Describes the machine (registers, stack, timers, display and the
+// next instruction) as JSON or plain text for external tooling.
+// ───────────────────────────────────────────────────────────────
+
+use std::io::{self, Write};
+
+use crate::chip8::cpu::Chip8;
+use crate::chip8::display::DisplayBuffer;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DumpFormat {
+    Json,
+    Text,
+}
+
+pub fn write_state<W: Write>(chip8: &Chip8, format: DumpFormat, output: &mut W) -> io::Result<()> {
+    match format {
+        DumpFormat::Json => write_json(chip8, output),
+        DumpFormat::Text => write_text(chip8, output),
+    }
+}
+
+// Each display row as a string of '0'/'1' characters, left to right
+fn row_bitstrings(display: &DisplayBuffer) -> Vec<String> {
+    display
+        .iter()
+        .map(|row| row.iter().map(|&pixel| if pixel { '1' } else { '0' }).collect())
+        .collect()
+}
+
+fn join_numbers<T: ToString>(values: &[T]) -> String {
+    values.iter().map(|value| value.to_string()).collect::<Vec<String>>().join(", ")
+}
+
+// ===============================================================
+// JSON
+// ===============================================================
+
+fn write_json<W: Write>(chip8: &Chip8, output: &mut W) -> io::Result<()> {
+    let active_stack: &[u16] = &chip8.stack[..chip8.sp as usize];
+    let keys: Vec<u8> = (0..chip8.keys.len() as u8).filter(|&key| chip8.keys[key as usize]).collect();
+
+    writeln!(output, "{{")?;
+    writeln!(output, "  \"pc\": {},", chip8.pc)?;
+    writeln!(output, "  \"i\": {},", chip8.i)?;
+    writeln!(output, "  \"v\": [{}],", join_numbers(&chip8.v))?;
+    writeln!(output, "  \"sp\": {},", chip8.sp)?;
+    writeln!(output, "  \"stack\": [{}],", join_numbers(active_stack))?;
+    writeln!(output, "  \"delay_timer\": {},", chip8.delay_timer)?;
+    writeln!(output, "  \"sound_timer\": {},", chip8.sound_timer)?;
+    writeln!(output, "  \"keys_pressed\": [{}],", join_numbers(&keys))?;
+
+    match chip8.waiting_for_key() {
+        Some(x) => writeln!(output, "  \"waiting_for_key\": {},", x)?,
+        None => writeln!(output, "  \"waiting_for_key\": null,")?,
+    }
+
+    match chip8.opcode_at_pc() {
+        Some(opcode) => {
+            writeln!(output, "  \"next_instruction\": {{")?;
+            writeln!(output, "    \"address\": {},", chip8.pc)?;
+            writeln!(output, "    \"opcode\": \"{:04X}\",", opcode)?;
+            writeln!(output, "    \"pattern\": \"{}\",", chip8.pattern_of(opcode))?;
+            writeln!(output, "    \"disassembly\": \"{}\"", chip8.disassemble(opcode))?;
+            writeln!(output, "  }},")?;
+        }
+        None => writeln!(output, "  \"next_instruction\": null,")?,
+    }
+
+    let rows: Vec<String> = row_bitstrings(&chip8.display);
+    writeln!(output, "  \"display\": [")?;
+    for (index, row) in rows.iter().enumerate() {
+        let separator: &str = if index + 1 < rows.len() { "," } else { "" };
+        writeln!(output, "    \"{}\"{}", row, separator)?;
+    }
+    writeln!(output, "  ]")?;
+    writeln!(output, "}}")
+}
+
+// ===============================================================
+// Plain text
+// ===============================================================
+
+fn write_text<W: Write>(chip8: &Chip8, output: &mut W) -> io::Result<()> {
+    writeln!(output, "PC {:03X}  I {:03X}  SP {}  DT {}  ST {}",
+        chip8.pc, chip8.i, chip8.sp, chip8.delay_timer, chip8.sound_timer)?;
+
+    for (index, value) in chip8.v.iter().enumerate() {
+        write!(output, "V{:X}={:02X}{}", index, value, if index % 8 == 7 { "\n" } else { " " })?;
+    }
+
+    let active_stack: Vec<String> =
+        chip8.stack[..chip8.sp as usize].iter().map(|address| format!("{:03X}", address)).collect();
+    writeln!(output, "Stack [{}]", active_stack.join(" "))?;
+
+    if let Some(x) = chip8.waiting_for_key() {
+        writeln!(output, "Waiting for key -> V{:X}", x)?;
+    }
+
+    if let Some(opcode) = chip8.opcode_at_pc() {
+        writeln!(output, "Next  {:03X}: {:04X}  {}", chip8.pc, opcode, chip8.disassemble(opcode))?;
+    }
+
+    for row in row_bitstrings(&chip8.display) {
+        writeln!(output, "{}", row.replace('0', ".").replace('1', "#"))?;
+    }
+
+    Ok(())
+}
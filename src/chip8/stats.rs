@@ -0,0 +1,138 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Runtime Statistics
+// Per-session counters describing what a ROM spends its time on.
+// ───────────────────────────────────────────────────────────────
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use crate::chip8::constants::TIMER_HZ;
+
+// One completed FX0A wait. Cycles depend on how often the host polled
+// the keypad (every cycle headless, once a frame in real time); frames
+// measure emulated time whatever the CPU speed.
+pub struct KeyWait {
+    pub cycles: u64,
+    pub frames: u64,
+}
+
+// One continuous stretch of sound, in 60Hz frames
+pub struct SoundEvent {
+    pub start_frame: u64,
+    pub frames: u64,
+}
+
+#[derive(Default)]
+pub struct Stats {
+    // Total cycles run, including cycles spent blocked in FX0A
+    pub cycles: u64,
+
+    // Instructions executed: cycles minus the ones spent blocked in FX0A
+    pub instructions: u64,
+
+    // Executions per instruction form, keyed by opcode pattern ("8XY4")
+    pub opcode_counts: BTreeMap<&'static str, u64>,
+
+    // DXYN sprites drawn, and how many of them reported a collision
+    pub sprites_drawn: u64,
+    pub sprite_collisions: u64,
+
+    // Every completed FX0A wait
+    pub key_waits: Vec<KeyWait>,
+
+    // Cycles spent so far in the wait that is still in progress, and the
+    // frame it started on
+    pub current_key_wait: u64,
+    pub current_key_wait_start: u64,
+
+    // Timer ticks (frames) run
+    pub frames: u64,
+
+    // Every sound start/stop; the last event may still be in progress
+    pub sound_events: Vec<SoundEvent>,
+
+    // Whether the previous frame was sounding, extending the last event
+    sounding: bool,
+}
+
+impl Stats {
+
+    // Called once per timer tick with whether the buzzer sounded this frame
+    pub fn record_sound_frame(&mut self, sounding: bool) {
+        if sounding {
+            match self.sound_events.last_mut() {
+                Some(event) if self.sounding => event.frames += 1,
+                _ => self.sound_events.push(SoundEvent { start_frame: self.frames, frames: 1 }),
+            }
+        }
+
+        self.sounding = sounding;
+        self.frames += 1;
+    }
+
+    // Write the counters as `category,name,value` CSV rows
+    pub fn write_csv<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        writeln!(output, "category,name,value")?;
+        writeln!(output, "session,cycles,{}", self.cycles)?;
+        writeln!(output, "session,instructions,{}", self.instructions)?;
+        writeln!(output, "session,frames,{}", self.frames)?;
+
+        for (pattern, count) in self.opcode_counts.iter() {
+            writeln!(output, "opcode,{},{}", pattern, count)?;
+        }
+
+        writeln!(output, "draw,sprites,{}", self.sprites_drawn)?;
+        writeln!(output, "draw,collisions,{}", self.sprite_collisions)?;
+
+        // Wait lengths in cycles, and durations in milliseconds of emulated time
+        for (index, wait) in self.key_waits.iter().enumerate() {
+            writeln!(output, "key_wait_cycles,{},{}", index, wait.cycles)?;
+            writeln!(output, "key_wait_ms,{},{:.1}", index, wait.frames as f64 * 1000.0 / TIMER_HZ as f64)?;
+        }
+
+        // Sound events by start frame, durations in milliseconds of emulated time
+        for event in self.sound_events.iter() {
+            writeln!(output, "sound_ms,{},{:.1}", event.start_frame, event.frames as f64 * 1000.0 / TIMER_HZ as f64)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chip8::cpu::Chip8;
+
+    // F00A: wait for a key into V0, then 1202: jump to self
+    const KEY_WAIT: [u8; 4] = [0xF0, 0x0A, 0x12, 0x02];
+
+    fn csv(chip8: &Chip8) -> String {
+        let mut output: Vec<u8> = Vec::new();
+        chip8.stats.write_csv(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    // Half a second of waiting is 500ms whether the keypad was polled once
+    // a frame or many times
+    #[test]
+    fn key_wait_duration_follows_frames_not_cycles() {
+        for cycles_per_frame in [1, 11, 50] {
+            let mut chip8: Chip8 = Chip8::new();
+            chip8.load_rom(&KEY_WAIT).unwrap();
+            chip8.cycle();
+
+            for _ in 0..30 {
+                for _ in 0..cycles_per_frame {
+                    chip8.cycle();
+                }
+                chip8.tick_timers();
+            }
+            chip8.keys[3] = true;
+            chip8.cycle();
+
+            let csv: String = csv(&chip8);
+            assert!(csv.contains("key_wait_ms,0,500.0\n"), "{}", csv);
+            assert!(csv.contains(&format!("key_wait_cycles,0,{}\n", 30 * cycles_per_frame + 1)), "{}", csv);
+        }
+    }
+}
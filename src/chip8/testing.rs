@@ -0,0 +1,153 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Test Helpers
+// Assertions and stepping helpers for tests written against the
+// core, by embedders and by this crate. Failures panic with a
+// readable picture of what differed, like the std assert macros.
+// ───────────────────────────────────────────────────────────────
+
+use crate::chip8::constants::{CYCLES_PER_FRAME, DISPLAY_HEIGHT, DISPLAY_WIDTH, NUM_REGISTERS};
+use crate::chip8::cpu::Chip8;
+
+#[track_caller]
+pub fn assert_pixel(chip8: &Chip8, x: usize, y: usize, on: bool) {
+    assert!(x < DISPLAY_WIDTH && y < DISPLAY_HEIGHT, "pixel ({}, {}) is off screen", x, y);
+
+    let actual: bool = chip8.display[y][x];
+    assert!(
+        actual == on,
+        "pixel ({}, {}) is {}, expected {}",
+        x,
+        y,
+        if actual { "on" } else { "off" },
+        if on { "on" } else { "off" }
+    );
+}
+
+#[track_caller]
+pub fn assert_reg(chip8: &Chip8, register: usize, value: u8) {
+    assert!(register < NUM_REGISTERS, "V{:X} is not a register", register);
+
+    let actual: u8 = chip8.v[register];
+    assert!(actual == value, "V{:X} is {:#04X}, expected {:#04X}", register, actual, value);
+}
+
+// Compare the top-left corner of the display against `art`: one line per
+// row, '#' or '1' for a lit pixel, '.', '0' or ' ' for a dark one. Blank
+// lines at either end are ignored and short lines are padded with dark
+// pixels, so the art only needs to cover the area under test.
+#[track_caller]
+pub fn assert_display_matches(chip8: &Chip8, art: &str) {
+    let rows: Vec<&str> = art.trim_matches('\n').lines().collect();
+    assert!(rows.len() <= DISPLAY_HEIGHT, "art is taller than the display");
+
+    let width: usize = rows.iter().map(|row| row.chars().count()).max().unwrap_or(0);
+    assert!(width <= DISPLAY_WIDTH, "art is wider than the display");
+
+    let mut mismatches: usize = 0;
+    let mut actual: String = String::new();
+
+    for (y, row) in rows.iter().enumerate() {
+        let expected: Vec<bool> = row
+            .chars()
+            .map(|ch| match ch {
+                '#' | '1' => true,
+                '.' | '0' | ' ' => false,
+                _ => panic!("unexpected character {:?} in display art", ch),
+            })
+            .collect();
+
+        for x in 0..width {
+            let lit: bool = chip8.display[y][x];
+            if lit != expected.get(x).copied().unwrap_or(false) {
+                mismatches += 1;
+            }
+            actual.push(if lit { '#' } else { '.' });
+        }
+        actual.push('\n');
+    }
+
+    assert!(
+        mismatches == 0,
+        "display differs in {} pixel(s); actual top-left {}x{}:\n{}",
+        mismatches,
+        width,
+        rows.len(),
+        actual
+    );
+}
+
+// Execute cycles until `condition` holds, ticking the timers every
+// CYCLES_PER_FRAME cycles as the real-time loop would. Returns the number
+// of cycles run; panics if the condition is still false after `max_cycles`.
+#[track_caller]
+pub fn run_until<F>(chip8: &mut Chip8, max_cycles: u64, mut condition: F) -> u64
+where
+    F: FnMut(&Chip8) -> bool,
+{
+    for cycles in 0..max_cycles {
+        if condition(chip8) {
+            return cycles;
+        }
+
+        chip8.cycle();
+        if (cycles + 1).is_multiple_of(CYCLES_PER_FRAME) {
+            chip8.tick_timers();
+        }
+    }
+
+    assert!(condition(chip8), "condition still false after {} cycles", max_cycles);
+    max_cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Two pixels lit on the diagonal from the top-left corner
+    fn lit_corner() -> Chip8 {
+        let mut chip8: Chip8 = Chip8::new();
+        chip8.display[0][0] = true;
+        chip8.display[1][1] = true;
+        chip8
+    }
+
+    #[test]
+    fn matching_art_passes() {
+        assert_display_matches(&lit_corner(), "
+#.
+.#
+");
+        assert_pixel(&lit_corner(), 1, 1, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "display differs in 1 pixel(s)")]
+    fn mismatched_art_fails() {
+        assert_display_matches(&lit_corner(), "
+##
+.#
+");
+    }
+
+    #[test]
+    #[should_panic(expected = "V3 is 0x00, expected 0x01")]
+    fn wrong_register_fails() {
+        assert_reg(&Chip8::new(), 3, 1);
+    }
+
+    #[test]
+    fn run_until_ticks_timers_once_a_frame() {
+        let mut chip8: Chip8 = Chip8::new();
+        chip8.delay_timer = 2;
+
+        let cycles: u64 = run_until(&mut chip8, 10 * CYCLES_PER_FRAME, |chip8| chip8.delay_timer == 0);
+
+        assert_eq!(cycles, 2 * CYCLES_PER_FRAME);
+    }
+
+    #[test]
+    #[should_panic(expected = "condition still false after 5 cycles")]
+    fn run_until_gives_up() {
+        run_until(&mut Chip8::new(), 5, |_| false);
+    }
+}
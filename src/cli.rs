@@ -0,0 +1,818 @@
+// ───────────────────────────────────────────────────────────────
+//  CHIP-8 Emulator — Rust
+//  Command-line interface definition.
+// ───────────────────────────────────────────────────────────────
+
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+
+use chip8_rs::chip8::quirks::Quirks;
+use chip8_rs::chip8::sram::SramRegion;
+use chip8_rs::chip8::state_dump::DumpFormat;
+#[cfg(feature = "terminal")]
+use chip8_rs::frontend::terminal::Graphics;
+#[cfg(any(feature = "sdl", feature = "wgpu", feature = "minifb"))]
+use chip8_rs::frontend::{CrtEffects, ScaleMode, WindowConfig};
+#[cfg(feature = "gamepad")]
+use chip8_rs::gamepad::GamepadMap;
+#[cfg(any(feature = "sdl", feature = "wgpu", feature = "minifb", feature = "terminal"))]
+use chip8_rs::keymap::KeyLayout;
+#[cfg(any(feature = "sdl", feature = "wgpu", feature = "minifb", feature = "terminal"))]
+use chip8_rs::palette::{Palette, Preset, Rgb};
+use chip8_rs::session::Session;
+use chip8_rs::stress::StressKind;
+use chip8_rs::visual_buzzer::BuzzerStyle;
+
+#[derive(Parser)]
+#[command(version, about = "CHIP-8 emulator", subcommand_negates_reqs = true)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Path to the ROM to run; windows and terminals show a splash screen without one
+    #[arg(conflicts_with = "playlist")]
+    pub rom: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub machine: MachineArgs,
+
+    /// Experimental: expose a cycle timer, random bytes and the UTC date/time at 0x1F0..0x200
+    #[arg(long)]
+    pub host_ports: bool,
+
+    /// Experimental: link the host serial device at PATH to the ROM through 0x1E0..0x1E5
+    #[arg(long, value_name = "PATH")]
+    pub serial: Option<PathBuf>,
+
+    /// With --serial, also decode 5X0E (SEND VX) and 5X0F (RECV VX)
+    #[arg(long, requires = "serial")]
+    pub serial_opcodes: bool,
+
+    /// Experimental: run ROMs past 0x1000 by cutting them into 512-byte banks mapped
+    /// into 0xE00..0x1000; the ROM selects one by writing its number to 0x1D0
+    #[arg(long, conflicts_with = "audit_determinism")]
+    pub banked: bool,
+
+    /// Run the ROM twice headlessly for FRAMES frames and report any nondeterminism
+    #[arg(long, value_name = "FRAMES")]
+    pub audit_determinism: Option<u64>,
+
+    /// With --audit-determinism, write both displays at the divergence side by side
+    /// to FILE (PNG), with an overlay highlighting the pixels that differ
+    #[arg(long, value_name = "FILE", requires = "audit_determinism")]
+    pub diff_image: Option<PathBuf>,
+
+    /// Run headlessly, writing one "frame state-hash display-hash" line per frame
+    /// to FILE, given as --emit-frame-hashes=FILE (stdout if no FILE is given)
+    #[arg(long, value_name = "FILE", num_args = 0..=1, require_equals = true)]
+    pub emit_frame_hashes: Option<Option<PathBuf>>,
+
+    /// Record tracing spans as folded stacks to FILE (for inferno/flamegraph)
+    #[cfg(feature = "tracing")]
+    #[arg(long, value_name = "FILE")]
+    pub trace_flame: Option<PathBuf>,
+
+    /// Run FRAMES frames as fast as possible without real-time pacing, then exit
+    #[arg(long)]
+    pub headless: bool,
+
+    /// Run headlessly and save a PNG of the display after each listed frame (e.g. 60,600,3600),
+    /// numbered like --emit-frame-hashes
+    #[arg(long, value_name = "FRAMES", value_delimiter = ',')]
+    pub shots: Vec<u64>,
+
+    /// Directory for --shots images, named <ROM name>-<frame>.png
+    #[arg(long, value_name = "DIR", default_value = ".", requires = "shots")]
+    pub shot_dir: PathBuf,
+
+    /// Size of each CHIP-8 pixel in --shots images
+    #[arg(long, value_name = "N", default_value_t = 4, value_parser = clap::value_parser!(u8).range(1..))]
+    pub shot_scale: u8,
+
+    /// Also tile every --shots image into one contact-sheet PNG
+    #[arg(long, value_name = "FILE", requires = "shots")]
+    pub montage: Option<PathBuf>,
+
+    /// Record the display as a looping animated GIF in Octo's colors (one GIF frame per change)
+    #[arg(long, value_name = "FILE")]
+    pub record_gif: Option<PathBuf>,
+
+    /// Size of each CHIP-8 pixel in --record-gif clips
+    #[arg(long, value_name = "N", default_value_t = 4, requires = "record_gif", value_parser = clap::value_parser!(u8).range(1..))]
+    pub gif_scale: u8,
+
+    /// Write per-opcode counts, draw counts, key-wait durations and sound events as CSV at exit
+    /// (real-time runs add tick jitter percentiles)
+    #[arg(long, value_name = "FILE")]
+    pub stats_out: Option<PathBuf>,
+
+    /// Send OSC "/chip8/sound" 1/0 messages to HOST:PORT when the sound timer starts/stops
+    #[arg(long, value_name = "HOST:PORT")]
+    pub osc_target: Option<String>,
+
+    /// Replace RAM with a memory image (.hex/.ihex = Intel HEX, otherwise raw binary)
+    /// after loading the ROM
+    #[arg(long, value_name = "FILE")]
+    pub load_memory: Option<PathBuf>,
+
+    /// Dump RAM to FILE when the run ends (.hex/.ihex = Intel HEX, otherwise raw binary)
+    #[arg(long, value_name = "FILE")]
+    pub dump_memory: Option<PathBuf>,
+
+    /// Persist RAM from hex address ADDR for LEN bytes (hex) between runs, like cartridge SRAM
+    #[arg(long, value_name = "ADDR:LEN", value_parser = parse_sram_region)]
+    pub sram: Option<SramRegion>,
+
+    /// Save file for --sram (default: the ROM path with a .sram extension)
+    #[arg(long, value_name = "FILE", requires = "sram")]
+    pub sram_file: Option<PathBuf>,
+
+    /// Kiosk mode: cycle through the ROMs listed in FILE (one path per line) in real time
+    #[arg(long, value_name = "FILE", conflicts_with = "sram")]
+    pub playlist: Option<PathBuf>,
+
+    /// Seconds each playlist ROM runs before the next one (a halted ROM moves on sooner)
+    #[arg(long, value_name = "SECONDS", default_value_t = 60.0, requires = "playlist", value_parser = parse_seconds)]
+    pub kiosk_seconds: f64,
+
+    /// Record the keypad from the start of the run and save it as an input macro to FILE
+    #[arg(long, value_name = "FILE")]
+    pub record_macro: Option<PathBuf>,
+
+    /// Load an input macro from FILE; F6 plays it
+    #[arg(long = "macro", value_name = "FILE")]
+    pub input_macro: Option<PathBuf>,
+
+    /// Play --macro automatically when the ROM starts and after every reset
+    #[arg(long, requires = "input_macro")]
+    pub macro_on_load: bool,
+
+    /// Type KEYS on the keypad once the ROM starts, one press per hex digit (e.g. 2A2A5F).
+    /// Text pasted into --terminal, or Ctrl+V in the SDL window, is typed the same way
+    #[arg(long, value_name = "KEYS")]
+    pub paste: Option<String>,
+
+    /// Warn when SECONDS pass with no display change, sound or key wait
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+    pub watchdog: Option<f64>,
+
+    /// Tune the CPU speed to the ROM while it runs, from how long it idles waiting on the delay
+    /// timer or a key, and save it to the ROM's settings (the ROM path with a .settings extension).
+    /// A ROM with saved settings always starts at its saved speed
+    #[arg(long)]
+    pub auto_speed: bool,
+
+    /// Stop a real-time run after SECONDS instead of running until killed
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+    pub run_for: Option<f64>,
+
+    /// Print CPU and timer tick jitter percentiles when a real-time run ends
+    #[arg(long)]
+    pub jitter_report: bool,
+
+    /// Log every host event (ROM loads, resets, sound, saves, recordings) to stderr
+    #[arg(long)]
+    pub log_events: bool,
+
+    /// Start from the ROM and options saved in a session FILE; options given here override it
+    #[arg(long, value_name = "FILE")]
+    pub session: Option<PathBuf>,
+
+    /// Save the ROM and options of this run as a session FILE for --session
+    #[arg(long, value_name = "FILE")]
+    pub save_session: Option<PathBuf>,
+
+    /// Start with the FPS/IPS/timer overlay shown (F3 toggles it)
+    #[arg(long)]
+    pub perf_overlay: bool,
+
+    /// Start with the keypad overlay shown, held keys highlighted (F4 toggles it)
+    #[arg(long)]
+    pub keypad_overlay: bool,
+
+    /// Show the buzzer on screen while the sound timer runs, as a flashing border
+    /// or a corner icon (each frontend picks one if STYLE is not given)
+    #[arg(long, value_enum, value_name = "STYLE", num_args = 0..=1)]
+    pub visual_buzzer: Option<Option<BuzzerArg>>,
+
+    /// Where real-time runs draw and read keys; defaults to the best window compiled in
+    #[arg(long, value_enum)]
+    pub backend: Option<BackendArg>,
+
+    /// Shorthand for --backend gpu
+    #[cfg(feature = "wgpu")]
+    #[arg(long, conflicts_with = "backend")]
+    pub gpu: bool,
+
+    /// Shorthand for --backend minifb
+    #[cfg(feature = "minifb")]
+    #[arg(long, conflicts_with = "backend")]
+    pub minifb: bool,
+
+    /// Window size as a multiple of the 64x32 display
+    #[cfg(any(feature = "sdl", feature = "wgpu", feature = "minifb"))]
+    #[arg(long, default_value_t = 10)]
+    pub scale: u32,
+
+    /// Let pixels fade out over N frames after turning off, hiding sprite flicker
+    #[cfg(any(feature = "sdl", feature = "wgpu", feature = "minifb"))]
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    pub phosphor: u8,
+
+    /// How the display fits the window (F8 cycles, -/= zoom the fixed scale)
+    #[cfg(any(feature = "sdl", feature = "wgpu", feature = "minifb"))]
+    #[arg(long, value_enum, default_value_t = ScalingArg::Integer)]
+    pub scaling: ScalingArg,
+
+    /// Draw thin lines between display pixels, at scale 4 and up
+    #[cfg(any(feature = "sdl", feature = "wgpu", feature = "minifb"))]
+    #[arg(long)]
+    pub grid: bool,
+
+    /// Controller buttons to CHIP-8 keys as BUTTON=KEY,... (default up=2,down=8,left=4,right=6,south=5)
+    #[cfg(feature = "gamepad")]
+    #[arg(long, value_name = "BUTTON=KEY,...")]
+    pub gamepad_map: Option<GamepadMap>,
+
+    /// Keypad bindings FILE, loaded if it exists and written when keys are remapped
+    /// (F7 in the TUI, Emulation menu in the GUI)
+    #[arg(long, value_name = "FILE")]
+    pub keymap: Option<PathBuf>,
+
+    /// Keyboard layout, for frontends that read keys by the character they type
+    #[cfg(any(feature = "minifb", feature = "terminal"))]
+    #[arg(long, value_enum, default_value_t = LayoutArg::Qwerty)]
+    pub layout: LayoutArg,
+
+    /// Start the wgpu window with CRT effects on (F9 toggles them)
+    #[cfg(feature = "wgpu")]
+    #[arg(long)]
+    pub crt: bool,
+
+    /// CRT scanline strength, 0 to 1
+    #[cfg(feature = "wgpu")]
+    #[arg(long, value_name = "STRENGTH", default_value_t = CrtEffects::default().scanlines, value_parser = parse_strength)]
+    pub scanlines: f32,
+
+    /// CRT screen curvature, 0 to 1
+    #[cfg(feature = "wgpu")]
+    #[arg(long, value_name = "STRENGTH", default_value_t = CrtEffects::default().curvature, value_parser = parse_strength)]
+    pub curvature: f32,
+
+    /// CRT corner darkening, 0 to 1
+    #[cfg(feature = "wgpu")]
+    #[arg(long, value_name = "STRENGTH", default_value_t = CrtEffects::default().vignette, value_parser = parse_strength)]
+    pub vignette: f32,
+
+    /// Display colors
+    #[cfg(any(feature = "sdl", feature = "wgpu", feature = "minifb", feature = "terminal"))]
+    #[arg(long, value_enum, default_value_t = PaletteArg::Gray)]
+    pub palette: PaletteArg,
+
+    /// Lit pixel color as RRGGBB, overriding --palette
+    #[cfg(any(feature = "sdl", feature = "wgpu", feature = "minifb", feature = "terminal"))]
+    #[arg(long, value_name = "RRGGBB")]
+    pub fg: Option<Rgb>,
+
+    /// Unlit pixel color as RRGGBB, overriding --palette
+    #[cfg(any(feature = "sdl", feature = "wgpu", feature = "minifb", feature = "terminal"))]
+    #[arg(long, value_name = "RRGGBB")]
+    pub bg: Option<Rgb>,
+
+    /// Shorthand for --backend terminal
+    #[cfg(feature = "terminal")]
+    #[arg(long, conflicts_with = "backend")]
+    pub terminal: bool,
+
+    /// How the terminal backend draws the display; auto uses kitty graphics or Sixel where supported
+    #[cfg(feature = "terminal")]
+    #[arg(long, value_enum, default_value_t = GraphicsArg::Auto)]
+    pub terminal_graphics: GraphicsArg,
+
+    /// Shorthand for --backend tui
+    #[cfg(feature = "tui")]
+    #[arg(long, conflicts_with_all = ["terminal", "backend"])]
+    pub tui: bool,
+
+    /// Annotate each executed instruction in the tui view and highlight what it touched
+    #[cfg(feature = "tui")]
+    #[arg(long)]
+    pub teach: bool,
+
+    /// Number of frames to run in headless modes
+    #[arg(long, default_value_t = 600)]
+    pub frames: u64,
+}
+
+impl Args {
+    // --backend, or the backend a shorthand flag picks, or the default window
+    pub fn backend(&self) -> BackendArg {
+        if let Some(backend) = self.backend {
+            return backend;
+        }
+
+        #[cfg(feature = "tui")]
+        if self.tui {
+            return BackendArg::Tui;
+        }
+        #[cfg(feature = "terminal")]
+        if self.terminal {
+            return BackendArg::Terminal;
+        }
+        #[cfg(feature = "minifb")]
+        if self.minifb {
+            return BackendArg::Minifb;
+        }
+        #[cfg(feature = "wgpu")]
+        if self.gpu {
+            return BackendArg::Gpu;
+        }
+
+        BackendArg::DEFAULT
+    }
+}
+
+#[cfg(any(feature = "sdl", feature = "wgpu", feature = "minifb", feature = "terminal"))]
+impl Args {
+    // --palette with any --fg/--bg override applied
+    pub fn palette(&self) -> Palette {
+        let preset: Palette = Preset::from(self.palette).palette();
+
+        Palette {
+            background: self.bg.unwrap_or(preset.background),
+            foreground: self.fg.unwrap_or(preset.foreground),
+        }
+    }
+
+    // Layout for the keypad, or QWERTY in builds where no frontend reads characters
+    pub fn layout(&self) -> KeyLayout {
+        #[cfg(any(feature = "minifb", feature = "terminal"))]
+        let layout: KeyLayout = self.layout.into();
+        #[cfg(not(any(feature = "minifb", feature = "terminal")))]
+        let layout: KeyLayout = KeyLayout::default();
+
+        layout
+    }
+
+    #[cfg(any(feature = "sdl", feature = "wgpu", feature = "minifb"))]
+    pub fn window_config(&self) -> WindowConfig {
+        #[cfg(feature = "wgpu")]
+        let crt: CrtEffects = CrtEffects {
+            enabled: self.crt,
+            scanlines: self.scanlines,
+            curvature: self.curvature,
+            vignette: self.vignette,
+        };
+        #[cfg(not(feature = "wgpu"))]
+        let crt: CrtEffects = CrtEffects::default();
+
+        WindowConfig {
+            scale: self.scale,
+            palette: self.palette(),
+            phosphor_frames: self.phosphor,
+            scaling: self.scaling.into(),
+            grid: self.grid,
+            crt,
+            layout: self.layout(),
+            keymap: None,
+        }
+    }
+}
+
+// Options that shape the emulated machine, shared by every mode that runs a ROM
+#[derive(clap::Args)]
+pub struct MachineArgs {
+    /// Seed for the CXNN random number generator (random if omitted)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// DXYN clips rows past the bottom edge and sets VF to the number of collided or clipped rows instead of 0/1
+    #[arg(long)]
+    pub collision_row_count: bool,
+
+    /// Show each draw as soon as it runs instead of latching the frame at 60Hz
+    #[arg(long)]
+    pub immediate_display: bool,
+}
+
+impl MachineArgs {
+    pub fn quirks(&self) -> Quirks {
+        Quirks {
+            collision_row_count: self.collision_row_count,
+            immediate_display: self.immediate_display,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Write a synthetic stress-test ROM
+    GenStress {
+        /// Kind of stress program to generate
+        kind: StressArg,
+
+        /// Seed for the random opcode soup
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Output ROM file
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Print the instruction set reference
+    Opcodes,
+
+    /// Print what this build supports (profiles, quirks, memory, features) as JSON
+    Capabilities,
+
+    /// Run the bundled opcode and quirk test ROMs under every machine profile
+    /// and print a pass/fail scorecard
+    Conformance,
+
+    /// Run a ROM headlessly and print the resulting machine state
+    DumpState {
+        /// Path to the ROM to run
+        rom: PathBuf,
+
+        #[command(flatten)]
+        machine: MachineArgs,
+
+        /// Number of frames to run before dumping
+        #[arg(long, default_value_t = 0)]
+        frames: u64,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = FormatArg::Text)]
+        format: FormatArg,
+    },
+
+    /// Run every ROM in a directory headlessly and summarise how each ended
+    CompatReport {
+        /// Directory containing the ROMs
+        dir: PathBuf,
+
+        #[command(flatten)]
+        machine: MachineArgs,
+
+        /// Number of frames to run each ROM for
+        #[arg(long, default_value_t = 600)]
+        frames: u64,
+    },
+
+    /// Run many ROMs headlessly in parallel and report outcome, statistics and coverage for each
+    Batch {
+        /// ROM files, or directories whose files are all run
+        #[arg(required = true)]
+        roms: Vec<PathBuf>,
+
+        #[command(flatten)]
+        machine: MachineArgs,
+
+        /// Number of frames to run each ROM for
+        #[arg(long, default_value_t = 600)]
+        frames: u64,
+
+        /// Worker threads (one per CPU if omitted)
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: Option<u16>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum BuzzerArg {
+    /// Invert the outermost pixels of the display
+    Border,
+    /// Speaker icon in the top-right corner
+    Corner,
+}
+
+impl From<BuzzerArg> for BuzzerStyle {
+    fn from(arg: BuzzerArg) -> Self {
+        match arg {
+            BuzzerArg::Border => BuzzerStyle::Border,
+            BuzzerArg::Corner => BuzzerStyle::Corner,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum FormatArg {
+    Json,
+    Text,
+}
+
+impl From<FormatArg> for DumpFormat {
+    fn from(arg: FormatArg) -> Self {
+        match arg {
+            FormatArg::Json => DumpFormat::Json,
+            FormatArg::Text => DumpFormat::Text,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum StressArg {
+    /// Back-to-back full-height sprite draws
+    Draws,
+    /// Call chain filling the whole stack
+    Calls,
+    /// Continuous FX55/FX65 traffic
+    Memory,
+    /// Random valid opcodes
+    Soup,
+}
+
+impl From<StressArg> for StressKind {
+    fn from(arg: StressArg) -> Self {
+        match arg {
+            StressArg::Draws => StressKind::Draws,
+            StressArg::Calls => StressKind::Calls,
+            StressArg::Memory => StressKind::Memory,
+            StressArg::Soup => StressKind::Soup,
+        }
+    }
+}
+
+#[cfg(any(feature = "sdl", feature = "wgpu", feature = "minifb", feature = "terminal"))]
+#[derive(Clone, Copy, ValueEnum)]
+pub enum PaletteArg {
+    /// Light gray on near-black
+    Gray,
+    /// Green phosphor monitor
+    Green,
+    /// Amber phosphor monitor
+    Amber,
+    /// Dark pixels on a greenish LCD
+    Lcd,
+    /// Octo's yellow on brown
+    Octo,
+}
+
+#[cfg(any(feature = "sdl", feature = "wgpu", feature = "minifb", feature = "terminal"))]
+impl From<PaletteArg> for Preset {
+    fn from(arg: PaletteArg) -> Self {
+        match arg {
+            PaletteArg::Gray => Preset::Gray,
+            PaletteArg::Green => Preset::Green,
+            PaletteArg::Amber => Preset::Amber,
+            PaletteArg::Lcd => Preset::Lcd,
+            PaletteArg::Octo => Preset::Octo,
+        }
+    }
+}
+
+#[cfg(any(feature = "minifb", feature = "terminal"))]
+#[derive(Clone, Copy, ValueEnum)]
+pub enum LayoutArg {
+    /// US and most English-speaking countries
+    Qwerty,
+    /// German and central European
+    Qwertz,
+    /// French and Belgian
+    Azerty,
+    Dvorak,
+    Colemak,
+}
+
+#[cfg(any(feature = "minifb", feature = "terminal"))]
+impl From<LayoutArg> for KeyLayout {
+    fn from(arg: LayoutArg) -> Self {
+        match arg {
+            LayoutArg::Qwerty => KeyLayout::Qwerty,
+            LayoutArg::Qwertz => KeyLayout::Qwertz,
+            LayoutArg::Azerty => KeyLayout::Azerty,
+            LayoutArg::Dvorak => KeyLayout::Dvorak,
+            LayoutArg::Colemak => KeyLayout::Colemak,
+        }
+    }
+}
+
+#[cfg(any(feature = "sdl", feature = "wgpu", feature = "minifb"))]
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ScalingArg {
+    /// Largest whole-number scale that fits, letterboxed
+    Integer,
+    /// Largest size that keeps the 2:1 shape
+    Aspect,
+    /// Fill the whole window
+    Stretch,
+    /// Always --scale, whatever the window size
+    Fixed,
+}
+
+#[cfg(any(feature = "sdl", feature = "wgpu", feature = "minifb"))]
+impl From<ScalingArg> for ScaleMode {
+    fn from(arg: ScalingArg) -> Self {
+        match arg {
+            ScalingArg::Integer => ScaleMode::Integer,
+            ScalingArg::Aspect => ScaleMode::Aspect,
+            ScalingArg::Stretch => ScaleMode::Stretch,
+            ScalingArg::Fixed => ScaleMode::Fixed,
+        }
+    }
+}
+
+// Only the backends compiled into this build are offered
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum BackendArg {
+    /// SDL2 window
+    #[cfg(feature = "sdl")]
+    Sdl,
+    /// wgpu window, upscaled in a shader, with optional CRT effects
+    #[cfg(feature = "wgpu")]
+    Gpu,
+    /// wgpu window with egui menus for opening ROMs, pausing, speed, quirks and palette
+    #[cfg(feature = "gui")]
+    Gui,
+    /// minifb window
+    #[cfg(feature = "minifb")]
+    Minifb,
+    /// This terminal, drawn with blocks, Braille, Sixel or kitty graphics
+    #[cfg(feature = "terminal")]
+    Terminal,
+    /// This terminal, with register, timer and stack panes beside the display
+    #[cfg(feature = "tui")]
+    Tui,
+    /// Nothing drawn and no keys read, still paced in real time (pair with --run-for)
+    Headless,
+}
+
+impl BackendArg {
+    // SDL, then wgpu, then minifb: the first window compiled in
+    #[cfg(feature = "sdl")]
+    pub const DEFAULT: BackendArg = BackendArg::Sdl;
+    #[cfg(all(feature = "wgpu", not(feature = "sdl")))]
+    pub const DEFAULT: BackendArg = BackendArg::Gpu;
+    #[cfg(all(feature = "minifb", not(any(feature = "sdl", feature = "wgpu"))))]
+    pub const DEFAULT: BackendArg = BackendArg::Minifb;
+    #[cfg(not(any(feature = "sdl", feature = "wgpu", feature = "minifb")))]
+    pub const DEFAULT: BackendArg = BackendArg::Headless;
+}
+
+#[cfg(feature = "terminal")]
+#[derive(Clone, Copy, ValueEnum)]
+pub enum GraphicsArg {
+    /// Kitty graphics or Sixel if the terminal reports support, block characters otherwise
+    Auto,
+    /// Half-block characters
+    Blocks,
+    /// Braille patterns, 2x4 pixels per character (32x8 characters)
+    Braille,
+    /// Sixel images
+    Sixel,
+    /// Kitty graphics protocol
+    Kitty,
+}
+
+#[cfg(feature = "terminal")]
+impl From<GraphicsArg> for Graphics {
+    fn from(arg: GraphicsArg) -> Self {
+        match arg {
+            GraphicsArg::Auto => Graphics::Auto,
+            GraphicsArg::Blocks => Graphics::Blocks,
+            GraphicsArg::Braille => Graphics::Braille,
+            GraphicsArg::Sixel => Graphics::Sixel,
+            GraphicsArg::Kitty => Graphics::Kitty,
+        }
+    }
+}
+
+// Parse the command line. With --session, the session's ROM and options
+// come first and any option also given on the command line replaces the
+// session's. Also returns the resulting setup, for --save-session; its ROM
+// path is left to the caller, which knows whether it loaded, but the saved
+// ROM hash is kept while the ROM is the session's.
+pub fn parse_args() -> (Args, Session) {
+    let argv: Vec<OsString> = std::env::args_os().collect();
+    let matches: ArgMatches = Args::command().get_matches_from(&argv);
+    let mut rom_hash: Option<u64> = None;
+
+    let matches: ArgMatches = match matches.get_one::<PathBuf>("session") {
+        Some(path) if matches.subcommand().is_none() => {
+            let session: Session = Session::load(path).unwrap_or_else(|err| {
+                eprintln!("Failed to load session {}: {}", path.display(), err);
+                process::exit(1);
+            });
+            if matches.value_source("rom") != Some(ValueSource::CommandLine) {
+                rom_hash = session.rom_hash;
+            }
+            Args::command().get_matches_from(merge_session(&argv, &matches, &session))
+        }
+        _ => matches,
+    };
+
+    let args: Args = Args::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    (args, Session { rom_hash, ..session_of(&matches) })
+}
+
+// The command line with the session's ROM placed first, where it can't be
+// taken for an option's value, and its options after everything else
+fn merge_session(argv: &[OsString], matches: &ArgMatches, session: &Session) -> Vec<OsString> {
+    let given = |long: &str| {
+        Args::command()
+            .get_arguments()
+            .find(|arg| arg.get_long() == Some(long))
+            .is_some_and(|arg| matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine))
+    };
+
+    let mut merged: Vec<OsString> = argv[..1].to_vec();
+    if let Some(rom) = &session.rom
+        && matches.value_source("rom") != Some(ValueSource::CommandLine)
+    {
+        merged.push(rom.into());
+    }
+    merged.extend(argv[1..].iter().cloned());
+
+    let options = session.options.iter().zip(session.to_args());
+    merged.extend(options.filter(|((key, _), _)| key != "session" && !given(key)).map(|(_, arg)| arg.into()));
+
+    merged
+}
+
+// Options set on the (merged) command line, as a session without a ROM
+fn session_of(matches: &ArgMatches) -> Session {
+    let mut session: Session = Session::default();
+
+    for arg in Args::command().get_arguments() {
+        let id: &str = arg.get_id().as_str();
+        let Some(long) = arg.get_long() else {
+            continue;
+        };
+        if id == "session" || id == "save_session" || matches.value_source(id) != Some(ValueSource::CommandLine) {
+            continue;
+        }
+
+        let values: Vec<String> = if arg.get_action().takes_values() {
+            matches.get_raw(id).into_iter().flatten().map(|value| value.to_string_lossy().into_owned()).collect()
+        } else {
+            Vec::new()
+        };
+
+        if values.is_empty() {
+            session.options.push((long.to_string(), None));
+        }
+        session.options.extend(values.into_iter().map(|value| (long.to_string(), Some(value))));
+    }
+
+    session
+}
+
+// Parse "E00:100" (both hex) into an SRAM region
+fn parse_sram_region(text: &str) -> Result<SramRegion, String> {
+    let (start, len) = text.split_once(':').ok_or("expected ADDR:LEN")?;
+    let start: u16 = u16::from_str_radix(start.trim_start_matches("0x"), 16).map_err(|err| err.to_string())?;
+    let len: u16 = u16::from_str_radix(len.trim_start_matches("0x"), 16).map_err(|err| err.to_string())?;
+
+    SramRegion::new(start, len).ok_or_else(|| "region must be non-empty and lie within 0x200..0x1000".to_string())
+}
+
+// Parse a positive number of seconds that fits in a Duration
+fn parse_seconds(text: &str) -> Result<f64, String> {
+    let seconds: f64 = text.parse().map_err(|err: std::num::ParseFloatError| err.to_string())?;
+
+    match Duration::try_from_secs_f64(seconds) {
+        Ok(duration) if !duration.is_zero() => Ok(seconds),
+        _ => Err("expected a positive number of seconds".to_string()),
+    }
+}
+
+// Parse an effect strength between 0 and 1
+#[cfg(feature = "wgpu")]
+fn parse_strength(text: &str) -> Result<f32, String> {
+    let strength: f32 = text.parse().map_err(|err: std::num::ParseFloatError| err.to_string())?;
+
+    if (0.0..=1.0).contains(&strength) {
+        Ok(strength)
+    } else {
+        Err("expected a strength between 0 and 1".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seconds_must_be_positive_and_fit_a_duration() {
+        assert_eq!(parse_seconds("2.5"), Ok(2.5));
+        for text in ["0", "-1", "nan", "inf", "1e30", "soon"] {
+            assert!(parse_seconds(text).is_err(), "{} was accepted", text);
+        }
+    }
+
+    #[test]
+    fn frame_hash_file_needs_an_equals_sign() {
+        let args: Args = Args::parse_from(["chip8", "--emit-frame-hashes", "game.ch8"]);
+        assert_eq!(args.emit_frame_hashes, Some(None));
+        assert_eq!(args.rom, Some(PathBuf::from("game.ch8")));
+
+        let args: Args = Args::parse_from(["chip8", "--emit-frame-hashes=hashes.txt", "game.ch8"]);
+        assert_eq!(args.emit_frame_hashes, Some(Some(PathBuf::from("hashes.txt"))));
+    }
+}
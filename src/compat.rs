@@ -0,0 +1,199 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Compatibility Report
+// Runs ROMs headlessly for a fixed number of frames and records
+// how each one ended (crash, halt, key wait) along with invalid
+// opcode counts, execution statistics, ROM coverage and the final
+// display hash, so a ROM collection can be compared before and
+// after a change.
+// ───────────────────────────────────────────────────────────────
+
+use std::any::Any;
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+
+use crate::chip8::constants::PROGRAM_START;
+use crate::chip8::cpu::Chip8;
+use crate::chip8::hooks::HookAction;
+use crate::chip8::instruction::Instruction;
+use crate::chip8::quirks::Quirks;
+use crate::chip8::stats::Stats;
+
+// How a ROM's run ended
+pub enum Outcome {
+    // Still executing after the requested frames
+    Running,
+
+    // Spinning on a jump to itself
+    Halted,
+
+    // Blocked in FX0A
+    WaitingForKey,
+
+    // The interpreter panicked (stack overflow, memory out of range, ...)
+    Crashed { frame: u64, message: String },
+}
+
+impl Outcome {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Outcome::Running => "running",
+            Outcome::Halted => "halted",
+            Outcome::WaitingForKey => "key-wait",
+            Outcome::Crashed { .. } => "CRASHED",
+        }
+    }
+}
+
+pub struct CompatResult {
+    pub name: String,
+    pub outcome: Outcome,
+    pub frames_run: u64,
+    pub invalid_opcodes: u64,
+
+    // Display hash after the last completed frame, None if it crashed
+    pub display_hash: Option<u64>,
+
+    // Counters for the run, up to the crash if there was one
+    pub stats: Stats,
+
+    // ROM bytes executed as part of an instruction, out of `rom_len`
+    pub bytes_executed: usize,
+    pub rom_len: usize,
+}
+
+// Run `rom` for `frames` frames, catching interpreter panics.
+// Invalid opcodes and 0NNN machine calls are counted but skipped silently
+// instead of logged.
+pub fn run_rom(name: &str, rom: &[u8], seed: u64, quirks: Quirks, frames: u64) -> CompatResult {
+    let mut chip8: Chip8 = Chip8::with_seed(seed);
+    chip8.quirks = quirks;
+
+    let executed: Arc<Mutex<Vec<bool>>> = Arc::new(Mutex::new(vec![false; rom.len()]));
+    let marks: Arc<Mutex<Vec<bool>>> = Arc::clone(&executed);
+
+    chip8.on_opcode(0, 0, move |chip8, instruction| {
+        // Instructions below the program (in the font or interpreter area)
+        // mark nothing
+        let offset: usize = chip8.instruction_address().checked_sub(PROGRAM_START).map_or(usize::MAX, usize::from);
+        if let Ok(mut marks) = marks.lock() {
+            for mark in marks.iter_mut().skip(offset).take(2) {
+                *mark = true;
+            }
+        }
+
+        match instruction {
+            Instruction::Invalid { .. } | Instruction::Sys { .. } => HookAction::Skip,
+            _ => HookAction::Continue,
+        }
+    });
+
+    let mut frames_run: u64 = 0;
+
+    // A ROM that does not fit is reported like a crash before the first frame
+    let result: Result<(), String> = match chip8.load_rom(rom) {
+        Ok(()) => panic::catch_unwind(AssertUnwindSafe(|| {
+            while frames_run < frames {
+                chip8.run_frame();
+                frames_run += 1;
+            }
+        }))
+        .map_err(panic_message),
+        Err(err) => Err(err.to_string()),
+    };
+
+    let invalid_opcodes: u64 = [Instruction::Invalid { opcode: 0 }, Instruction::Sys { nnn: 0 }]
+        .iter()
+        .filter_map(|instruction| chip8.stats.opcode_counts.get(instruction.spec().pattern))
+        .sum();
+
+    let (outcome, display_hash) = match result {
+        Ok(()) => {
+            let outcome: Outcome = if chip8.waiting_for_key().is_some() {
+                Outcome::WaitingForKey
+            } else if chip8.is_idle() {
+                Outcome::Halted
+            } else {
+                Outcome::Running
+            };
+            (outcome, Some(chip8.display_hash()))
+        }
+        Err(message) => (Outcome::Crashed { frame: frames_run, message }, None),
+    };
+
+    let bytes_executed: usize = executed.lock().map_or(0, |marks| marks.iter().filter(|&&mark| mark).count());
+
+    CompatResult {
+        name: name.to_string(),
+        outcome,
+        frames_run,
+        invalid_opcodes,
+        display_hash,
+        stats: std::mem::take(&mut chip8.stats),
+        bytes_executed,
+        rom_len: rom.len(),
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+// ===============================================================
+// Summary table
+// ===============================================================
+
+// A column between FRAMES and INVALID: header, width and the cell for one ROM
+pub type Column = (&'static str, usize, fn(&CompatResult) -> String);
+
+pub fn write_report<W: Write>(results: &[CompatResult], output: &mut W) -> io::Result<()> {
+    write_rows(results, &[], output)?;
+    writeln!(output)?;
+    writeln!(output, "{}", summary(results))
+}
+
+// The header and one row per ROM, with `extra` columns after FRAMES
+pub fn write_rows<W: Write>(results: &[CompatResult], extra: &[Column], output: &mut W) -> io::Result<()> {
+    let name_width: usize = results.iter().map(|result| result.name.len()).max().unwrap_or(0).max(4);
+
+    let mut header: String = format!("{:<name_width$}  {:<9}{:>8}", "ROM", "OUTCOME", "FRAMES");
+    for &(title, width, _) in extra {
+        header.push_str(&format!("{:>width$}", title));
+    }
+    writeln!(output, "{}{:>9}  {:<18}NOTES", header, "INVALID", "DISPLAY")?;
+
+    for result in results {
+        let display: String = match result.display_hash {
+            Some(hash) => format!("{:016x}", hash),
+            None => "-".to_string(),
+        };
+        let notes: String = match &result.outcome {
+            Outcome::Crashed { frame, message } => format!("frame {}: {}", frame, message),
+            _ => String::new(),
+        };
+
+        let mut line: String =
+            format!("{:<name_width$}  {:<9}{:>8}", result.name, result.outcome.label(), result.frames_run);
+        for &(_, width, cell) in extra {
+            line.push_str(&format!("{:>width$}", cell(result)));
+        }
+        line.push_str(&format!("{:>9}  {:<18}{}", result.invalid_opcodes, display, notes));
+        writeln!(output, "{}", line.trim_end())?;
+    }
+
+    Ok(())
+}
+
+// "N ROMs, N crashed, N hit invalid opcodes"
+pub fn summary(results: &[CompatResult]) -> String {
+    let crashed: usize = results.iter().filter(|result| matches!(result.outcome, Outcome::Crashed { .. })).count();
+    let with_invalid: usize = results.iter().filter(|result| result.invalid_opcodes > 0).count();
+
+    format!("{} ROMs, {} crashed, {} hit invalid opcodes", results.len(), crashed, with_invalid)
+}
@@ -0,0 +1,223 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Conformance Scorecard
+// Bundled micro ROMs, one per opcode behaviour or quirk, run under
+// every machine profile. Each profile is scored against the
+// platform it stands for (COSMAC VIP or SUPER-CHIP), so the card
+// shows exactly which behaviours a profile gets right.
+// ───────────────────────────────────────────────────────────────
+
+use std::io::{self, Write};
+
+use crate::chip8::constants::{FONT_SET, FONT_START, PROGRAM_START};
+use crate::chip8::cpu::Chip8;
+use crate::chip8::quirks::Quirks;
+
+// Cycles a check may take before it counts as hung
+const MAX_CYCLES: u64 = 1000;
+
+// Platform a profile is expected to behave like
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    Vip,
+    Schip,
+}
+
+pub struct Profile {
+    pub name: &'static str,
+    pub platform: Platform,
+    pub quirks: Quirks,
+}
+
+pub const PROFILES: [Profile; 2] = [
+    Profile {
+        name: "VIP",
+        platform: Platform::Vip,
+        quirks: Quirks { collision_row_count: false, immediate_display: false },
+    },
+    Profile {
+        name: "SCHIP",
+        platform: Platform::Schip,
+        quirks: Quirks { collision_row_count: true, immediate_display: true },
+    },
+];
+
+// A program (a halt is appended) and the test of the machine it leaves behind
+struct Check {
+    behavior: &'static str,
+    program: &'static [u16],
+    passes: fn(&Chip8, Platform) -> bool,
+}
+
+// One row per behaviour, one pass/fail per profile in PROFILES order
+pub struct Scorecard {
+    pub rows: Vec<(&'static str, Vec<bool>)>,
+}
+
+pub fn run() -> Scorecard {
+    let rows: Vec<(&'static str, Vec<bool>)> = CHECKS
+        .iter()
+        .map(|check| (check.behavior, PROFILES.iter().map(|profile| run_check(check, profile)).collect()))
+        .collect();
+
+    Scorecard { rows }
+}
+
+fn run_check(check: &Check, profile: &Profile) -> bool {
+    let halt: u16 = 0x1000 | (PROGRAM_START + check.program.len() as u16 * 2);
+    let rom: Vec<u8> = check.program.iter().chain([&halt]).flat_map(|opcode| opcode.to_be_bytes()).collect();
+
+    let mut chip8: Chip8 = Chip8::with_seed(0);
+    chip8.quirks = profile.quirks;
+    if chip8.load_rom(&rom).is_err() {
+        return false;
+    }
+
+    for _ in 0..MAX_CYCLES {
+        if chip8.is_halted() {
+            return (check.passes)(&chip8, profile.platform);
+        }
+        chip8.cycle();
+    }
+
+    false
+}
+
+pub fn write_scorecard<W: Write>(scorecard: &Scorecard, output: &mut W) -> io::Result<()> {
+    let width: usize = scorecard.rows.iter().map(|(behavior, _)| behavior.len()).max().unwrap_or(0).max(8);
+    let line = |label: &str, cells: Vec<String>| {
+        let line: String =
+            cells.iter().fold(format!("{:<width$}", label), |line, cell| line + &format!("  {:<7}", cell));
+        line.trim_end().to_string()
+    };
+
+    writeln!(output, "{}", line("BEHAVIOR", PROFILES.iter().map(|profile| profile.name.to_string()).collect()))?;
+
+    for (behavior, results) in scorecard.rows.iter() {
+        let cells: Vec<String> =
+            results.iter().map(|&passed| if passed { "pass" } else { "FAIL" }.to_string()).collect();
+        writeln!(output, "{}", line(behavior, cells))?;
+    }
+
+    let scores: Vec<String> = (0..PROFILES.len())
+        .map(|index| {
+            let passed: usize = scorecard.rows.iter().filter(|(_, results)| results[index]).count();
+            format!("{}/{}", passed, scorecard.rows.len())
+        })
+        .collect();
+    writeln!(output)?;
+    writeln!(output, "{}", line("SCORE", scores))
+}
+
+// ===============================================================
+// Checks
+// ===============================================================
+
+// Programs start at 0x200; jump targets below are worked out by hand
+const CHECKS: &[Check] = &[
+    // Opcodes every platform agrees on
+    Check {
+        behavior: "7XNN adds without touching VF",
+        program: &[0x60FF, 0x6F05, 0x7002],
+        passes: |chip8, _| chip8.v[0] == 0x01 && chip8.v[0xF] == 0x05,
+    },
+    Check {
+        behavior: "8XY4 sets VF on carry",
+        program: &[0x60FF, 0x6102, 0x8014],
+        passes: |chip8, _| chip8.v[0] == 0x01 && chip8.v[0xF] == 1,
+    },
+    Check {
+        behavior: "8XY5 clears VF on borrow",
+        program: &[0x6001, 0x6102, 0x8015],
+        passes: |chip8, _| chip8.v[0] == 0xFF && chip8.v[0xF] == 0,
+    },
+    Check {
+        behavior: "8XY7 subtracts VX from VY",
+        program: &[0x6003, 0x6105, 0x8017],
+        passes: |chip8, _| chip8.v[0] == 0x02 && chip8.v[0xF] == 1,
+    },
+    Check {
+        behavior: "8XY4 flag wins when VX is VF",
+        program: &[0x6FFF, 0x6101, 0x8F14],
+        passes: |chip8, _| chip8.v[0xF] == 1,
+    },
+    Check {
+        behavior: "2NNN/00EE call and return",
+        // 0x206: V0 = 2, return; the caller sets V1 = 1 and jumps to the halt
+        program: &[0x2206, 0x6101, 0x120A, 0x6002, 0x00EE],
+        passes: |chip8, _| chip8.v[0] == 2 && chip8.v[1] == 1 && chip8.sp == 0,
+    },
+    Check {
+        behavior: "FX33 stores BCD",
+        program: &[0x60EA, 0xA300, 0xF033],
+        passes: |chip8, _| chip8.memory[0x300..0x303] == [2, 3, 4],
+    },
+    Check {
+        behavior: "FX55/FX65 round trip",
+        program: &[0x6011, 0x6122, 0x6233, 0xA300, 0xF255, 0x6000, 0x6100, 0x6200, 0xA300, 0xF265],
+        passes: |chip8, _| chip8.v[0..3] == [0x11, 0x22, 0x33],
+    },
+    Check {
+        behavior: "FX1E adds VX to I",
+        program: &[0xA300, 0x6010, 0xF01E],
+        passes: |chip8, _| chip8.i == 0x310,
+    },
+    Check {
+        behavior: "FX29 points I at the digit glyph",
+        program: &[0x600A, 0xF029],
+        passes: |chip8, _| {
+            let i: usize = chip8.i as usize;
+            chip8.memory[i..i + 5] == FONT_SET[50..55]
+        },
+    },
+    Check {
+        behavior: "DXYN XORs and flags collisions",
+        program: &[0xA000 | FONT_START, 0x6000, 0x6100, 0xD015, 0xD015],
+        passes: |chip8, _| chip8.v[0xF] != 0 && chip8.display.iter().flatten().all(|&lit| !lit),
+    },
+    Check {
+        behavior: "DXYN wraps the starting position",
+        program: &[0xA000 | FONT_START, 0x6042, 0x6100, 0xD015],
+        passes: |chip8, _| chip8.display[0][2],
+    },
+
+    // Quirks, where the platforms part ways
+    Check {
+        behavior: "8XY1 resets VF (VIP only)",
+        program: &[0x6F05, 0x8011],
+        passes: |chip8, platform| chip8.v[0xF] == if platform == Platform::Vip { 0 } else { 5 },
+    },
+    Check {
+        behavior: "8XY6 shifts VY (VIP) or VX (SCHIP)",
+        program: &[0x6008, 0x6104, 0x8016],
+        passes: |chip8, platform| chip8.v[0] == if platform == Platform::Vip { 0x02 } else { 0x04 },
+    },
+    Check {
+        behavior: "8XYE shifts VY (VIP) or VX (SCHIP)",
+        program: &[0x6003, 0x6181, 0x801E],
+        passes: |chip8, platform| match platform {
+            Platform::Vip => chip8.v[0] == 0x02 && chip8.v[0xF] == 1,
+            Platform::Schip => chip8.v[0] == 0x06 && chip8.v[0xF] == 0,
+        },
+    },
+    Check {
+        behavior: "FX55 advances I (VIP only)",
+        program: &[0xA300, 0xF255],
+        passes: |chip8, platform| chip8.i == if platform == Platform::Vip { 0x303 } else { 0x300 },
+    },
+    Check {
+        behavior: "BNNN adds V0 (VIP) or VX (SCHIP)",
+        // 0x20A: V3 = 1 (V0 + 0x206), 0x20E: V3 = 2 (V2 + 0x206); all paths end at the halt
+        program: &[0x6004, 0x6208, 0xB206, 0x1212, 0x1212, 0x6301, 0x1212, 0x6302, 0x1212],
+        passes: |chip8, platform| chip8.v[3] == if platform == Platform::Vip { 1 } else { 2 },
+    },
+    Check {
+        behavior: "DXYN clips at the right edge",
+        program: &[0xA000 | FONT_START, 0x603E, 0x6100, 0xD015],
+        passes: |chip8, _| chip8.display[0][62] && !chip8.display[0][0],
+    },
+    Check {
+        behavior: "DXYN VF is 0/1 (VIP) or a row count (SCHIP)",
+        program: &[0xA000 | FONT_START, 0x6000, 0x6100, 0xD015, 0xD015],
+        passes: |chip8, platform| chip8.v[0xF] == if platform == Platform::Vip { 1 } else { 5 },
+    },
+];
@@ -0,0 +1,142 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Divergence Reports
+// Shows where two runs of the same ROM parted ways: an image with
+// both displays side by side and an overlay that marks every pixel
+// they disagree on, plus the registers and the code around each
+// program counter, so the cause is visible at a glance.
+// ───────────────────────────────────────────────────────────────
+
+use std::io::{self, Write};
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH};
+use crate::chip8::cpu::Chip8;
+use crate::chip8::display::DisplayBuffer;
+use crate::screenshot::Image;
+
+// Overlay colors: lit in both runs, only in the first, only in the second
+const BOTH: [u8; 3] = [0x60, 0x60, 0x60];
+const FIRST_ONLY: [u8; 3] = [0xF0, 0x40, 0x40];
+const SECOND_ONLY: [u8; 3] = [0x40, 0xC0, 0xF0];
+
+const BACKGROUND: [u8; 3] = [0x10, 0x10, 0x10];
+const FOREGROUND: [u8; 3] = [0xE0, 0xE0, 0xE0];
+
+// Fill between and around the three panels
+const GUTTER: [u8; 3] = [0x40, 0x40, 0x40];
+
+// Instructions shown either side of the program counter
+const CONTEXT: usize = 4;
+
+// ===============================================================
+// Image
+// ===============================================================
+
+// First display, second display and their overlay, left to right,
+// each CHIP-8 pixel drawn as a `scale` x `scale` square
+pub fn diff_image(first: &DisplayBuffer, second: &DisplayBuffer, scale: usize, gap: usize) -> Image {
+    let panels: [Image; 3] = [
+        panel(scale, |x, y| if first[y][x] { FOREGROUND } else { BACKGROUND }),
+        panel(scale, |x, y| if second[y][x] { FOREGROUND } else { BACKGROUND }),
+        panel(scale, |x, y| match (first[y][x], second[y][x]) {
+            (true, true) => BOTH,
+            (true, false) => FIRST_ONLY,
+            (false, true) => SECOND_ONLY,
+            (false, false) => BACKGROUND,
+        }),
+    ];
+
+    let (tile_width, tile_height) = (DISPLAY_WIDTH * scale + gap, DISPLAY_HEIGHT * scale + gap);
+    let (width, height) = (3 * tile_width + gap, tile_height + gap);
+    let mut sheet: Image = Image { width, height, channels: 3, pixels: GUTTER.repeat(width * height) };
+
+    for (index, image) in panels.iter().enumerate() {
+        sheet.blit(image, gap + index * tile_width, gap);
+    }
+
+    sheet
+}
+
+// Number of pixels lit in one display and not the other
+pub fn differing_pixels(first: &DisplayBuffer, second: &DisplayBuffer) -> usize {
+    first.iter().flatten().zip(second.iter().flatten()).filter(|(a, b)| a != b).count()
+}
+
+fn panel(scale: usize, color: impl Fn(usize, usize) -> [u8; 3]) -> Image {
+    let (width, height) = (DISPLAY_WIDTH * scale, DISPLAY_HEIGHT * scale);
+    let mut pixels: Vec<u8> = Vec::with_capacity(width * height * 3);
+
+    for y in 0..height {
+        for x in 0..width {
+            pixels.extend_from_slice(&color(x / scale, y / scale));
+        }
+    }
+
+    Image { width, height, channels: 3, pixels }
+}
+
+// ===============================================================
+// Text report
+// ===============================================================
+
+// Registers and surrounding code of both machines, with the fields that
+// differ flagged
+pub fn write_report<W: Write>(first: &Chip8, second: &Chip8, output: &mut W) -> io::Result<()> {
+    let mark = |same: bool| if same { ' ' } else { '*' };
+
+    writeln!(output, "{:<10}{:>8}{:>8}", "", "FIRST", "SECOND")?;
+    writeln!(output, "{} {:<8}{:>8X}{:>8X}", mark(first.pc == second.pc), "PC", first.pc, second.pc)?;
+    writeln!(output, "{} {:<8}{:>8X}{:>8X}", mark(first.i == second.i), "I", first.i, second.i)?;
+    for (index, (a, b)) in first.v.iter().zip(second.v.iter()).enumerate() {
+        writeln!(output, "{} {:<8}{:>8X}{:>8X}", mark(a == b), format!("V{:X}", index), a, b)?;
+    }
+    writeln!(output, "{} {:<8}{:>8}{:>8}", mark(first.sp == second.sp), "SP", first.sp, second.sp)?;
+    writeln!(
+        output,
+        "{} {:<8}{:>8}{:>8}",
+        mark(first.delay_timer == second.delay_timer),
+        "DELAY",
+        first.delay_timer,
+        second.delay_timer
+    )?;
+    writeln!(
+        output,
+        "{} {:<8}{:>8}{:>8}",
+        mark(first.sound_timer == second.sound_timer),
+        "SOUND",
+        first.sound_timer,
+        second.sound_timer
+    )?;
+
+    let memory: usize = first.memory.iter().zip(second.memory.iter()).filter(|(a, b)| a != b).count();
+    writeln!(output, "{} {} memory bytes differ", mark(memory == 0), memory)?;
+
+    let pixels: usize = differing_pixels(first.frame(), second.frame());
+    writeln!(output, "{} {} display pixels differ", mark(pixels == 0), pixels)?;
+
+    for (label, chip8) in [("First run", first), ("Second run", second)] {
+        writeln!(output)?;
+        writeln!(output, "{}:", label)?;
+        for line in disassembly_context(chip8) {
+            writeln!(output, "{}", line)?;
+        }
+    }
+
+    Ok(())
+}
+
+// The instructions around the program counter, the current one marked
+fn disassembly_context(chip8: &Chip8) -> Vec<String> {
+    let pc: usize = chip8.pc as usize;
+    let start: usize = pc.saturating_sub(2 * CONTEXT);
+
+    (start..=pc + 2 * CONTEXT)
+        .step_by(2)
+        .filter_map(|address| {
+            let bytes: &[u8] = chip8.memory.get(address..address + 2)?;
+            let opcode: u16 = u16::from_be_bytes([bytes[0], bytes[1]]);
+            let marker: &str = if address == pc { ">" } else { " " };
+
+            Some(format!("{} {:03X}  {:04X}  {}", marker, address, opcode, chip8.disassemble(opcode)))
+        })
+        .collect()
+}
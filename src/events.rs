@@ -0,0 +1,89 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Event Bus
+// Host-side notifications (ROM loaded, reset, sound, saves,
+// recordings, errors) published once and delivered to every
+// subscriber: logging, OSC output, and later the on-screen display,
+// scripting and remote control. Publishers never need to know who
+// is listening.
+// ───────────────────────────────────────────────────────────────
+
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Clone, Debug)]
+pub enum Event {
+    // A ROM was loaded into memory, at startup or by a hard reset
+    RomLoaded { path: PathBuf, bytes: usize },
+
+    // The machine restarted; a hard reset re-reads the ROM as well
+    Reset { hard: bool },
+
+    // The buzzer started or stopped
+    Sound { on: bool },
+
+    // Persistent state (`what`: SRAM, memory dump) was written to `path`
+    StateSaved { what: &'static str, path: PathBuf },
+
+    // A recording (`what`: GIF clip, input macro) began, or was written out
+    RecordingStarted { what: &'static str, path: PathBuf },
+    RecordingFinished { what: &'static str, path: PathBuf },
+
+    // A game controller was plugged in or unplugged
+    Gamepad { name: String, connected: bool },
+
+    // The CPU now runs at this many instructions per second
+    SpeedChanged(u64),
+
+    // Something looks wrong with the run (a ROM that seems stuck)
+    Warning(String),
+
+    // Something failed without ending the run
+    Error(String),
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::RomLoaded { path, bytes } => write!(f, "loaded ROM {} ({} bytes)", path.display(), bytes),
+            Event::Reset { hard: false } => write!(f, "reset"),
+            Event::Reset { hard: true } => write!(f, "hard reset"),
+            Event::Sound { on } => write!(f, "sound {}", if *on { "on" } else { "off" }),
+            Event::StateSaved { what, path } => write!(f, "saved {} to {}", what, path.display()),
+            Event::RecordingStarted { what, path } => write!(f, "recording {} to {}", what, path.display()),
+            Event::RecordingFinished { what, path } => write!(f, "wrote {} {}", what, path.display()),
+            Event::Gamepad { name, connected } => {
+                write!(f, "gamepad {} {}", name, if *connected { "connected" } else { "disconnected" })
+            }
+            Event::SpeedChanged(cpu_hz) => write!(f, "speed {} Hz", cpu_hz),
+            Event::Warning(message) | Event::Error(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+pub type Subscriber = Box<dyn FnMut(&Event) + Send>;
+
+// Subscribers are called synchronously, in the order they subscribed
+#[derive(Default)]
+pub struct EventBus {
+    subscribers: Vec<Subscriber>,
+}
+
+impl EventBus {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn subscribe<F>(&mut self, subscriber: F)
+    where
+        F: FnMut(&Event) + Send + 'static,
+    {
+        self.subscribers.push(Box::new(subscriber));
+    }
+
+    pub fn publish(&mut self, event: Event) {
+        for subscriber in self.subscribers.iter_mut() {
+            subscriber(&event);
+        }
+    }
+}
@@ -0,0 +1,609 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — wgpu Window
+// Uploads each completed frame as a 64x32 texture and scales it to
+// the window in a fragment shader, so presenting costs one small
+// texture write and a single full-screen draw. The same shader adds
+// the optional CRT effects (scanlines, curvature, vignette), which
+// F9 toggles, and the pixel grid. The draw is limited to the area the
+// scale mode picks (F8 cycles, -/= zoom), letterboxed; F11 toggles
+// fullscreen.
+// ───────────────────────────────────────────────────────────────
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use winit::application::ApplicationHandler;
+use winit::dpi::{LogicalSize, PhysicalSize};
+use winit::event::{ElementState, KeyEvent, WindowEvent};
+use winit::event_loop::{ActiveEventLoop, EventLoop};
+use winit::keyboard::{KeyCode, ModifiersState, PhysicalKey};
+use winit::platform::pump_events::EventLoopExtPumpEvents;
+use winit::window::{Fullscreen, Window, WindowId};
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH};
+use crate::chip8::cpu::Chip8;
+use crate::chip8::display::{DirtyRows, DisplayBuffer};
+use crate::frontend::phosphor::{Levels, Phosphor};
+use crate::frontend::{CrtEffects, Frontend, GRID_LEVEL, HostAction, MIN_GRID_SCALE, Scaling, Viewport, WindowConfig};
+use crate::keymap::Keymap;
+use crate::palette::{Palette, Rgb};
+
+// Full-screen triangle sampling the display texture texel by texel. The
+// palette is prepended as WGSL constants by `shader_source`.
+const SHADER: &str = r#"
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+// CRT strengths, all zero while the CRT effects are off, and 1.0 when
+// the pixel grid is on
+struct Effects {
+    scanlines: f32,
+    curvature: f32,
+    vignette: f32,
+    grid: f32,
+}
+
+@group(0) @binding(0) var screen: texture_2d<f32>;
+@group(0) @binding(1) var<uniform> effects: Effects;
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Barrel distortion around the centre; outside the bent screen is black
+    let centred = in.uv * 2.0 - vec2<f32>(1.0);
+    let uv = centred * (1.0 + effects.curvature * dot(centred.yx, centred.yx)) * 0.5 + vec2<f32>(0.5);
+
+    let size = textureDimensions(screen);
+    let position = uv * vec2<f32>(size);
+
+    // One screen pixel in display pixels; derivatives need uniform control flow
+    let screen_pixel = fwidth(position);
+
+    if any(uv < vec2<f32>(0.0)) || any(uv >= vec2<f32>(1.0)) {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+
+    let texel = min(vec2<u32>(position), size - vec2<u32>(1u));
+    let brightness = textureLoad(screen, texel, 0).r;
+
+    var color = mix(BACKGROUND, FOREGROUND, brightness);
+
+    // Grid lines on the inner pixel boundaries, one screen pixel wide
+    let on_line = fract(position) < screen_pixel & position >= vec2<f32>(1.0);
+    if effects.grid > 0.0 && all(screen_pixel <= vec2<f32>(1.0 / MIN_GRID_SCALE)) && any(on_line) {
+        color = GRID;
+    }
+
+    color *= 1.0 - effects.scanlines * (1.0 - sin(fract(position.y) * 3.14159265));
+    color *= 1.0 - effects.vignette * 0.5 * dot(centred, centred);
+
+    return vec4<f32>(color, 1.0);
+}
+"#;
+
+pub struct GpuFrontend {
+    event_loop: EventLoop<()>,
+    window: WindowState,
+}
+
+impl GpuFrontend {
+
+    // Open a window `config.scale` times the CHIP-8 resolution
+    pub fn new(title: &str, config: &WindowConfig) -> Result<Self, String> {
+        let mut event_loop: EventLoop<()> = EventLoop::new().map_err(|err| err.to_string())?;
+
+        let mut window: WindowState = WindowState {
+            title: title.to_string(),
+            config: *config,
+            renderer: None,
+            error: None,
+            key_changes: Vec::new(),
+            modifiers: ModifiersState::empty(),
+            action: HostAction::None,
+        };
+
+        // The window is created once the event loop reports it is resumed
+        while window.renderer.is_none() && window.error.is_none() {
+            event_loop.pump_app_events(Some(Duration::from_millis(10)), &mut window);
+        }
+        if let Some(err) = window.error.take() {
+            return Err(err);
+        }
+
+        Ok(Self { event_loop, window })
+    }
+}
+
+impl Frontend for GpuFrontend {
+    fn pump_events(&mut self, chip8: &mut Chip8) -> HostAction {
+        self.event_loop.pump_app_events(Some(Duration::ZERO), &mut self.window);
+
+        let keymap: Keymap = self.window.config.keymap.unwrap_or_default();
+        for (ch, pressed) in self.window.key_changes.drain(..) {
+            keymap.set_key(&mut chip8.keys, ch, pressed);
+        }
+
+        std::mem::replace(&mut self.window.action, HostAction::None)
+    }
+
+    fn present(&mut self, frame: &DisplayBuffer, dirty: DirtyRows) -> Result<(), String> {
+        match &mut self.window.renderer {
+            Some(renderer) => renderer.render(frame, dirty),
+            None => Ok(()),
+        }
+    }
+}
+
+// ===============================================================
+// Window events
+// ===============================================================
+
+struct WindowState {
+    title: String,
+    config: WindowConfig,
+    renderer: Option<Renderer>,
+
+    // Failure while creating the window or renderer
+    error: Option<String>,
+
+    // Characters pressed and released since the last pump, as (char, pressed)
+    key_changes: Vec<(char, bool)>,
+
+    modifiers: ModifiersState,
+    action: HostAction,
+}
+
+impl ApplicationHandler for WindowState {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.renderer.is_some() {
+            return;
+        }
+
+        let attributes = Window::default_attributes().with_title(self.title.as_str()).with_inner_size(
+            LogicalSize::new(DISPLAY_WIDTH as u32 * self.config.scale, DISPLAY_HEIGHT as u32 * self.config.scale),
+        );
+
+        let result: Result<Renderer, String> = event_loop
+            .create_window(attributes)
+            .map_err(|err| err.to_string())
+            .and_then(|window| pollster::block_on(Renderer::new(Arc::new(window), &self.config)));
+
+        match result {
+            Ok(renderer) => self.renderer = Some(renderer),
+            Err(err) => self.error = Some(err),
+        }
+    }
+
+    fn window_event(&mut self, _event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
+        match event {
+            WindowEvent::CloseRequested => self.action = HostAction::Quit,
+            WindowEvent::Resized(size) => {
+                if let Some(renderer) = &mut self.renderer {
+                    renderer.resize(size);
+                }
+            }
+            WindowEvent::ModifiersChanged(modifiers) => self.modifiers = modifiers.state(),
+            WindowEvent::KeyboardInput { event, .. } => self.handle_key(event),
+            _ => {}
+        }
+    }
+}
+
+impl WindowState {
+    fn handle_key(&mut self, event: KeyEvent) {
+        let PhysicalKey::Code(code) = event.physical_key else {
+            return;
+        };
+        let pressed: bool = event.state == ElementState::Pressed;
+
+        match code {
+            KeyCode::Escape if pressed => self.action = HostAction::Quit,
+            KeyCode::F5 if pressed && !event.repeat && self.action != HostAction::Quit => {
+                self.action = if self.modifiers.shift_key() {
+                    HostAction::HardReset
+                } else {
+                    HostAction::Reset
+                };
+            }
+            KeyCode::F6 if pressed && !event.repeat && self.action == HostAction::None => {
+                self.action = HostAction::PlayMacro;
+            }
+            KeyCode::F3 if pressed && !event.repeat && self.action == HostAction::None => {
+                self.action = HostAction::TogglePerfOverlay;
+            }
+            KeyCode::F4 if pressed && !event.repeat && self.action == HostAction::None => {
+                self.action = HostAction::ToggleKeypadOverlay;
+            }
+            KeyCode::F8 if pressed && !event.repeat => {
+                if let Some(renderer) = &mut self.renderer {
+                    renderer.scaling.cycle();
+                }
+            }
+            KeyCode::Minus | KeyCode::Equal if pressed => {
+                if let Some(renderer) = &mut self.renderer {
+                    renderer.scaling.zoom(if code == KeyCode::Minus { -1 } else { 1 });
+                }
+            }
+            KeyCode::F9 if pressed && !event.repeat => {
+                if let Some(renderer) = &mut self.renderer {
+                    renderer.toggle_crt();
+                }
+            }
+            KeyCode::F11 if pressed && !event.repeat => {
+                if let Some(renderer) = &self.renderer {
+                    renderer.toggle_fullscreen();
+                }
+            }
+            _ => {
+                if let Some(ch) = qwerty_char(code) {
+                    self.key_changes.push((ch, pressed));
+                }
+            }
+        }
+    }
+}
+
+// ===============================================================
+// Renderer
+// ===============================================================
+
+struct Renderer {
+    window: Arc<Window>,
+    surface: wgpu::Surface<'static>,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    config: wgpu::SurfaceConfiguration,
+
+    // 64x32 single-channel texture holding each pixel's brightness
+    screen: wgpu::Texture,
+    phosphor: Phosphor,
+    scaling: Scaling,
+
+    // Effect settings and the uniform buffer the shader reads them from
+    crt: CrtEffects,
+    grid: bool,
+    effects_buffer: wgpu::Buffer,
+
+    bind_group: wgpu::BindGroup,
+    pipeline: wgpu::RenderPipeline,
+}
+
+impl Renderer {
+    async fn new(window: Arc<Window>, display: &WindowConfig) -> Result<Self, String> {
+        let size: PhysicalSize<u32> = window.inner_size();
+
+        let instance: wgpu::Instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let surface: wgpu::Surface<'static> =
+            instance.create_surface(Arc::clone(&window)).map_err(|err| err.to_string())?;
+
+        let adapter: wgpu::Adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                compatible_surface: Some(&surface),
+                ..Default::default()
+            })
+            .await
+            .map_err(|err| err.to_string())?;
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor::default())
+            .await
+            .map_err(|err| err.to_string())?;
+
+        let mut config: wgpu::SurfaceConfiguration = surface
+            .get_default_config(&adapter, size.width.max(1), size.height.max(1))
+            .ok_or("surface is not supported by the adapter")?;
+
+        // The shader's colours are sRGB values, so write them without conversion
+        let capabilities: wgpu::SurfaceCapabilities = surface.get_capabilities(&adapter);
+        if let Some(&format) = capabilities.formats.iter().find(|format| !format.is_srgb()) {
+            config.format = format;
+        }
+        surface.configure(&device, &config);
+
+        let screen: wgpu::Texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("chip8 display"),
+            size: screen_extent(),
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::R8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+
+        let bind_group_layout: wgpu::BindGroupLayout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: None,
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                ],
+            });
+
+        let effects_buffer: wgpu::Buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("effects"),
+            size: EFFECTS_UNIFORM_SIZE as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        queue.write_buffer(&effects_buffer, 0, &effects_uniform(&display.crt, display.grid));
+
+        let view: wgpu::TextureView = screen.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group: wgpu::BindGroup = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&view) },
+                wgpu::BindGroupEntry { binding: 1, resource: effects_buffer.as_entire_binding() },
+            ],
+        });
+
+        let shader: wgpu::ShaderModule = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("chip8 upscale"),
+            source: wgpu::ShaderSource::Wgsl(shader_source(display.palette).into()),
+        });
+
+        let layout: wgpu::PipelineLayout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline: wgpu::RenderPipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("chip8 upscale"),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: Default::default(),
+                buffers: &[],
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: Default::default(),
+                targets: &[Some(config.format.into())],
+            }),
+            multiview: None,
+            cache: None,
+        });
+
+        Ok(Self {
+            window,
+            surface,
+            device,
+            queue,
+            config,
+            screen,
+            phosphor: Phosphor::new(display.phosphor_frames),
+            scaling: Scaling::new(display),
+            crt: display.crt,
+            grid: display.grid,
+            effects_buffer,
+            bind_group,
+            pipeline,
+        })
+    }
+
+    // Switch the CRT effects on or off; the next frame picks up the change
+    fn toggle_crt(&mut self) {
+        self.crt.enabled = !self.crt.enabled;
+        self.queue.write_buffer(&self.effects_buffer, 0, &effects_uniform(&self.crt, self.grid));
+    }
+
+    // Switch between a window and borderless fullscreen on the current monitor;
+    // the resize event that follows reconfigures the surface
+    fn toggle_fullscreen(&self) {
+        let next: Option<Fullscreen> = match self.window.fullscreen() {
+            Some(_) => None,
+            None => Some(Fullscreen::Borderless(None)),
+        };
+
+        self.window.set_fullscreen(next);
+    }
+
+    fn resize(&mut self, size: PhysicalSize<u32>) {
+        // A minimised window reports zero size, which cannot be configured
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
+
+        self.config.width = size.width;
+        self.config.height = size.height;
+        self.surface.configure(&self.device, &self.config);
+    }
+
+    fn render(&mut self, frame: &DisplayBuffer, dirty: DirtyRows) -> Result<(), String> {
+        // Brightness levels go straight into the texture; the shader blends.
+        // Only the band of rows that changed is uploaded.
+        self.phosphor.update(frame, dirty);
+        let levels: &Levels = self.phosphor.levels();
+
+        if let Some(rows) = self.phosphor.changed_rows().span() {
+            let pixels: Vec<u8> = levels[rows.clone()].iter().flatten().copied().collect();
+
+            self.queue.write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    origin: wgpu::Origin3d { x: 0, y: rows.start as u32, z: 0 },
+                    ..self.screen.as_image_copy()
+                },
+                &pixels,
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(DISPLAY_WIDTH as u32),
+                    rows_per_image: Some(rows.len() as u32),
+                },
+                wgpu::Extent3d { height: rows.len() as u32, ..screen_extent() },
+            );
+        }
+
+        let output: wgpu::SurfaceTexture = match self.surface.get_current_texture() {
+            Ok(output) => output,
+
+            // Reconfigure and skip this frame; the next one will draw
+            Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+                self.surface.configure(&self.device, &self.config);
+                return Ok(());
+            }
+            Err(err) => return Err(err.to_string()),
+        };
+        let target: wgpu::TextureView = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder: wgpu::CommandEncoder =
+            self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass: wgpu::RenderPass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &target,
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: wgpu::Operations { load: wgpu::LoadOp::Clear(wgpu::Color::BLACK), store: wgpu::StoreOp::Store },
+                })],
+                ..Default::default()
+            });
+
+            // Letterbox by drawing into the scaled area only
+            let viewport: Viewport = self.scaling.viewport(self.config.width, self.config.height);
+            pass.set_viewport(
+                viewport.x as f32,
+                viewport.y as f32,
+                viewport.width.min(self.config.width) as f32,
+                viewport.height.min(self.config.height) as f32,
+                0.0,
+                1.0,
+            );
+
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &self.bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+
+        self.queue.submit([encoder.finish()]);
+        output.present();
+
+        Ok(())
+    }
+}
+
+fn screen_extent() -> wgpu::Extent3d {
+    wgpu::Extent3d { width: DISPLAY_WIDTH as u32, height: DISPLAY_HEIGHT as u32, depth_or_array_layers: 1 }
+}
+
+// The shader's Effects struct: four f32s
+const EFFECTS_UNIFORM_SIZE: usize = 16;
+
+fn effects_uniform(crt: &CrtEffects, grid: bool) -> [u8; EFFECTS_UNIFORM_SIZE] {
+    let strengths: [f32; 4] = match crt.enabled {
+        true => [crt.scanlines, crt.curvature, crt.vignette, grid as u8 as f32],
+        false => [0.0, 0.0, 0.0, grid as u8 as f32],
+    };
+
+    let mut bytes: [u8; EFFECTS_UNIFORM_SIZE] = [0; EFFECTS_UNIFORM_SIZE];
+    for (chunk, strength) in bytes.chunks_exact_mut(4).zip(strengths) {
+        chunk.copy_from_slice(&strength.to_ne_bytes());
+    }
+    bytes
+}
+
+// SHADER with the palette colors and grid settings defined in front of it
+fn shader_source(palette: Palette) -> String {
+    let constant = |name: &str, color: Rgb| {
+        let [r, g, b] = color.to_f32();
+        format!("const {}: vec3<f32> = vec3<f32>({:?}, {:?}, {:?});\n", name, r, g, b)
+    };
+
+    constant("BACKGROUND", palette.background)
+        + &constant("FOREGROUND", palette.foreground)
+        + &constant("GRID", palette.shade(GRID_LEVEL))
+        + &format!("const MIN_GRID_SCALE: f32 = {:?};\n", MIN_GRID_SCALE as f32)
+        + SHADER
+}
+
+// Character each key types on a US keyboard, which is how keymaps name
+// key positions
+pub(crate) fn qwerty_char(code: KeyCode) -> Option<char> {
+    let ch: char = match code {
+        KeyCode::Digit0 => '0',
+        KeyCode::Digit1 => '1',
+        KeyCode::Digit2 => '2',
+        KeyCode::Digit3 => '3',
+        KeyCode::Digit4 => '4',
+        KeyCode::Digit5 => '5',
+        KeyCode::Digit6 => '6',
+        KeyCode::Digit7 => '7',
+        KeyCode::Digit8 => '8',
+        KeyCode::Digit9 => '9',
+        KeyCode::KeyA => 'a',
+        KeyCode::KeyB => 'b',
+        KeyCode::KeyC => 'c',
+        KeyCode::KeyD => 'd',
+        KeyCode::KeyE => 'e',
+        KeyCode::KeyF => 'f',
+        KeyCode::KeyG => 'g',
+        KeyCode::KeyH => 'h',
+        KeyCode::KeyI => 'i',
+        KeyCode::KeyJ => 'j',
+        KeyCode::KeyK => 'k',
+        KeyCode::KeyL => 'l',
+        KeyCode::KeyM => 'm',
+        KeyCode::KeyN => 'n',
+        KeyCode::KeyO => 'o',
+        KeyCode::KeyP => 'p',
+        KeyCode::KeyQ => 'q',
+        KeyCode::KeyR => 'r',
+        KeyCode::KeyS => 's',
+        KeyCode::KeyT => 't',
+        KeyCode::KeyU => 'u',
+        KeyCode::KeyV => 'v',
+        KeyCode::KeyW => 'w',
+        KeyCode::KeyX => 'x',
+        KeyCode::KeyY => 'y',
+        KeyCode::KeyZ => 'z',
+        KeyCode::Backquote => '`',
+        KeyCode::Minus => '-',
+        KeyCode::Equal => '=',
+        KeyCode::BracketLeft => '[',
+        KeyCode::BracketRight => ']',
+        KeyCode::Backslash => '\\',
+        KeyCode::Semicolon => ';',
+        KeyCode::Quote => '\'',
+        KeyCode::Comma => ',',
+        KeyCode::Period => '.',
+        KeyCode::Slash => '/',
+        _ => return None,
+    };
+
+    Some(ch)
+}
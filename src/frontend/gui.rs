@@ -0,0 +1,583 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — egui Window
+// The display under a menu bar: open a ROM, reset, pause, change
+// the CPU speed, toggle quirks and pick a palette. egui draws on
+// wgpu and is pumped from the run loop like any other frontend, so
+// the machine keeps running while a menu is open. The keypad uses
+// the same keys as the wgpu window, except while a text field has
+// focus, and can be remapped from the Emulation menu.
+// ───────────────────────────────────────────────────────────────
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use winit::application::ApplicationHandler;
+use winit::dpi::{LogicalSize, PhysicalSize};
+use winit::event::{ElementState, KeyEvent, WindowEvent};
+use winit::event_loop::{ActiveEventLoop, EventLoop};
+use winit::keyboard::{KeyCode, ModifiersState, PhysicalKey};
+use winit::platform::pump_events::EventLoopExtPumpEvents;
+use winit::window::{Window, WindowId};
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH, NUM_KEYS};
+use crate::chip8::cpu::Chip8;
+use crate::chip8::display::{DirtyRows, DisplayBuffer};
+use crate::chip8::quirks::Quirks;
+use crate::frontend::gpu::qwerty_char;
+use crate::frontend::phosphor::Phosphor;
+use crate::frontend::{Frontend, HostAction, WindowConfig};
+use crate::keymap::{self, Keymap, Remap};
+use crate::palette::{Palette, Preset, Rgb};
+
+// Range of the CPU speed slider, in instructions per second
+const MIN_CPU_HZ: u64 = 60;
+const MAX_CPU_HZ: u64 = 5000;
+
+// Space around the display, and the menu bar's height in points
+const LETTERBOX: egui::Color32 = egui::Color32::BLACK;
+const MENU_HEIGHT: f32 = 24.0;
+
+pub struct GuiFrontend {
+    event_loop: EventLoop<()>,
+    window: WindowState,
+}
+
+impl GuiFrontend {
+
+    // Open a window with the display `config.scale` times the CHIP-8
+    // resolution, the speed slider starting at `cpu_hz`
+    pub fn new(title: &str, config: &WindowConfig, cpu_hz: u64) -> Result<Self, String> {
+        let mut event_loop: EventLoop<()> = EventLoop::new().map_err(|err| err.to_string())?;
+
+        let mut window: WindowState = WindowState {
+            title: title.to_string(),
+            config: *config,
+            renderer: None,
+            error: None,
+            key_changes: Vec::new(),
+            modifiers: ModifiersState::empty(),
+            phosphor: Phosphor::new(config.phosphor_frames),
+            menu: Menu {
+                paused: false,
+                cpu_hz,
+                palette: config.palette,
+                quirks: Quirks::default(),
+                quirks_changed: false,
+                open_path: None,
+                keymap: config.keymap.unwrap_or_default(),
+                remap: None,
+                remapped: None,
+                opened_rom: None,
+                action: HostAction::None,
+            },
+            presented: false,
+        };
+
+        // The window is created once the event loop reports it is resumed
+        while window.renderer.is_none() && window.error.is_none() {
+            event_loop.pump_app_events(Some(Duration::from_millis(10)), &mut window);
+        }
+        if let Some(err) = window.error.take() {
+            return Err(err);
+        }
+
+        Ok(Self { event_loop, window })
+    }
+}
+
+impl Frontend for GuiFrontend {
+    fn pump_events(&mut self, chip8: &mut Chip8) -> HostAction {
+        self.event_loop.pump_app_events(Some(Duration::ZERO), &mut self.window);
+
+        for (ch, pressed) in self.window.key_changes.drain(..) {
+            self.window.menu.keymap.set_key(&mut chip8.keys, ch, pressed);
+        }
+
+        // Keys pressed while remapping are not for the ROM
+        if self.window.menu.remap.is_some() {
+            chip8.keys = [false; NUM_KEYS];
+        }
+
+        let menu: &mut Menu = &mut self.window.menu;
+        if std::mem::take(&mut menu.quirks_changed) {
+            chip8.quirks = menu.quirks;
+        }
+
+        // No frames arrive while paused, but the menus still have to respond
+        if !std::mem::take(&mut self.window.presented)
+            && let Err(err) = self.window.render()
+        {
+            eprintln!("Failed to draw window: {}", err);
+        }
+
+        std::mem::replace(&mut self.window.menu.action, HostAction::None)
+    }
+
+    fn observe(&mut self, chip8: &Chip8) {
+        if !self.window.menu.quirks_changed {
+            self.window.menu.quirks = chip8.quirks;
+        }
+    }
+
+    fn take_opened_rom(&mut self) -> Option<PathBuf> {
+        self.window.menu.opened_rom.take()
+    }
+
+    fn take_keymap(&mut self) -> Option<Keymap> {
+        self.window.menu.remapped.take()
+    }
+
+    fn present(&mut self, frame: &DisplayBuffer, dirty: DirtyRows) -> Result<(), String> {
+        self.window.phosphor.update(frame, dirty);
+        self.window.upload(self.window.phosphor.changed_rows());
+        self.window.presented = true;
+        self.window.render()
+    }
+}
+
+// ===============================================================
+// Menus
+// ===============================================================
+
+// Menu settings, and the requests they leave for the run loop
+struct Menu {
+    paused: bool,
+    cpu_hz: u64,
+    palette: Palette,
+
+    // Copy of the machine's quirks; edits are written back on the next pump
+    quirks: Quirks,
+    quirks_changed: bool,
+
+    // Path typed into the Open ROM window, while it is open
+    open_path: Option<String>,
+
+    // Keypad bindings, the remap in progress and its result for the run loop
+    keymap: Keymap,
+    remap: Option<Remap>,
+    remapped: Option<Keymap>,
+
+    opened_rom: Option<PathBuf>,
+    action: HostAction,
+}
+
+impl Menu {
+    fn draw(&mut self, ctx: &egui::Context, screen: &egui::TextureHandle) {
+        egui::TopBottomPanel::top("menu").exact_height(MENU_HEIGHT).show(ctx, |ui| {
+            egui::MenuBar::new().ui(ui, |ui| {
+                ui.menu_button("File", |ui| self.file_menu(ui));
+                ui.menu_button("Emulation", |ui| self.emulation_menu(ui));
+                ui.menu_button("Quirks", |ui| {
+                    let label: &str = "DXYN clips at the bottom and counts collided rows in VF (SCHIP)";
+                    if ui.checkbox(&mut self.quirks.collision_row_count, label).changed() {
+                        self.quirks_changed = true;
+                    }
+
+                    let label: &str = "Show draws immediately instead of at 60Hz (SCHIP)";
+                    if ui.checkbox(&mut self.quirks.immediate_display, label).changed() {
+                        self.quirks_changed = true;
+                    }
+                });
+                ui.menu_button("Palette", |ui| {
+                    for preset in Preset::ALL {
+                        ui.radio_value(&mut self.palette, preset.palette(), preset.name());
+                    }
+                });
+
+                if self.paused {
+                    ui.label("Paused");
+                }
+            });
+        });
+
+        self.open_window(ctx);
+        self.remap_window(ctx);
+
+        egui::CentralPanel::default().frame(egui::Frame::NONE.fill(LETTERBOX)).show(ctx, |ui| {
+            let area: egui::Rect = ui.max_rect();
+
+            // Largest whole-number scale that fits, like the other windows' default
+            let scale: f32 = (area.width() / DISPLAY_WIDTH as f32).min(area.height() / DISPLAY_HEIGHT as f32).floor().max(1.0);
+            let size: egui::Vec2 = egui::vec2(DISPLAY_WIDTH as f32, DISPLAY_HEIGHT as f32) * scale;
+
+            let image: egui::Image = egui::Image::from_texture(egui::load::SizedTexture::new(screen.id(), size));
+            ui.put(egui::Rect::from_center_size(area.center(), size), image);
+        });
+    }
+
+    fn file_menu(&mut self, ui: &mut egui::Ui) {
+        if ui.button("Open ROM…").clicked() {
+            self.open_path = Some(String::new());
+        }
+        if ui.button("Reset").clicked() {
+            self.action = HostAction::Reset;
+        }
+        if ui.button("Reload ROM and reset").clicked() {
+            self.action = HostAction::HardReset;
+        }
+        ui.separator();
+        if ui.button("Quit").clicked() {
+            self.action = HostAction::Quit;
+        }
+    }
+
+    fn emulation_menu(&mut self, ui: &mut egui::Ui) {
+        if ui.checkbox(&mut self.paused, "Paused").changed() {
+            self.action = HostAction::TogglePause;
+        }
+
+        let speed = egui::Slider::new(&mut self.cpu_hz, MIN_CPU_HZ..=MAX_CPU_HZ)
+            .logarithmic(true)
+            .suffix(" Hz")
+            .text("CPU speed");
+        if ui.add(speed).changed() {
+            self.action = HostAction::SetSpeed(self.cpu_hz);
+        }
+
+        ui.separator();
+        if ui.button("Remap keys…").clicked() {
+            self.remap = Some(Remap::new(self.keymap));
+        }
+    }
+
+    // Path entry for File > Open ROM, while it is open
+    fn open_window(&mut self, ctx: &egui::Context) {
+        let Some(path) = &mut self.open_path else {
+            return;
+        };
+
+        let mut open: bool = false;
+        let mut cancel: bool = false;
+
+        egui::Window::new("Open ROM").collapsible(false).resizable(false).show(ctx, |ui| {
+            let field: egui::Response = ui.text_edit_singleline(path);
+            field.request_focus();
+            let entered: bool = field.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter));
+
+            ui.horizontal(|ui| {
+                open = ui.button("Open").clicked() || entered;
+                cancel = ui.button("Cancel").clicked() || ui.input(|input| input.key_pressed(egui::Key::Escape));
+            });
+        });
+
+        if open && !path.trim().is_empty() {
+            self.opened_rom = Some(PathBuf::from(path.trim()));
+            self.open_path = None;
+        } else if cancel {
+            self.open_path = None;
+        }
+    }
+
+    // The keypad with each key's binding while Emulation > Remap keys runs;
+    // the key presses themselves arrive through handle_key
+    fn remap_window(&mut self, ctx: &egui::Context) {
+        let Some(remap) = &self.remap else {
+            return;
+        };
+
+        let keys: [char; NUM_KEYS] = remap.keymap().keys();
+        let mut cancel: bool = false;
+
+        egui::Window::new("Remap keys").collapsible(false).resizable(false).show(ctx, |ui| {
+            egui::Grid::new("keypad").show(ui, |ui| {
+                for row in keymap::BLOCK {
+                    for key in row {
+                        let text: egui::RichText = egui::RichText::new(format!("{:X}: {}", key, keys[key])).monospace();
+                        if key == remap.current() {
+                            ui.label(text.strong().background_color(ui.visuals().selection.bg_fill));
+                        } else {
+                            ui.label(text);
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+
+            ui.label(format!("Press the key for {:X} (Esc cancels)", remap.current()));
+            cancel = ui.button("Cancel").clicked();
+        });
+
+        if cancel {
+            self.remap = None;
+        }
+    }
+}
+
+// ===============================================================
+// Window events
+// ===============================================================
+
+struct WindowState {
+    title: String,
+    config: WindowConfig,
+    renderer: Option<Renderer>,
+
+    // Failure while creating the window or renderer
+    error: Option<String>,
+
+    // Characters pressed and released since the last pump, as (char, pressed)
+    key_changes: Vec<(char, bool)>,
+
+    modifiers: ModifiersState,
+    phosphor: Phosphor,
+    menu: Menu,
+
+    // Whether a frame was drawn since the last pump
+    presented: bool,
+}
+
+impl ApplicationHandler for WindowState {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.renderer.is_some() {
+            return;
+        }
+
+        let attributes = Window::default_attributes().with_title(self.title.as_str()).with_inner_size(LogicalSize::new(
+            (DISPLAY_WIDTH as u32 * self.config.scale) as f32,
+            (DISPLAY_HEIGHT as u32 * self.config.scale) as f32 + MENU_HEIGHT,
+        ));
+
+        let result: Result<Renderer, String> = event_loop
+            .create_window(attributes)
+            .map_err(|err| err.to_string())
+            .and_then(|window| pollster::block_on(Renderer::new(Arc::new(window), self.menu.palette)));
+
+        match result {
+            Ok(renderer) => self.renderer = Some(renderer),
+            Err(err) => self.error = Some(err),
+        }
+    }
+
+    fn window_event(&mut self, _event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
+        let Some(renderer) = &mut self.renderer else {
+            return;
+        };
+
+        // Keys typed into the Open ROM field do not reach the keypad
+        let consumed: bool = renderer.egui.on_window_event(&renderer.window, &event).consumed;
+
+        match event {
+            WindowEvent::CloseRequested => self.menu.action = HostAction::Quit,
+            WindowEvent::Resized(size) => renderer.resize(size),
+            WindowEvent::ModifiersChanged(modifiers) => self.modifiers = modifiers.state(),
+            WindowEvent::KeyboardInput { event, .. } if !consumed => self.handle_key(event),
+            _ => {}
+        }
+    }
+}
+
+impl WindowState {
+    fn handle_key(&mut self, event: KeyEvent) {
+        let PhysicalKey::Code(code) = event.physical_key else {
+            return;
+        };
+        let pressed: bool = event.state == ElementState::Pressed;
+
+        if let Some(remap) = &mut self.menu.remap {
+            if pressed && !event.repeat {
+                if code == KeyCode::Escape {
+                    self.menu.remap = None;
+                } else if let Some(keymap) = qwerty_char(code).and_then(|ch| remap.press(ch)) {
+                    self.menu.keymap = keymap;
+                    self.menu.remapped = Some(keymap);
+                    self.menu.remap = None;
+                }
+            }
+            return;
+        }
+
+        match code {
+            KeyCode::Escape if pressed && self.menu.open_path.is_none() => self.menu.action = HostAction::Quit,
+            KeyCode::F5 if pressed && !event.repeat && self.menu.action != HostAction::Quit => {
+                self.menu.action = if self.modifiers.shift_key() {
+                    HostAction::HardReset
+                } else {
+                    HostAction::Reset
+                };
+            }
+            _ => {
+                if let Some(ch) = qwerty_char(code) {
+                    self.key_changes.push((ch, pressed));
+                }
+            }
+        }
+    }
+
+    // Copy the `rows` of the phosphor levels that changed into the display texture
+    fn upload(&mut self, rows: DirtyRows) {
+        let (Some(renderer), Some(rows)) = (&mut self.renderer, rows.span()) else {
+            return;
+        };
+
+        let levels = &self.phosphor.levels()[rows.clone()];
+        let pixels: Vec<egui::Color32> =
+            levels.iter().flatten().map(|&level| color(self.menu.palette.shade(level))).collect();
+
+        renderer.screen.set_partial(
+            [0, rows.start],
+            egui::ColorImage::new([DISPLAY_WIDTH, rows.len()], pixels),
+            egui::TextureOptions::NEAREST,
+        );
+    }
+
+    fn render(&mut self) -> Result<(), String> {
+        let Some(renderer) = &mut self.renderer else {
+            return Ok(());
+        };
+
+        let palette: Palette = self.menu.palette;
+        renderer.render(&mut self.menu)?;
+
+        // A new palette recolors every pixel, from the next frame on
+        if self.menu.palette != palette {
+            self.upload(DirtyRows::ALL);
+        }
+
+        Ok(())
+    }
+}
+
+// ===============================================================
+// Renderer
+// ===============================================================
+
+struct Renderer {
+    window: Arc<Window>,
+    surface: wgpu::Surface<'static>,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    config: wgpu::SurfaceConfiguration,
+
+    egui: egui_winit::State,
+    painter: egui_wgpu::Renderer,
+
+    // The display as an egui texture, one texel per CHIP-8 pixel
+    screen: egui::TextureHandle,
+}
+
+impl Renderer {
+    async fn new(window: Arc<Window>, palette: Palette) -> Result<Self, String> {
+        let size: PhysicalSize<u32> = window.inner_size();
+
+        let instance: wgpu::Instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let surface: wgpu::Surface<'static> =
+            instance.create_surface(Arc::clone(&window)).map_err(|err| err.to_string())?;
+
+        let adapter: wgpu::Adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                compatible_surface: Some(&surface),
+                ..Default::default()
+            })
+            .await
+            .map_err(|err| err.to_string())?;
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor::default())
+            .await
+            .map_err(|err| err.to_string())?;
+
+        let mut config: wgpu::SurfaceConfiguration = surface
+            .get_default_config(&adapter, size.width.max(1), size.height.max(1))
+            .ok_or("surface is not supported by the adapter")?;
+
+        // egui blends in gamma space and expects a surface that does not convert
+        let capabilities: wgpu::SurfaceCapabilities = surface.get_capabilities(&adapter);
+        if let Some(&format) = capabilities.formats.iter().find(|format| !format.is_srgb()) {
+            config.format = format;
+        }
+        surface.configure(&device, &config);
+
+        let ctx: egui::Context = egui::Context::default();
+        let egui: egui_winit::State = egui_winit::State::new(
+            ctx.clone(),
+            egui::ViewportId::ROOT,
+            &window,
+            Some(window.scale_factor() as f32),
+            None,
+            Some(device.limits().max_texture_dimension_2d as usize),
+        );
+        let painter: egui_wgpu::Renderer =
+            egui_wgpu::Renderer::new(&device, config.format, egui_wgpu::RendererOptions::default());
+
+        let blank: egui::ColorImage =
+            egui::ColorImage::new([DISPLAY_WIDTH, DISPLAY_HEIGHT], vec![color(palette.background); DISPLAY_WIDTH * DISPLAY_HEIGHT]);
+        let screen: egui::TextureHandle = ctx.load_texture("chip8 display", blank, egui::TextureOptions::NEAREST);
+
+        Ok(Self { window, surface, device, queue, config, egui, painter, screen })
+    }
+
+    fn resize(&mut self, size: PhysicalSize<u32>) {
+        // A minimised window reports zero size, which cannot be configured
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
+
+        self.config.width = size.width;
+        self.config.height = size.height;
+        self.surface.configure(&self.device, &self.config);
+    }
+
+    fn render(&mut self, menu: &mut Menu) -> Result<(), String> {
+        let input: egui::RawInput = self.egui.take_egui_input(&self.window);
+        let ctx: egui::Context = self.egui.egui_ctx().clone();
+        let output: egui::FullOutput = ctx.run(input, |ctx| menu.draw(ctx, &self.screen));
+
+        self.egui.handle_platform_output(&self.window, output.platform_output);
+
+        for (id, delta) in &output.textures_delta.set {
+            self.painter.update_texture(&self.device, &self.queue, *id, delta);
+        }
+
+        let surface_texture: wgpu::SurfaceTexture = match self.surface.get_current_texture() {
+            Ok(surface_texture) => surface_texture,
+
+            // Reconfigure and skip this frame; the next one will draw
+            Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+                self.surface.configure(&self.device, &self.config);
+                return Ok(());
+            }
+            Err(err) => return Err(err.to_string()),
+        };
+        let target: wgpu::TextureView = surface_texture.texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let jobs: Vec<egui::ClippedPrimitive> = ctx.tessellate(output.shapes, output.pixels_per_point);
+        let descriptor: egui_wgpu::ScreenDescriptor = egui_wgpu::ScreenDescriptor {
+            size_in_pixels: [self.config.width, self.config.height],
+            pixels_per_point: output.pixels_per_point,
+        };
+
+        let mut encoder: wgpu::CommandEncoder =
+            self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        let uploads: Vec<wgpu::CommandBuffer> =
+            self.painter.update_buffers(&self.device, &self.queue, &mut encoder, &jobs, &descriptor);
+        {
+            let mut pass: wgpu::RenderPass<'static> = encoder
+                .begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: None,
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &target,
+                        depth_slice: None,
+                        resolve_target: None,
+                        ops: wgpu::Operations { load: wgpu::LoadOp::Clear(wgpu::Color::BLACK), store: wgpu::StoreOp::Store },
+                    })],
+                    ..Default::default()
+                })
+                .forget_lifetime();
+
+            self.painter.render(&mut pass, &jobs, &descriptor);
+        }
+
+        self.queue.submit(uploads.into_iter().chain([encoder.finish()]));
+        surface_texture.present();
+
+        for id in &output.textures_delta.free {
+            self.painter.free_texture(id);
+        }
+
+        Ok(())
+    }
+}
+
+fn color(rgb: Rgb) -> egui::Color32 {
+    egui::Color32::from_rgb(rgb.r, rgb.g, rgb.b)
+}
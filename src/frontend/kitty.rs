@@ -0,0 +1,68 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Kitty Graphics
+// Sends display frames to terminals that speak the kitty graphics
+// protocol (kitty, WezTerm, Ghostty) as upscaled RGB PNGs. Every frame
+// replaces the same image id, so the terminal keeps one placement.
+// ───────────────────────────────────────────────────────────────
+
+use std::fmt::Write as _;
+
+use crate::chip8::display::DisplayBuffer;
+use crate::palette::Palette;
+use crate::screenshot::Image;
+
+// Screen pixels per CHIP-8 pixel, done here so the terminal never smooths
+const SCALE: usize = 6;
+
+// Id of the one image the frontend keeps replacing
+const IMAGE_ID: u32 = 1;
+
+// Largest base64 payload the protocol allows per escape sequence
+const MAX_CHUNK: usize = 4096;
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+// Escape sequences that transmit and show the frame at the cursor
+pub(crate) fn encode(frame: &DisplayBuffer, palette: &Palette) -> String {
+    let mut png: Vec<u8> = Vec::new();
+    let _ = Image::from_frame_colored(frame, SCALE, palette).write_png(&mut png);
+
+    let payload: String = base64(&png);
+    let mut out: String = String::with_capacity(payload.len() + payload.len() / MAX_CHUNK * 16 + 64);
+
+    // PNG data (f=100), transmit and display (a=T), no replies (q=2) and
+    // leave the cursor where it is (C=1); later chunks only carry m=
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(MAX_CHUNK).collect();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let more: u8 = (index + 1 < chunks.len()) as u8;
+
+        out.push_str("\x1b_G");
+        if index == 0 {
+            let _ = write!(out, "a=T,f=100,i={},q=2,C=1,", IMAGE_ID);
+        }
+        let _ = write!(out, "m={};", more);
+        out.push_str(std::str::from_utf8(chunk).unwrap_or_default());
+        out.push_str("\x1b\\");
+    }
+
+    out
+}
+
+// Standard base64 with padding
+fn base64(data: &[u8]) -> String {
+    let mut out: String = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for group in data.chunks(3) {
+        let bits: u32 = group.iter().enumerate().fold(0, |bits, (index, &byte)| bits | (byte as u32) << (16 - 8 * index));
+
+        for index in 0..4 {
+            if index <= group.len() {
+                out.push(BASE64[(bits >> (18 - 6 * index) & 0x3F) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+
+    out
+}
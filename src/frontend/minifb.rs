@@ -0,0 +1,195 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — minifb Window
+// Smallest possible window: the display is drawn into a buffer the
+// size of the window, fitted by the current scale mode (F8 cycles,
+// -/= zoom) and letterboxed. The buffer is kept between frames and
+// only rows that changed are redrawn, unless the layout moved. No native libraries are needed at build
+// time. minifb cannot switch to fullscreen after the window is open.
+// ───────────────────────────────────────────────────────────────
+
+use minifb::{Key, KeyRepeat, Window, WindowOptions};
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH, NUM_KEYS};
+use crate::chip8::cpu::Chip8;
+use crate::chip8::display::{DirtyRows, DisplayBuffer};
+use crate::frontend::phosphor::{Levels, Phosphor};
+use crate::frontend::{Frontend, GRID_LEVEL, HostAction, MIN_GRID_SCALE, Scaling, Viewport, WindowConfig};
+use crate::keymap::Keymap;
+
+// 0RGB bars around the display when the window is not an exact multiple
+const LETTERBOX: u32 = 0x00_00_00;
+
+pub struct MinifbFrontend {
+    window: Window,
+    buffer: Vec<u32>,
+
+    // Window size and viewport the buffer was drawn for
+    drawn: Option<(usize, usize, Viewport)>,
+
+    // Host key for each CHIP-8 key 0x0..0xF
+    keymap: [Key; NUM_KEYS],
+    phosphor: Phosphor,
+    scaling: Scaling,
+
+    // 0RGB pixel for each brightness level
+    shades: [u32; 256],
+
+    // 0RGB grid line color, if --grid is on
+    grid: Option<u32>,
+}
+
+impl MinifbFrontend {
+
+    // Open a window `config.scale` times the CHIP-8 resolution
+    pub fn new(title: &str, config: &WindowConfig) -> Result<Self, String> {
+        let scale: u32 = config.scale;
+        let options: WindowOptions = WindowOptions { resize: true, ..WindowOptions::default() };
+
+        let mut window: Window = Window::new(
+            title,
+            DISPLAY_WIDTH * scale as usize,
+            DISPLAY_HEIGHT * scale as usize,
+            options,
+        )
+        .map_err(|err| err.to_string())?;
+
+        // The scheduler paces the loop; minifb must not sleep on its own
+        window.set_target_fps(0);
+
+        Ok(Self {
+            window,
+            buffer: Vec::new(),
+            drawn: None,
+            keymap: keymap(config.keymap.unwrap_or(config.layout.keymap())),
+            phosphor: Phosphor::new(config.phosphor_frames),
+            scaling: Scaling::new(config),
+            shades: std::array::from_fn(|level| config.palette.shade(level as u8).to_u32()),
+            grid: config.grid.then(|| config.palette.shade(GRID_LEVEL).to_u32()),
+        })
+    }
+}
+
+impl Frontend for MinifbFrontend {
+    // Input is polled by minifb during present; this reads the resulting key state
+    fn pump_events(&mut self, chip8: &mut Chip8) -> HostAction {
+        if !self.window.is_open() || self.window.is_key_down(Key::Escape) {
+            return HostAction::Quit;
+        }
+
+        for (key, &mapped) in self.keymap.iter().enumerate() {
+            chip8.keys[key] = self.window.is_key_down(mapped);
+        }
+
+        if self.window.is_key_pressed(Key::F5, KeyRepeat::No) {
+            let shift: bool = self.window.is_key_down(Key::LeftShift) || self.window.is_key_down(Key::RightShift);
+            return if shift { HostAction::HardReset } else { HostAction::Reset };
+        }
+
+        if self.window.is_key_pressed(Key::F6, KeyRepeat::No) {
+            return HostAction::PlayMacro;
+        }
+
+        if self.window.is_key_pressed(Key::F3, KeyRepeat::No) {
+            return HostAction::TogglePerfOverlay;
+        }
+
+        if self.window.is_key_pressed(Key::F4, KeyRepeat::No) {
+            return HostAction::ToggleKeypadOverlay;
+        }
+
+        if self.window.is_key_pressed(Key::F8, KeyRepeat::No) {
+            self.scaling.cycle();
+        }
+        if self.window.is_key_pressed(Key::Minus, KeyRepeat::Yes) {
+            self.scaling.zoom(-1);
+        }
+        if self.window.is_key_pressed(Key::Equal, KeyRepeat::Yes) {
+            self.scaling.zoom(1);
+        }
+
+        HostAction::None
+    }
+
+    // The window size is read on every frame, so resizes need no event handling
+    fn present(&mut self, frame: &DisplayBuffer, dirty: DirtyRows) -> Result<(), String> {
+        let (width, height) = self.window.get_size();
+
+        // Minimised: nothing to draw, but input still has to be polled
+        if width == 0 || height == 0 {
+            self.window.update();
+            return Ok(());
+        }
+
+        let viewport: Viewport = self.scaling.viewport(width as u32, height as u32);
+        self.phosphor.update(frame, dirty);
+        let levels: &Levels = self.phosphor.levels();
+        let grid: Option<u32> = self.grid.filter(|_| viewport.pixel_size() >= MIN_GRID_SCALE);
+
+        // A resize or zoom moves everything; otherwise only changed rows are redrawn
+        let rows: DirtyRows = if self.drawn == Some((width, height, viewport)) {
+            self.phosphor.changed_rows()
+        } else {
+            self.drawn = Some((width, height, viewport));
+            self.buffer.clear();
+            self.buffer.resize(width * height, LETTERBOX);
+            DirtyRows::ALL
+        };
+
+        // A window smaller than the display shows its top-left corner
+        for (y, row) in self.buffer.chunks_exact_mut(width).enumerate().skip(viewport.y as usize) {
+            let Some(display_row) = viewport.row_at(y as u32) else {
+                break;
+            };
+            if !rows.contains(display_row) {
+                continue;
+            }
+            let pixels: &[u8; DISPLAY_WIDTH] = &levels[display_row];
+            let row_line: bool = y > viewport.y as usize && viewport.row_at(y as u32 - 1) != Some(display_row);
+
+            for (x, pixel) in row.iter_mut().enumerate().skip(viewport.x as usize) {
+                let Some(column) = viewport.column_at(x as u32) else {
+                    break;
+                };
+                let level: u8 = pixels[column];
+                let column_line: bool = x > viewport.x as usize && viewport.column_at(x as u32 - 1) != Some(column);
+
+                *pixel = match grid {
+                    Some(line) if row_line || column_line => line,
+                    _ => self.shades[level as usize],
+                };
+            }
+        }
+
+        self.window.update_with_buffer(&self.buffer, width, height).map_err(|err| err.to_string())
+    }
+}
+
+// minifb reports keys by the character they type (on X11 at least), so
+// the keypad follows the layout. Characters minifb has no key for, like
+// AZERTY's number row, fall back to the key in the same place on QWERTY.
+fn keymap(keymap: Keymap) -> [Key; NUM_KEYS] {
+    let keys: [char; NUM_KEYS] = keymap.keys();
+    let qwerty: [char; NUM_KEYS] = Keymap::default().keys();
+
+    std::array::from_fn(|key| host_key(keys[key]).or_else(|| host_key(qwerty[key])).unwrap_or(Key::Unknown))
+}
+
+fn host_key(ch: char) -> Option<Key> {
+    let key: Key = match ch {
+        '0'..='9' => [
+            Key::Key0, Key::Key1, Key::Key2, Key::Key3, Key::Key4,
+            Key::Key5, Key::Key6, Key::Key7, Key::Key8, Key::Key9,
+        ][ch as usize - '0' as usize],
+        'a'..='z' => [
+            Key::A, Key::B, Key::C, Key::D, Key::E, Key::F, Key::G, Key::H, Key::I, Key::J, Key::K, Key::L, Key::M,
+            Key::N, Key::O, Key::P, Key::Q, Key::R, Key::S, Key::T, Key::U, Key::V, Key::W, Key::X, Key::Y, Key::Z,
+        ][ch as usize - 'a' as usize],
+        '\'' => Key::Apostrophe,
+        ',' => Key::Comma,
+        '.' => Key::Period,
+        ';' => Key::Semicolon,
+        _ => return None,
+    };
+
+    Some(key)
+}
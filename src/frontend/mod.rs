@@ -0,0 +1,281 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Frontends
+// Host-side presentation and input. Each backend sits behind its
+// own cargo feature so the core builds without native libraries.
+// ───────────────────────────────────────────────────────────────
+
+pub mod phosphor;
+
+#[cfg(feature = "wgpu")]
+pub mod gpu;
+#[cfg(feature = "gui")]
+pub mod gui;
+#[cfg(feature = "minifb")]
+pub mod minifb;
+#[cfg(feature = "sdl")]
+pub mod sdl;
+#[cfg(feature = "terminal")]
+mod kitty;
+#[cfg(feature = "terminal")]
+mod sixel;
+#[cfg(feature = "terminal")]
+pub mod terminal;
+#[cfg(feature = "tui")]
+pub mod tui;
+
+use std::path::PathBuf;
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH};
+use crate::chip8::cpu::Chip8;
+use crate::chip8::display::{DirtyRows, DisplayBuffer};
+use crate::keymap::{KeyLayout, Keymap};
+use crate::palette::Palette;
+use crate::visual_buzzer::BuzzerStyle;
+
+// A place to show frames and read the keypad from, driven by the real-time loop
+pub trait Frontend {
+    // Drain pending input into the keypad and report any host action
+    fn pump_events(&mut self, chip8: &mut Chip8) -> HostAction;
+
+    // Called before each present, for frontends that show more than the display
+    fn observe(&mut self, _chip8: &Chip8) {}
+
+    // Text pasted since the last call, for frontends that can get at the clipboard
+    fn take_paste(&mut self) -> Option<String> {
+        None
+    }
+
+    // ROM picked since the last call, for frontends with an Open ROM command
+    fn take_opened_rom(&mut self) -> Option<PathBuf> {
+        None
+    }
+
+    // Keymap changed by remapping since the last call, for frontends that can remap keys
+    fn take_keymap(&mut self) -> Option<Keymap> {
+        None
+    }
+
+    // Sound timer started (true) or ran out (false), for frontends that can make a noise
+    fn beep(&mut self, _on: bool) {}
+
+    // How --visual-buzzer shows the sound timer unless a style is given
+    fn buzzer_style(&self) -> BuzzerStyle {
+        BuzzerStyle::Border
+    }
+
+    // Show a completed frame; rows outside `dirty` are unchanged since the last one
+    fn present(&mut self, frame: &DisplayBuffer, dirty: DirtyRows) -> Result<(), String>;
+}
+
+// Requests from the user to the run loop, as opposed to keypad input
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum HostAction {
+    None,
+
+    // Window closed or quit key pressed
+    Quit,
+
+    // Restart the loaded ROM (F5)
+    Reset,
+
+    // Re-read the ROM from disk, then restart (Shift+F5)
+    HardReset,
+
+    // Play the loaded input macro from the start (F6)
+    PlayMacro,
+
+    // Show or hide the FPS/IPS overlay (F3)
+    TogglePerfOverlay,
+
+    // Show or hide the keypad overlay (F4)
+    ToggleKeypadOverlay,
+
+    // Stop or resume the machine (gui menu)
+    TogglePause,
+
+    // Run the CPU at this many instructions per second (gui menu)
+    SetSpeed(u64),
+}
+
+// Brightness of the --grid lines, between background (0) and foreground (255)
+pub const GRID_LEVEL: u8 = 64;
+
+// Smallest scale the grid is drawn at; below it the lines would swallow the pixels
+pub const MIN_GRID_SCALE: u32 = 4;
+
+// How the window frontends draw the display
+#[derive(Clone, Copy, Debug)]
+pub struct WindowConfig {
+    // Initial window size as a multiple of the display, and the Fixed mode scale
+    pub scale: u32,
+
+    pub palette: Palette,
+
+    // Frames a pixel takes to fade out after turning off; 0 for none
+    pub phosphor_frames: u8,
+
+    // How the display is fitted into the window
+    pub scaling: ScaleMode,
+
+    // Thin lines between display pixels, at scales of MIN_GRID_SCALE and up
+    pub grid: bool,
+
+    // Post effects; only the wgpu window applies them
+    pub crt: CrtEffects,
+
+    // Characters the keypad keys type; only minifb reads keys by character
+    pub layout: KeyLayout,
+
+    // Bindings from --keymap, replacing the layout (and for windows that read
+    // key positions, the QWERTY block)
+    pub keymap: Option<Keymap>,
+}
+
+// CRT-style post effects, each strength from 0.0 (none) to 1.0
+#[derive(Clone, Copy, Debug)]
+pub struct CrtEffects {
+    // Whether the effects start switched on (F9 toggles them)
+    pub enabled: bool,
+
+    // How much darker the edges of each display row are
+    pub scanlines: f32,
+
+    // Barrel distortion bending the screen outwards
+    pub curvature: f32,
+
+    // Darkening towards the corners
+    pub vignette: f32,
+}
+
+impl Default for CrtEffects {
+    fn default() -> Self {
+        Self { enabled: false, scanlines: 0.35, curvature: 0.08, vignette: 0.4 }
+    }
+}
+
+// How the display is fitted into the window; F8 cycles through them
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ScaleMode {
+    // Largest whole-number scale that fits, letterboxed, so every pixel is the same size
+    #[default]
+    Integer,
+
+    // Largest size that keeps the 2:1 shape, pixels may differ by one
+    Aspect,
+
+    // Fill the whole window, whatever its shape
+    Stretch,
+
+    // Always the same scale, cropped if the window is too small
+    Fixed,
+}
+
+impl ScaleMode {
+
+    pub fn next(self) -> Self {
+        match self {
+            ScaleMode::Integer => ScaleMode::Aspect,
+            ScaleMode::Aspect => ScaleMode::Stretch,
+            ScaleMode::Stretch => ScaleMode::Fixed,
+            ScaleMode::Fixed => ScaleMode::Integer,
+        }
+    }
+}
+
+// Largest scale the -/= keys zoom to
+pub const MAX_FIXED_SCALE: u32 = 64;
+
+// A window's scaling state: the mode, and the scale Fixed mode keeps to
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Scaling {
+    pub mode: ScaleMode,
+    pub fixed: u32,
+}
+
+impl Scaling {
+
+    pub fn new(config: &WindowConfig) -> Self {
+        Self { mode: config.scaling, fixed: config.scale.clamp(1, MAX_FIXED_SCALE) }
+    }
+
+    pub fn cycle(&mut self) {
+        self.mode = self.mode.next();
+    }
+
+    // Step the fixed scale by `step`, switching to Fixed mode
+    pub fn zoom(&mut self, step: i32) {
+        self.mode = ScaleMode::Fixed;
+        self.fixed = self.fixed.saturating_add_signed(step).clamp(1, MAX_FIXED_SCALE);
+    }
+
+    pub fn viewport(&self, width: u32, height: u32) -> Viewport {
+        Viewport::fit(self.mode, self.fixed, width, height)
+    }
+}
+
+// Where the display goes in a window, centred with letterbox bars around it
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Viewport {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Viewport {
+
+    // Never below scale 1, even if the window is smaller than the display
+    pub fn fit(mode: ScaleMode, fixed: u32, width: u32, height: u32) -> Self {
+        let display_width: u32 = DISPLAY_WIDTH as u32;
+        let display_height: u32 = DISPLAY_HEIGHT as u32;
+
+        let (area_width, area_height) = match mode {
+            ScaleMode::Integer => {
+                let scale: u32 = (width / display_width).min(height / display_height).max(1);
+                (display_width * scale, display_height * scale)
+            }
+            ScaleMode::Aspect if width * display_height <= height * display_width => {
+                (width, width * display_height / display_width)
+            }
+            ScaleMode::Aspect => (height * display_width / display_height, height),
+            ScaleMode::Stretch => (width, height),
+            ScaleMode::Fixed => (display_width * fixed, display_height * fixed),
+        };
+        let area_width: u32 = area_width.max(display_width);
+        let area_height: u32 = area_height.max(display_height);
+
+        Self {
+            x: width.saturating_sub(area_width) / 2,
+            y: height.saturating_sub(area_height) / 2,
+            width: area_width,
+            height: area_height,
+        }
+    }
+
+    // Left edge of display column `column`; DISPLAY_WIDTH gives the right edge
+    pub fn column_x(&self, column: usize) -> u32 {
+        self.x + (column as u64 * self.width as u64 / DISPLAY_WIDTH as u64) as u32
+    }
+
+    // Top edge of display row `row`; DISPLAY_HEIGHT gives the bottom edge
+    pub fn row_y(&self, row: usize) -> u32 {
+        self.y + (row as u64 * self.height as u64 / DISPLAY_HEIGHT as u64) as u32
+    }
+
+    // Display column under window column `x`, if it is inside the display
+    pub fn column_at(&self, x: u32) -> Option<usize> {
+        let column: u64 = (x.checked_sub(self.x)? as u64 * DISPLAY_WIDTH as u64).checked_div(self.width as u64)?;
+        ((column as usize) < DISPLAY_WIDTH).then_some(column as usize)
+    }
+
+    // Display row under window row `y`, if it is inside the display
+    pub fn row_at(&self, y: u32) -> Option<usize> {
+        let row: u64 = (y.checked_sub(self.y)? as u64 * DISPLAY_HEIGHT as u64).checked_div(self.height as u64)?;
+        ((row as usize) < DISPLAY_HEIGHT).then_some(row as usize)
+    }
+
+    // Size of the smallest display pixel, in whole window pixels
+    pub fn pixel_size(&self) -> u32 {
+        (self.width / DISPLAY_WIDTH as u32).min(self.height / DISPLAY_HEIGHT as u32)
+    }
+}
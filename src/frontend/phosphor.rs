@@ -0,0 +1,140 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Phosphor Persistence
+// Simulates a slow-phosphor screen: lit pixels glow at full
+// brightness and fade out over a few frames after turning off,
+// which hides most of the flicker from ROMs that erase and redraw
+// their sprites every frame.
+// ───────────────────────────────────────────────────────────────
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH};
+use crate::chip8::display::{DirtyRows, DisplayBuffer};
+
+// Brightness of every pixel, 0 (off) to 255 (fully lit)
+pub type Levels = [[u8; DISPLAY_WIDTH]; DISPLAY_HEIGHT];
+
+pub struct Phosphor {
+    levels: Levels,
+
+    // Brightness lost per frame once a pixel is off
+    fade_step: u8,
+
+    // Rows with a pixel still fading out, which change without being drawn to
+    fading: DirtyRows,
+
+    // Rows whose levels the last update touched
+    changed: DirtyRows,
+}
+
+impl Phosphor {
+
+    // Unlit pixels stay visible for `frames` frames; 0 turns them off at once
+    pub fn new(frames: u8) -> Self {
+        let fade_step: u8 = 255_u16.div_ceil(frames as u16 + 1) as u8;
+
+        Self {
+            levels: [[0; DISPLAY_WIDTH]; DISPLAY_HEIGHT],
+            fade_step,
+            fading: DirtyRows::default(),
+            changed: DirtyRows::ALL,
+        }
+    }
+
+    // Advance by one presented frame. Only the `dirty` rows of `frame` and
+    // rows still fading are recomputed; the rest cannot have changed.
+    pub fn update(&mut self, frame: &DisplayBuffer, dirty: DirtyRows) -> &Levels {
+        self.changed = dirty.union(self.fading);
+        self.fading = DirtyRows::default();
+
+        for row in self.changed.iter() {
+            for (level, &lit) in self.levels[row].iter_mut().zip(frame[row].iter()) {
+                *level = if lit { 255 } else { level.saturating_sub(self.fade_step) };
+
+                if !lit && *level > 0 {
+                    self.fading.mark(row);
+                }
+            }
+        }
+
+        &self.levels
+    }
+
+    // Brightness after the last update
+    pub fn levels(&self) -> &Levels {
+        &self.levels
+    }
+
+    // Rows the last update may have changed, for frontends that upload only those
+    pub fn changed_rows(&self) -> DirtyRows {
+        self.changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chip8::display::BLANK_DISPLAY;
+
+    fn rows(rows: &[usize]) -> DirtyRows {
+        let mut dirty: DirtyRows = DirtyRows::default();
+        for &row in rows {
+            dirty.mark(row);
+        }
+        dirty
+    }
+
+    #[test]
+    fn first_update_changes_every_row() {
+        let phosphor: Phosphor = Phosphor::new(2);
+        assert_eq!(phosphor.changed_rows(), DirtyRows::ALL);
+    }
+
+    #[test]
+    fn fading_row_stays_dirty_until_dark() {
+        let mut phosphor: Phosphor = Phosphor::new(2);
+        let mut lit: DisplayBuffer = BLANK_DISPLAY;
+        lit[5][7] = true;
+
+        phosphor.update(&lit, DirtyRows::ALL);
+        assert_eq!(phosphor.levels()[5][7], 255);
+
+        // Turned off: 255 -> 170 -> 85 -> 0, dirty on each step without
+        // the core marking the row again
+        phosphor.update(&BLANK_DISPLAY, rows(&[5]));
+        for expected in [170, 85, 0] {
+            assert!(phosphor.changed_rows().contains(5));
+            assert_eq!(phosphor.levels()[5][7], expected);
+            phosphor.update(&BLANK_DISPLAY, DirtyRows::default());
+        }
+
+        assert!(phosphor.changed_rows().is_empty());
+    }
+
+    #[test]
+    fn only_dirty_rows_are_recomputed() {
+        let mut phosphor: Phosphor = Phosphor::new(0);
+        phosphor.update(&BLANK_DISPLAY, DirtyRows::ALL);
+
+        let mut lit: DisplayBuffer = BLANK_DISPLAY;
+        lit[1][0] = true;
+        lit[2][0] = true;
+        phosphor.update(&lit, rows(&[1]));
+
+        assert_eq!(phosphor.changed_rows(), rows(&[1]));
+        assert_eq!(phosphor.levels()[1][0], 255);
+        assert_eq!(phosphor.levels()[2][0], 0);
+    }
+
+    #[test]
+    fn no_persistence_turns_pixels_off_at_once() {
+        let mut phosphor: Phosphor = Phosphor::new(0);
+        let mut lit: DisplayBuffer = BLANK_DISPLAY;
+        lit[0][0] = true;
+
+        phosphor.update(&lit, DirtyRows::ALL);
+        phosphor.update(&BLANK_DISPLAY, rows(&[0]));
+
+        assert_eq!(phosphor.levels()[0][0], 0);
+        phosphor.update(&BLANK_DISPLAY, DirtyRows::default());
+        assert!(phosphor.changed_rows().is_empty());
+    }
+}
@@ -0,0 +1,220 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — SDL2 Window
+// Presents completed frames in a resizable window, fitted by the
+// current scale mode (F8 cycles, -/= zoom) with letterboxing around
+// it, and feeds keyboard state into the keypad. The display lives in a
+// texture one texel per pixel, where only rows that changed are
+// rewritten, and is stretched into place; --grid lines go over it.
+// F11 toggles fullscreen.
+// Keys are matched by scancode, so the pad keeps its physical
+// 1234/QWER/ASDF/ZXCV shape on any layout. Ctrl+V types the
+// clipboard on the keypad.
+// ───────────────────────────────────────────────────────────────
+
+use sdl2::event::Event;
+use sdl2::keyboard::{Mod, Scancode};
+use sdl2::pixels::{Color, PixelFormatEnum};
+use sdl2::rect::Rect;
+use sdl2::render::{Texture, WindowCanvas};
+use sdl2::video::FullscreenType;
+use sdl2::EventPump;
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH, NUM_KEYS};
+use crate::chip8::cpu::Chip8;
+use crate::chip8::display::{DirtyRows, DisplayBuffer};
+use crate::frontend::phosphor::Phosphor;
+use crate::frontend::{Frontend, GRID_LEVEL, HostAction, MIN_GRID_SCALE, Scaling, Viewport, WindowConfig};
+use crate::keymap::Keymap;
+use crate::palette::{Palette, Rgb};
+
+// Bars around the display when the window is not an exact multiple
+const LETTERBOX: Color = Color::RGB(0x00, 0x00, 0x00);
+
+pub struct SdlFrontend {
+    // Keeps SDL initialised for as long as the window lives
+    _context: sdl2::Sdl,
+    video: sdl2::VideoSubsystem,
+    canvas: WindowCanvas,
+
+    // The display, one RGB24 texel per pixel; freed along with the canvas
+    texture: Texture,
+    event_pump: EventPump,
+    palette: Palette,
+    phosphor: Phosphor,
+    scaling: Scaling,
+    grid: bool,
+
+    // Host scancode for each CHIP-8 key 0x0..0xF
+    keymap: [Option<Scancode>; NUM_KEYS],
+
+    // Clipboard text from Ctrl+V, not yet taken
+    pasted: Option<String>,
+}
+
+impl SdlFrontend {
+
+    // Open a window `config.scale` times the CHIP-8 resolution
+    pub fn new(title: &str, config: &WindowConfig) -> Result<Self, String> {
+        let scale: u32 = config.scale;
+        let context: sdl2::Sdl = sdl2::init()?;
+        let video: sdl2::VideoSubsystem = context.video()?;
+
+        let window: sdl2::video::Window = video
+            .window(title, DISPLAY_WIDTH as u32 * scale, DISPLAY_HEIGHT as u32 * scale)
+            .position_centered()
+            .resizable()
+            .build()
+            .map_err(|err| err.to_string())?;
+
+        let canvas: WindowCanvas = window.into_canvas().build().map_err(|err| err.to_string())?;
+        let texture: Texture = canvas
+            .create_texture_streaming(PixelFormatEnum::RGB24, DISPLAY_WIDTH as u32, DISPLAY_HEIGHT as u32)
+            .map_err(|err| err.to_string())?;
+        let event_pump: EventPump = context.event_pump()?;
+
+        Ok(Self {
+            _context: context,
+            video,
+            canvas,
+            texture,
+            event_pump,
+            palette: config.palette,
+            phosphor: Phosphor::new(config.phosphor_frames),
+            scaling: Scaling::new(config),
+            grid: config.grid,
+            keymap: scancodes(config.keymap.unwrap_or_default()),
+            pasted: None,
+        })
+    }
+}
+
+impl Frontend for SdlFrontend {
+    fn pump_events(&mut self, chip8: &mut Chip8) -> HostAction {
+        let mut action: HostAction = HostAction::None;
+
+        for event in self.event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. } => return HostAction::Quit,
+                Event::KeyDown { scancode: Some(Scancode::Escape), .. } => return HostAction::Quit,
+                Event::KeyDown { scancode: Some(Scancode::F5), keymod, repeat: false, .. } => {
+                    action = if keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD) {
+                        HostAction::HardReset
+                    } else {
+                        HostAction::Reset
+                    };
+                }
+                Event::KeyDown { scancode: Some(Scancode::F6), repeat: false, .. } => {
+                    action = HostAction::PlayMacro;
+                }
+                Event::KeyDown { scancode: Some(Scancode::F3), repeat: false, .. } => {
+                    action = HostAction::TogglePerfOverlay;
+                }
+                Event::KeyDown { scancode: Some(Scancode::F4), repeat: false, .. } => {
+                    action = HostAction::ToggleKeypadOverlay;
+                }
+                Event::KeyDown { scancode: Some(Scancode::F8), repeat: false, .. } => self.scaling.cycle(),
+                Event::KeyDown { scancode: Some(Scancode::Minus), .. } => self.scaling.zoom(-1),
+                Event::KeyDown { scancode: Some(Scancode::Equals), .. } => self.scaling.zoom(1),
+                Event::KeyDown { scancode: Some(Scancode::F11), repeat: false, .. } => toggle_fullscreen(&mut self.canvas),
+
+                // V is also keypad F; Ctrl+V pastes instead of pressing it
+                Event::KeyDown { scancode: Some(Scancode::V), keymod, repeat: false, .. }
+                    if keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD) =>
+                {
+                    self.pasted = self.video.clipboard().clipboard_text().ok();
+                }
+                Event::KeyDown { scancode: Some(Scancode::V), keymod, .. }
+                    if keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD) => {}
+                Event::KeyDown { scancode: Some(scancode), .. } => set_key(chip8, &self.keymap, scancode, true),
+                Event::KeyUp { scancode: Some(scancode), .. } => set_key(chip8, &self.keymap, scancode, false),
+                _ => {}
+            }
+        }
+
+        action
+    }
+
+    fn take_paste(&mut self) -> Option<String> {
+        self.pasted.take()
+    }
+
+    // The window size is read on every frame, so resizes need no event handling
+    fn present(&mut self, frame: &DisplayBuffer, dirty: DirtyRows) -> Result<(), String> {
+        let (width, height) = self.canvas.output_size()?;
+        let viewport: Viewport = self.scaling.viewport(width, height);
+
+        // Upload the rows the phosphor changed, as one rectangle
+        self.phosphor.update(frame, dirty);
+        if let Some(rows) = self.phosphor.changed_rows().span() {
+            let texels: Vec<u8> = self.phosphor.levels()[rows.clone()]
+                .iter()
+                .flatten()
+                .flat_map(|&level| {
+                    let rgb: Rgb = self.palette.shade(level);
+                    [rgb.r, rgb.g, rgb.b]
+                })
+                .collect();
+            let rect: Rect = Rect::new(0, rows.start as i32, DISPLAY_WIDTH as u32, rows.len() as u32);
+
+            self.texture.update(rect, &texels, DISPLAY_WIDTH * 3).map_err(|err| err.to_string())?;
+        }
+
+        // The back buffer is undefined after a present, so the window itself
+        // is composed from scratch: bars, then the display stretched into place
+        self.canvas.set_draw_color(LETTERBOX);
+        self.canvas.clear();
+        self.canvas.copy(
+            &self.texture,
+            None,
+            Rect::new(viewport.x as i32, viewport.y as i32, viewport.width, viewport.height),
+        )?;
+
+        if self.grid && viewport.pixel_size() >= MIN_GRID_SCALE {
+            self.canvas.set_draw_color(color(self.palette.shade(GRID_LEVEL)));
+            self.canvas.fill_rects(&grid_lines(&viewport))?;
+        }
+
+        self.canvas.present();
+
+        Ok(())
+    }
+}
+
+// One-pixel lines on the boundaries between display pixels, not around the edge
+fn grid_lines(viewport: &Viewport) -> Vec<Rect> {
+    let columns = (1..DISPLAY_WIDTH).map(|x| Rect::new(viewport.column_x(x) as i32, viewport.y as i32, 1, viewport.height));
+    let rows = (1..DISPLAY_HEIGHT).map(|y| Rect::new(viewport.x as i32, viewport.row_y(y) as i32, viewport.width, 1));
+
+    columns.chain(rows).collect()
+}
+
+fn color(rgb: Rgb) -> Color {
+    Color::RGB(rgb.r, rgb.g, rgb.b)
+}
+
+// SDL names scancodes after what they type on a US keyboard, as keymaps do
+fn scancodes(keymap: Keymap) -> [Option<Scancode>; NUM_KEYS] {
+    let qwerty: [char; NUM_KEYS] = Keymap::default().keys();
+    let scancode = |ch: char| Scancode::from_name(&ch.to_uppercase().to_string());
+
+    std::array::from_fn(|key| scancode(keymap.keys()[key]).or_else(|| scancode(qwerty[key])))
+}
+
+fn set_key(chip8: &mut Chip8, keymap: &[Option<Scancode>; NUM_KEYS], scancode: Scancode, pressed: bool) {
+    if let Some(key) = keymap.iter().position(|&mapped| mapped == Some(scancode)) {
+        chip8.keys[key] = pressed;
+    }
+}
+
+// Switch between a window and desktop-sized fullscreen
+fn toggle_fullscreen(canvas: &mut WindowCanvas) {
+    let window: &mut sdl2::video::Window = canvas.window_mut();
+    let next: FullscreenType = match window.fullscreen_state() {
+        FullscreenType::Off => FullscreenType::Desktop,
+        _ => FullscreenType::Off,
+    };
+
+    if let Err(err) = window.set_fullscreen(next) {
+        eprintln!("Failed to toggle fullscreen: {}", err);
+    }
+}
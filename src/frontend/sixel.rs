@@ -0,0 +1,59 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Sixel Graphics
+// Encodes display frames as Sixel images for terminals that can
+// show them (mlterm, foot, xterm with sixel enabled). Each CHIP-8
+// row becomes one six-pixel sixel band, so every pixel is a 6x6
+// square.
+// ───────────────────────────────────────────────────────────────
+
+use std::fmt::Write as _;
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH};
+use crate::chip8::display::DisplayBuffer;
+use crate::palette::Palette;
+
+// Screen pixels per CHIP-8 pixel; one sixel band is six pixels tall
+const SCALE: usize = 6;
+
+// The whole frame as one sixel image, drawn at the cursor
+pub(crate) fn encode(frame: &DisplayBuffer, palette: &Palette) -> String {
+    let mut out: String = String::with_capacity(4096);
+
+    // 1:1 pixel aspect, then the image size and both color registers (as
+    // RGB percentages)
+    let _ = write!(out, "\x1bP0;1;0q\"1;1;{};{}", DISPLAY_WIDTH * SCALE, DISPLAY_HEIGHT * SCALE);
+    for (register, color) in [palette.background, palette.foreground].into_iter().enumerate() {
+        let percent = |component: u8| component as u32 * 100 / 255;
+        let _ = write!(out, "#{};2;{};{};{}", register, percent(color.r), percent(color.g), percent(color.b));
+    }
+
+    for (y, row) in frame.iter().enumerate() {
+        if y > 0 {
+            out.push('-');
+        }
+
+        // Foreground pass, then back to the band start for the background pass
+        encode_pass(&mut out, row, 1, true);
+        out.push('$');
+        encode_pass(&mut out, row, 0, false);
+    }
+
+    out.push_str("\x1b\\");
+    out
+}
+
+// Paint the pixels of `row` that are `lit` in color `register`; the others
+// are skipped over
+fn encode_pass(out: &mut String, row: &[bool; DISPLAY_WIDTH], register: usize, lit: bool) {
+    let _ = write!(out, "#{}", register);
+
+    let mut x: usize = 0;
+    while x < DISPLAY_WIDTH {
+        let painted: bool = row[x] == lit;
+        let run: usize = row[x..].iter().take_while(|&&pixel| (pixel == lit) == painted).count();
+
+        // '~' sets all six pixels of a column, '?' none
+        let _ = write!(out, "!{}{}", run * SCALE, if painted { '~' } else { '?' });
+        x += run;
+    }
+}
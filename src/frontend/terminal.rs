@@ -0,0 +1,463 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Terminal Frontend
+// Draws the display with half-block characters (two pixel rows per
+// text row, 64x16 cells) or Braille patterns (2x4 pixels per cell,
+// 32x8 cells) and reads the keypad from the terminal, so ROMs can
+// be played over SSH. Only rows that changed are redrawn.
+// Terminals with kitty graphics or Sixel support get real pixels
+// instead. Everything is drawn in the configured palette. Text
+// pasted into the terminal is typed on the keypad.
+// ───────────────────────────────────────────────────────────────
+
+use std::io::{self, Stdout, Write};
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{
+    self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers,
+    KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+};
+use crossterm::style::{Color, Colors, Print, ResetColor, SetColors};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH, NUM_KEYS};
+use crate::chip8::cpu::Chip8;
+use crate::chip8::display::{DirtyRows, DisplayBuffer};
+use crate::frontend::{Frontend, HostAction, kitty, sixel};
+use crate::keymap::{Keymap, Remap};
+use crate::palette::{Palette, Rgb};
+use crate::visual_buzzer::BuzzerStyle;
+
+// Without key release events, a key counts as held this long after its last
+// press or auto-repeat
+const KEY_HOLD: Duration = Duration::from_millis(150);
+
+// How long to wait for the terminal to answer the graphics queries
+#[cfg(unix)]
+const REPLY_TIMEOUT: Duration = Duration::from_millis(200);
+
+// ===============================================================
+// Terminal session and keypad input
+// ===============================================================
+
+// Raw mode on the alternate screen plus keypad state, shared by the
+// terminal-based frontends. Dropping it restores the terminal.
+pub(crate) struct TerminalSession {
+    // Characters typed on the keypad keys
+    keymap: Keymap,
+
+    // Set while F7 remapping runs, for frontends that show it
+    remap: Option<Remap>,
+    can_remap: bool,
+
+    // New bindings once a remap finishes
+    remapped: Option<Keymap>,
+
+    // Whether the terminal reports key releases (kitty keyboard protocol)
+    reports_release: bool,
+
+    // When each held key was last pressed, for terminals without releases
+    pressed_at: [Option<Instant>; NUM_KEYS],
+
+    // Set when the terminal was resized since the last check
+    resized: bool,
+
+    // Text pasted since the last check
+    pasted: Option<String>,
+}
+
+impl TerminalSession {
+
+    pub(crate) fn start(keymap: Keymap) -> io::Result<Self> {
+        let mut stdout: Stdout = io::stdout();
+
+        terminal::enable_raw_mode()?;
+        execute!(stdout, EnterAlternateScreen, Hide, Clear(ClearType::All), EnableBracketedPaste)?;
+
+        let reports_release: bool = terminal::supports_keyboard_enhancement().unwrap_or(false);
+        if reports_release {
+            execute!(stdout, PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES))?;
+        }
+
+        Ok(Self {
+            keymap,
+            remap: None,
+            can_remap: false,
+            remapped: None,
+            reports_release,
+            pressed_at: [None; NUM_KEYS],
+            resized: false,
+            pasted: None,
+        })
+    }
+
+    // Let F7 start remapping the keypad; only for frontends that show `remap`
+    #[cfg_attr(not(feature = "tui"), allow(dead_code))]
+    pub(crate) fn enable_remap(&mut self) {
+        self.can_remap = true;
+    }
+
+    // The remap in progress, if any
+    #[cfg_attr(not(feature = "tui"), allow(dead_code))]
+    pub(crate) fn remap(&self) -> Option<&Remap> {
+        self.remap.as_ref()
+    }
+
+    #[cfg_attr(not(feature = "tui"), allow(dead_code))]
+    pub(crate) fn take_keymap(&mut self) -> Option<Keymap> {
+        self.remapped.take()
+    }
+
+    // Drain pending terminal events into the keypad and report any host action
+    pub(crate) fn pump_events(&mut self, chip8: &mut Chip8) -> HostAction {
+        let mut action: HostAction = HostAction::None;
+
+        while let Ok(true) = event::poll(Duration::ZERO) {
+            let Ok(event) = event::read() else {
+                break;
+            };
+
+            match event {
+                Event::Key(key) => match self.handle_key(chip8, key) {
+                    HostAction::None => {}
+                    HostAction::Quit => return HostAction::Quit,
+                    other => action = other,
+                },
+                Event::Resize(..) => self.resized = true,
+                Event::Paste(text) => self.pasted.get_or_insert_default().push_str(&text),
+                _ => {}
+            }
+        }
+
+        if !self.reports_release {
+            for (key, pressed_at) in self.pressed_at.iter_mut().enumerate() {
+                if pressed_at.is_some_and(|at| at.elapsed() >= KEY_HOLD) {
+                    *pressed_at = None;
+                    chip8.keys[key] = false;
+                }
+            }
+        }
+
+        action
+    }
+
+    // Whether the terminal was resized since the last call
+    pub(crate) fn take_resized(&mut self) -> bool {
+        std::mem::take(&mut self.resized)
+    }
+
+    pub(crate) fn take_paste(&mut self) -> Option<String> {
+        self.pasted.take()
+    }
+
+    fn handle_key(&mut self, chip8: &mut Chip8, key: KeyEvent) -> HostAction {
+        let pressed: bool = key.kind != KeyEventKind::Release;
+
+        if let Some(remap) = &mut self.remap {
+            match key.code {
+                KeyCode::Char('c') if pressed && key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return HostAction::Quit;
+                }
+                KeyCode::Esc if pressed => self.remap = None,
+                KeyCode::Char(ch) if key.kind == KeyEventKind::Press => {
+                    if let Some(keymap) = remap.press(ch) {
+                        self.keymap = keymap;
+                        self.remapped = Some(keymap);
+                        self.remap = None;
+                    }
+                }
+                _ => {}
+            }
+            return HostAction::None;
+        }
+
+        match key.code {
+            KeyCode::Esc if pressed => return HostAction::Quit,
+            KeyCode::Char('c') if pressed && key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return HostAction::Quit;
+            }
+            KeyCode::F(5) if key.kind == KeyEventKind::Press => {
+                return if key.modifiers.contains(KeyModifiers::SHIFT) {
+                    HostAction::HardReset
+                } else {
+                    HostAction::Reset
+                };
+            }
+            KeyCode::F(6) if key.kind == KeyEventKind::Press => return HostAction::PlayMacro,
+            KeyCode::F(3) if key.kind == KeyEventKind::Press => return HostAction::TogglePerfOverlay,
+            KeyCode::F(4) if key.kind == KeyEventKind::Press => return HostAction::ToggleKeypadOverlay,
+            KeyCode::F(7) if key.kind == KeyEventKind::Press && self.can_remap => {
+                // Release everything first; the keys pressed next are not for the ROM
+                for (key, pressed_at) in self.pressed_at.iter_mut().enumerate() {
+                    *pressed_at = None;
+                    chip8.keys[key] = false;
+                }
+                self.remap = Some(Remap::new(self.keymap));
+            }
+            KeyCode::Char(ch) => {
+                if let Some(index) = self.keymap.set_key(&mut chip8.keys, ch, pressed) {
+                    self.pressed_at[index] = pressed.then(Instant::now);
+                }
+            }
+            _ => {}
+        }
+
+        HostAction::None
+    }
+}
+
+impl Drop for TerminalSession {
+    fn drop(&mut self) {
+        let mut stdout: Stdout = io::stdout();
+
+        if self.reports_release {
+            let _ = execute!(stdout, PopKeyboardEnhancementFlags);
+        }
+        let _ = execute!(stdout, DisableBracketedPaste, Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+// Text row `row` of the display: pixel rows 2*row and 2*row+1 as half blocks
+pub(crate) fn half_block_line(frame: &DisplayBuffer, row: usize) -> String {
+    frame[2 * row]
+        .iter()
+        .zip(frame[2 * row + 1].iter())
+        .map(|pixels| match pixels {
+            (true, true) => '█',
+            (true, false) => '▀',
+            (false, true) => '▄',
+            (false, false) => ' ',
+        })
+        .collect()
+}
+
+// Text row `row` of the display: pixel rows 4*row..4*row+4, two pixel
+// columns per Braille cell
+pub(crate) fn braille_line(frame: &DisplayBuffer, row: usize) -> String {
+    // Dot bit for each pixel of a cell, indexed [dy][dx]
+    const DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+    (0..DISPLAY_WIDTH / 2)
+        .map(|column| {
+            let mut bits: u32 = 0;
+            for (dy, dots) in DOTS.iter().enumerate() {
+                for (dx, dot) in dots.iter().enumerate() {
+                    if frame[4 * row + dy][2 * column + dx] {
+                        bits |= dot;
+                    }
+                }
+            }
+            char::from_u32(0x2800 + bits).unwrap_or(' ')
+        })
+        .collect()
+}
+
+// 24-bit terminal color for a palette entry
+pub(crate) fn terminal_color(color: Rgb) -> Color {
+    Color::Rgb { r: color.r, g: color.g, b: color.b }
+}
+
+// ===============================================================
+// Display frontend
+// ===============================================================
+
+// How the display is drawn into the terminal
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Graphics {
+    // The best the terminal reports support for: kitty, Sixel, then blocks
+    Auto,
+
+    // Half-block characters, which work everywhere
+    Blocks,
+
+    // Braille patterns, a quarter of the block size; needs a font with Braille
+    Braille,
+
+    // Sixel images
+    Sixel,
+
+    // PNGs sent with the kitty graphics protocol
+    Kitty,
+}
+
+pub struct TerminalFrontend {
+    stdout: Stdout,
+    session: TerminalSession,
+
+    // Never Auto; resolved when the session starts
+    graphics: Graphics,
+    palette: Palette,
+
+    // Set until the first frame and after a resize, when every row has to be drawn
+    stale: bool,
+}
+
+impl TerminalFrontend {
+
+    // Switch to raw mode on the alternate screen; undone when dropped
+    pub fn new(graphics: Graphics, palette: Palette, keymap: Keymap) -> io::Result<Self> {
+        let session: TerminalSession = TerminalSession::start(keymap)?;
+
+        let graphics: Graphics = match graphics {
+            Graphics::Auto => detect_graphics(),
+            other => other,
+        };
+
+        Ok(Self { stdout: io::stdout(), session, graphics, palette, stale: true })
+    }
+
+    fn draw(&mut self, frame: &DisplayBuffer, dirty: DirtyRows) -> io::Result<()> {
+        let dirty: DirtyRows = if std::mem::take(&mut self.stale) { DirtyRows::ALL } else { dirty };
+
+        match self.graphics {
+            Graphics::Sixel => self.draw_image(frame, dirty, sixel::encode),
+            Graphics::Kitty => self.draw_image(frame, dirty, kitty::encode),
+            Graphics::Braille => self.draw_text(frame, dirty, 4, braille_line),
+            _ => self.draw_text(frame, dirty, 2, half_block_line),
+        }
+    }
+
+    // Rewrite only the text rows, `pixel_rows` pixel rows each, covering a
+    // dirty pixel row
+    fn draw_text(
+        &mut self,
+        frame: &DisplayBuffer,
+        dirty: DirtyRows,
+        pixel_rows: usize,
+        line: fn(&DisplayBuffer, usize) -> String,
+    ) -> io::Result<()> {
+        let colors: Colors = Colors::new(terminal_color(self.palette.foreground), terminal_color(self.palette.background));
+
+        for row in 0..DISPLAY_HEIGHT / pixel_rows {
+            let mut pixels: Range<usize> = pixel_rows * row..pixel_rows * (row + 1);
+
+            if !pixels.any(|pixel_row| dirty.contains(pixel_row)) {
+                continue;
+            }
+
+            queue!(self.stdout, MoveTo(0, row as u16), SetColors(colors), Print(line(frame, row)), ResetColor)?;
+        }
+
+        self.stdout.flush()
+    }
+
+    // Send the whole frame as one image, only when it changed
+    fn draw_image(
+        &mut self,
+        frame: &DisplayBuffer,
+        dirty: DirtyRows,
+        encode: fn(&DisplayBuffer, &Palette) -> String,
+    ) -> io::Result<()> {
+        if dirty.is_empty() {
+            return Ok(());
+        }
+
+        queue!(self.stdout, MoveTo(0, 0), Print(encode(frame, &self.palette)))?;
+
+        self.stdout.flush()
+    }
+}
+
+impl Frontend for TerminalFrontend {
+    fn pump_events(&mut self, chip8: &mut Chip8) -> HostAction {
+        let action: HostAction = self.session.pump_events(chip8);
+
+        // Everything has to be drawn again after a resize
+        if self.session.take_resized() {
+            self.stale = true;
+            let _ = execute!(self.stdout, Clear(ClearType::All));
+        }
+
+        action
+    }
+
+    fn take_paste(&mut self) -> Option<String> {
+        self.session.take_paste()
+    }
+
+    // A flashing border rewrites every text row twice per beep
+    fn buzzer_style(&self) -> BuzzerStyle {
+        BuzzerStyle::Corner
+    }
+
+    // Terminals have no tone to hold, so ring the bell once as the sound starts
+    fn beep(&mut self, on: bool) {
+        if on {
+            let _ = execute!(self.stdout, Print('\x07'));
+        }
+    }
+
+    fn present(&mut self, frame: &DisplayBuffer, dirty: DirtyRows) -> Result<(), String> {
+        self.draw(frame, dirty).map_err(|err| err.to_string())
+    }
+}
+
+// ===============================================================
+// Graphics detection
+// ===============================================================
+
+// Ask the terminal what it can draw: a kitty graphics query for a 1x1
+// image, then Primary Device Attributes (CSI c), which every terminal
+// answers. Kitty-capable terminals reply to the first before the second;
+// attribute 4 in the second means Sixel. Needs raw mode so the replies are
+// not echoed, and terminals that do not answer in time get blocks.
+#[cfg(unix)]
+fn detect_graphics() -> Graphics {
+    use std::io::IsTerminal;
+
+    if !io::stdin().is_terminal() {
+        return Graphics::Blocks;
+    }
+
+    let mut stdout: Stdout = io::stdout();
+    let query = write!(stdout, "\x1b_Gi=31,s=1,v=1,a=q,t=d,f=24;AAAA\x1b\\\x1b[c").and_then(|_| stdout.flush());
+    if query.is_err() {
+        return Graphics::Blocks;
+    }
+
+    // The attributes reply looks like ESC [ ? 62 ; 4 ; 22 c
+    let deadline: Instant = Instant::now() + REPLY_TIMEOUT;
+    let mut reply: Vec<u8> = Vec::new();
+    while !(reply.contains(&b'?') && reply.ends_with(b"c")) {
+        match read_byte(deadline.saturating_duration_since(Instant::now())) {
+            Some(byte) => reply.push(byte),
+            None => return Graphics::Blocks,
+        }
+    }
+
+    let text: String = String::from_utf8_lossy(&reply).into_owned();
+    if text.contains("\x1b_Gi=31;OK") {
+        return Graphics::Kitty;
+    }
+
+    let sixel: bool = text
+        .rfind("\x1b[?")
+        .is_some_and(|start| text[start + 3..text.len() - 1].split(';').any(|attribute| attribute == "4"));
+    if sixel { Graphics::Sixel } else { Graphics::Blocks }
+}
+
+#[cfg(not(unix))]
+fn detect_graphics() -> Graphics {
+    Graphics::Blocks
+}
+
+// One byte from stdin, read unbuffered so nothing is held back from
+// crossterm's own input reader, or None after `timeout`
+#[cfg(unix)]
+fn read_byte(timeout: Duration) -> Option<u8> {
+    let mut poll_fd: libc::pollfd = libc::pollfd { fd: libc::STDIN_FILENO, events: libc::POLLIN, revents: 0 };
+    let millis: libc::c_int = timeout.as_millis().min(libc::c_int::MAX as u128) as libc::c_int;
+
+    // SAFETY: poll and read get a valid pollfd and a one-byte buffer
+    unsafe {
+        if libc::poll(&mut poll_fd, 1, millis) <= 0 {
+            return None;
+        }
+
+        let mut byte: u8 = 0;
+        (libc::read(libc::STDIN_FILENO, (&mut byte as *mut u8).cast(), 1) == 1).then_some(byte)
+    }
+}
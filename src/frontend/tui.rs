@@ -0,0 +1,334 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — TUI Frontend
+// Terminal debugger view built on ratatui: the display in one pane
+// and registers, PC, I, stack and timers in side panes, updated
+// live while the ROM runs. Input is shared with the plain terminal
+// frontend. In teaching mode a pane under the display annotates
+// each executed instruction, and the registers and display cells
+// touched during the frame are highlighted. F7 remaps the keypad,
+// one key at a time, in a pane under the display.
+// ───────────────────────────────────────────────────────────────
+
+use std::collections::VecDeque;
+use std::io::{self, Stdout};
+
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::{Frame, Terminal};
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH, NUM_KEYS, NUM_REGISTERS, STACK_SIZE};
+use crate::chip8::cpu::Chip8;
+use crate::chip8::display::{DirtyRows, DisplayBuffer};
+use crate::frontend::terminal::{half_block_line, TerminalSession};
+use crate::frontend::{Frontend, HostAction};
+use crate::keymap::{self, Keymap, Remap};
+use crate::palette::Palette;
+use crate::teaching::{Lesson, Step, Touch};
+
+// Executed instructions kept in the teaching pane
+const HISTORY: usize = 32;
+
+// Highlights for state touched during the last frame
+const WRITTEN: Style = Style::new().fg(Color::Yellow);
+const READ: Style = Style::new().fg(Color::Cyan);
+const DRAWN: Style = Style::new().fg(Color::Yellow).bg(Color::DarkGray);
+const NOTE: Style = Style::new().fg(Color::DarkGray);
+
+// The key waiting for a host key while remapping
+const REMAP_CURRENT: Style = Style::new().fg(Color::Black).bg(Color::Yellow);
+
+// Machine state shown in the side panes, copied once per frame
+struct Registers {
+    v: [u8; NUM_REGISTERS],
+    i: u16,
+    pc: u16,
+    sp: u8,
+    stack: [u16; STACK_SIZE],
+    delay_timer: u8,
+    sound_timer: u8,
+
+    // Disassembly of the instruction at PC
+    next: String,
+}
+
+// Teaching mode state: the recording plus what the panes show of it
+struct Teaching {
+    lesson: Lesson,
+
+    // Most recent instructions, oldest first
+    history: VecDeque<Step>,
+
+    // Instructions executed during the last frame
+    frame: Vec<Step>,
+}
+
+impl Teaching {
+
+    // WRITTEN if any instruction this frame wrote `touch`, READ if one read it
+    fn style_of(&self, touch: Touch) -> Style {
+        if self.frame.iter().any(|step| step.writes.contains(&touch)) {
+            WRITTEN
+        } else if self.frame.iter().any(|step| step.reads.contains(&touch)) {
+            READ
+        } else {
+            Style::new()
+        }
+    }
+}
+
+pub struct TuiFrontend {
+    terminal: Terminal<CrosstermBackend<Stdout>>,
+    registers: Option<Registers>,
+    teaching: Option<Teaching>,
+
+    // Display pane colors from the palette
+    display_style: Style,
+
+    // Declared last so the terminal is restored after ratatui is done with it
+    session: TerminalSession,
+}
+
+impl TuiFrontend {
+
+    // Switch to raw mode on the alternate screen; undone when dropped.
+    // With a lesson attached to the machine, the teaching pane is shown.
+    pub fn new(lesson: Option<Lesson>, palette: Palette, keymap: Keymap) -> io::Result<Self> {
+        let mut session: TerminalSession = TerminalSession::start(keymap)?;
+        session.enable_remap();
+        let terminal: Terminal<CrosstermBackend<Stdout>> = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+        let teaching: Option<Teaching> =
+            lesson.map(|lesson| Teaching { lesson, history: VecDeque::with_capacity(HISTORY), frame: Vec::new() });
+
+        let display_style: Style = Style::new()
+            .fg(Color::Rgb(palette.foreground.r, palette.foreground.g, palette.foreground.b))
+            .bg(Color::Rgb(palette.background.r, palette.background.g, palette.background.b));
+
+        Ok(Self { terminal, registers: None, teaching, display_style, session })
+    }
+}
+
+impl Frontend for TuiFrontend {
+    fn pump_events(&mut self, chip8: &mut Chip8) -> HostAction {
+        let action: HostAction = self.session.pump_events(chip8);
+
+        if self.session.take_resized() {
+            let _ = self.terminal.clear();
+        }
+
+        action
+    }
+
+    fn take_paste(&mut self) -> Option<String> {
+        self.session.take_paste()
+    }
+
+    fn take_keymap(&mut self) -> Option<Keymap> {
+        self.session.take_keymap()
+    }
+
+    fn observe(&mut self, chip8: &Chip8) {
+        self.registers = Some(Registers {
+            v: chip8.v,
+            i: chip8.i,
+            pc: chip8.pc,
+            sp: chip8.sp,
+            stack: chip8.stack,
+            delay_timer: chip8.delay_timer,
+            sound_timer: chip8.sound_timer,
+            next: chip8.opcode_at_pc().map(|opcode| chip8.disassemble(opcode)).unwrap_or_default(),
+        });
+
+        if let Some(teaching) = &mut self.teaching {
+            teaching.frame = teaching.lesson.take_steps();
+
+            let skip: usize = teaching.frame.len().saturating_sub(HISTORY);
+            teaching.history.extend(teaching.frame[skip..].iter().cloned());
+            while teaching.history.len() > HISTORY {
+                teaching.history.pop_front();
+            }
+        }
+    }
+
+    // ratatui diffs against the previous frame, so only changed cells are written
+    fn present(&mut self, frame: &DisplayBuffer, _dirty: DirtyRows) -> Result<(), String> {
+        let registers: Option<&Registers> = self.registers.as_ref();
+        let teaching: Option<&Teaching> = self.teaching.as_ref();
+        let display_style: Style = self.display_style;
+        let remap: Option<&Remap> = self.session.remap();
+
+        self.terminal
+            .draw(|ui| draw(ui, frame, display_style, registers, teaching, remap))
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    }
+}
+
+fn draw(
+    ui: &mut Frame,
+    frame: &DisplayBuffer,
+    display_style: Style,
+    registers: Option<&Registers>,
+    teaching: Option<&Teaching>,
+    remap: Option<&Remap>,
+) {
+    let display_width: Constraint = Constraint::Length(DISPLAY_WIDTH as u16 + 2);
+
+    // The teaching pane takes all the width the side panes leave
+    let [left, right] = Layout::horizontal([
+        if teaching.is_some() { Constraint::Fill(1) } else { display_width },
+        Constraint::Length(26),
+    ])
+    .areas(ui.area());
+
+    let [display_row, teaching_area] = Layout::vertical([
+        Constraint::Length(DISPLAY_HEIGHT as u16 / 2 + 2),
+        Constraint::Fill(1),
+    ])
+    .areas(left);
+    let [display_area, _] = Layout::horizontal([display_width, Constraint::Fill(1)]).areas(display_row);
+
+    let display: Vec<Line> = match teaching {
+        Some(teaching) => display_lines(frame, display_style, teaching),
+        None => (0..DISPLAY_HEIGHT / 2).map(|row| Line::styled(half_block_line(frame, row), display_style)).collect(),
+    };
+    ui.render_widget(Paragraph::new(display).block(Block::bordered().title(" Display ")), display_area);
+
+    if let Some(remap) = remap {
+        draw_remap_pane(ui, teaching_area, remap);
+    } else if let Some(teaching) = teaching {
+        draw_teaching_pane(ui, teaching_area, teaching);
+    }
+
+    if let Some(registers) = registers {
+        draw_side_panes(ui, right, registers, teaching);
+    }
+}
+
+// The display with the cells drawn during the last frame highlighted
+fn display_lines(frame: &DisplayBuffer, display_style: Style, teaching: &Teaching) -> Vec<Line<'static>> {
+    let drawn: Vec<Touch> = teaching
+        .frame
+        .iter()
+        .flat_map(|step| step.writes.iter().copied())
+        .filter(|touch| matches!(touch, Touch::Display { .. }))
+        .collect();
+
+    (0..DISPLAY_HEIGHT / 2)
+        .map(|row| {
+            let spans: Vec<Span> = half_block_line(frame, row)
+                .chars()
+                .enumerate()
+                .map(|(x, cell)| {
+                    let touched: bool = drawn
+                        .iter()
+                        .any(|touch| touch.covers_cell(x, 2 * row) || touch.covers_cell(x, 2 * row + 1));
+                    Span::styled(cell.to_string(), if touched { DRAWN } else { display_style })
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect()
+}
+
+// The latest instructions, each with its description and the state it touched
+fn draw_teaching_pane(ui: &mut Frame, area: Rect, teaching: &Teaching) {
+    let list = |touches: &[Touch]| -> String {
+        if touches.is_empty() {
+            "-".to_string()
+        } else {
+            touches.iter().map(Touch::to_string).collect::<Vec<String>>().join(", ")
+        }
+    };
+
+    let lines: Vec<Line> = teaching
+        .history
+        .iter()
+        .flat_map(|step| {
+            [
+                Line::raw(format!("{:03X}  {:04X}  {:<16} {}", step.pc, step.opcode, step.disassembly, step.description)),
+                Line::styled(format!("           reads {}  writes {}", list(&step.reads), list(&step.writes)), NOTE),
+            ]
+        })
+        .collect();
+
+    // Keep the newest lines when the pane is too short for all of them
+    let visible: usize = area.height.saturating_sub(2) as usize;
+    let lines: Vec<Line> = lines[lines.len().saturating_sub(visible)..].to_vec();
+
+    ui.render_widget(Paragraph::new(lines).block(Block::bordered().title(" Teaching ")), area);
+}
+
+// The keypad with each key's binding, and the one to press a key for highlighted
+fn draw_remap_pane(ui: &mut Frame, area: Rect, remap: &Remap) {
+    let keys: [char; NUM_KEYS] = remap.keymap().keys();
+
+    let mut lines: Vec<Line> = keymap::BLOCK
+        .iter()
+        .map(|row| {
+            let spans: Vec<Span> = row
+                .iter()
+                .map(|&key| {
+                    let style: Style = if key == remap.current() { REMAP_CURRENT } else { Style::new() };
+                    Span::styled(format!(" {:X}: {} ", key, keys[key]), style)
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect();
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(format!("Press the key for {:X} (Esc cancels)", remap.current()), NOTE));
+
+    ui.render_widget(Paragraph::new(lines).block(Block::bordered().title(" Remap keys ")), area);
+}
+
+fn draw_side_panes(ui: &mut Frame, area: Rect, registers: &Registers, teaching: Option<&Teaching>) {
+    let style_of = |touch: Touch| teaching.map_or(Style::new(), |teaching| teaching.style_of(touch));
+
+    let [v_area, cpu_area, stack_area] = Layout::vertical([
+        Constraint::Length(NUM_REGISTERS as u16 / 2 + 2),
+        Constraint::Length(6),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
+
+    // V0..V7 in the left column, V8..VF in the right
+    let half: usize = NUM_REGISTERS / 2;
+    let v_lines: Vec<Line> = (0..half)
+        .map(|index| {
+            let right: usize = index + half;
+            Line::from(vec![
+                Span::styled(format!("V{:X} {:02X}", index, registers.v[index]), style_of(Touch::Register(index as u8))),
+                Span::raw("    "),
+                Span::styled(format!("V{:X} {:02X}", right, registers.v[right]), style_of(Touch::Register(right as u8))),
+            ])
+        })
+        .collect();
+    ui.render_widget(Paragraph::new(v_lines).block(Block::bordered().title(" Registers ")), v_area);
+
+    let cpu_lines: Vec<Line> = vec![
+        Line::from(vec![
+            Span::raw(format!("PC {:03X}   ", registers.pc)),
+            Span::styled(format!("I  {:03X}", registers.i), style_of(Touch::Index)),
+        ]),
+        Line::from(vec![
+            Span::styled(format!("DT {:02X}", registers.delay_timer), style_of(Touch::DelayTimer)),
+            Span::raw("    "),
+            Span::styled(format!("ST {:02X}", registers.sound_timer), style_of(Touch::SoundTimer)),
+        ]),
+        Line::styled(format!("SP {:X}", registers.sp), style_of(Touch::Stack)),
+        Line::raw(format!("-> {}", registers.next)),
+    ];
+    ui.render_widget(Paragraph::new(cpu_lines).block(Block::bordered().title(" CPU ")), cpu_area);
+
+    // Innermost return address first
+    let stack_lines: Vec<Line> = registers.stack[..registers.sp as usize]
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(depth, address)| Line::raw(format!("{:X}: {:03X}", depth, address)))
+        .collect();
+    ui.render_widget(Paragraph::new(stack_lines).block(Block::bordered().title(" Stack ")), stack_area);
+}
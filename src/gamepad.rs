@@ -0,0 +1,158 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Gamepads
+// Game controllers read through gilrs, next to whichever frontend
+// has the keyboard. Each mapped button (and the left stick, which
+// acts as the D-pad) holds one CHIP-8 key. Controllers can be
+// plugged in and out while a ROM runs; every connected one drives
+// the same keys.
+// ───────────────────────────────────────────────────────────────
+
+use std::str::FromStr;
+
+use gilrs::{Axis, Button, EventType, Gilrs};
+
+use crate::chip8::constants::NUM_KEYS;
+use crate::chip8::cpu::Chip8;
+use crate::events::{Event, EventBus};
+
+// How far the left stick has to lean before it counts as a D-pad press
+const STICK_THRESHOLD: f32 = 0.5;
+
+// Buttons that can be mapped, by the name --gamepad-map uses
+const BUTTONS: [(&str, Button); 14] = [
+    ("up", Button::DPadUp),
+    ("down", Button::DPadDown),
+    ("left", Button::DPadLeft),
+    ("right", Button::DPadRight),
+    ("south", Button::South),
+    ("east", Button::East),
+    ("west", Button::West),
+    ("north", Button::North),
+    ("l1", Button::LeftTrigger),
+    ("r1", Button::RightTrigger),
+    ("l2", Button::LeftTrigger2),
+    ("r2", Button::RightTrigger2),
+    ("select", Button::Select),
+    ("start", Button::Start),
+];
+
+// CHIP-8 key held by each button, if any
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GamepadMap {
+    keys: Vec<(Button, u8)>,
+}
+
+// The D-pad on the keys around 5, which most games steer with, and the
+// bottom face button on 5 itself
+impl Default for GamepadMap {
+    fn default() -> Self {
+        Self {
+            keys: vec![
+                (Button::DPadUp, 0x2),
+                (Button::DPadDown, 0x8),
+                (Button::DPadLeft, 0x4),
+                (Button::DPadRight, 0x6),
+                (Button::South, 0x5),
+            ],
+        }
+    }
+}
+
+// Parses "BUTTON=KEY,...", e.g. "up=1,down=4,south=5"; buttons not named
+// keep their default key
+impl FromStr for GamepadMap {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut map: GamepadMap = GamepadMap::default();
+
+        for entry in text.split(',').filter(|entry| !entry.is_empty()) {
+            let (name, key) = entry.split_once('=').ok_or_else(|| format!("expected BUTTON=KEY, got '{}'", entry))?;
+
+            let button: Button = BUTTONS
+                .iter()
+                .find(|(known, _)| known.eq_ignore_ascii_case(name.trim()))
+                .map(|&(_, button)| button)
+                .ok_or_else(|| {
+                    let names: Vec<&str> = BUTTONS.iter().map(|&(known, _)| known).collect();
+                    format!("unknown button '{}' (expected one of {})", name, names.join(", "))
+                })?;
+
+            let key: u8 = u8::from_str_radix(key.trim(), 16)
+                .ok()
+                .filter(|&key| (key as usize) < NUM_KEYS)
+                .ok_or_else(|| format!("expected a CHIP-8 key 0-F for {}, got '{}'", name, key))?;
+
+            map.keys.retain(|&(mapped, _)| mapped != button);
+            map.keys.push((button, key));
+        }
+
+        Ok(map)
+    }
+}
+
+pub struct Gamepads {
+    gilrs: Gilrs,
+    map: GamepadMap,
+
+    // Keys held through a controller at the last poll
+    held: [bool; NUM_KEYS],
+}
+
+impl Gamepads {
+
+    // Start watching for controllers; fails where gilrs has no backend
+    pub fn new(map: GamepadMap) -> Result<Self, String> {
+        let gilrs: Gilrs = Gilrs::new().map_err(|err| err.to_string())?;
+        Ok(Self { gilrs, map, held: [false; NUM_KEYS] })
+    }
+
+    // Controllers already plugged in, for reporting at startup
+    pub fn connected(&self) -> Vec<String> {
+        self.gilrs.gamepads().map(|(_, gamepad)| gamepad.name().to_string()).collect()
+    }
+
+    // Read controller state into the keypad. Called after the frontend's
+    // pump, so keys are released only when the controller lets go of them
+    // and frontends that rewrite the whole keypad don't drop held buttons.
+    pub fn poll(&mut self, chip8: &mut Chip8, events: &mut EventBus) {
+        while let Some(event) = self.gilrs.next_event() {
+            let connected: bool = match event.event {
+                EventType::Connected => true,
+                EventType::Disconnected => false,
+                _ => continue,
+            };
+
+            let name: String = self.gilrs.gamepad(event.id).name().to_string();
+            events.publish(Event::Gamepad { name, connected });
+        }
+
+        let mut held: [bool; NUM_KEYS] = [false; NUM_KEYS];
+        for (_, gamepad) in self.gilrs.gamepads() {
+            let x: f32 = gamepad.value(Axis::LeftStickX);
+            let y: f32 = gamepad.value(Axis::LeftStickY);
+            let stick = |button: Button| match button {
+                Button::DPadUp => y >= STICK_THRESHOLD,
+                Button::DPadDown => y <= -STICK_THRESHOLD,
+                Button::DPadLeft => x <= -STICK_THRESHOLD,
+                Button::DPadRight => x >= STICK_THRESHOLD,
+                _ => false,
+            };
+
+            for &(button, key) in &self.map.keys {
+                if gamepad.is_pressed(button) || stick(button) {
+                    held[key as usize] = true;
+                }
+            }
+        }
+
+        for (key, (&now, was)) in held.iter().zip(&self.held).enumerate() {
+            if now {
+                chip8.keys[key] = true;
+            } else if *was {
+                chip8.keys[key] = false;
+            }
+        }
+        self.held = held;
+    }
+}
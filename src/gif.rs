@@ -0,0 +1,210 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — GIF Clips
+// Records the display stream as a looping animated GIF in Octo's
+// default palette, the format CHIP-8 clips are usually shared in.
+// A GIF frame is only written when the display changes; its delay
+// covers every 60Hz frame it stayed on screen. The LZW encoder is
+// hand-rolled like the PNG writer in `screenshot`.
+// ───────────────────────────────────────────────────────────────
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH, TIMER_HZ};
+use crate::chip8::display::DisplayBuffer;
+
+// Octo's default background, fill, fill2 and blend colors. Only the first
+// two are used without XO-CHIP planes.
+const OCTO_PALETTE: [[u8; 3]; 4] = [
+    [0x99, 0x66, 0x00],
+    [0xFF, 0xCC, 0x00],
+    [0xFF, 0x66, 0x00],
+    [0x66, 0x22, 0x00],
+];
+
+// Palette indices need two bits, the smallest LZW code size GIF allows
+const MIN_CODE_SIZE: u8 = 2;
+
+// LZW codes are at most 12 bits wide
+const MAX_CODES: u16 = 4096;
+
+// Largest data sub-block
+const MAX_SUB_BLOCK: usize = 255;
+
+pub struct GifRecorder<W: Write> {
+    output: W,
+    scale: usize,
+
+    // Frame on screen and how many 60Hz frames it has been shown for
+    pending: Option<(DisplayBuffer, u64)>,
+
+    // 60Hz frames and centiseconds already written, so rounding never drifts
+    frames_written: u64,
+    centis_written: u64,
+}
+
+impl<W: Write> GifRecorder<W> {
+
+    // Write the GIF header for frames `scale` times the CHIP-8 resolution
+    pub fn new(mut output: W, scale: usize) -> io::Result<Self> {
+        let (width, height) = ((DISPLAY_WIDTH * scale) as u16, (DISPLAY_HEIGHT * scale) as u16);
+
+        output.write_all(b"GIF89a")?;
+        output.write_all(&width.to_le_bytes())?;
+        output.write_all(&height.to_le_bytes())?;
+
+        // Global color table of 4 entries, background color 0, square pixels
+        output.write_all(&[0x81, 0, 0])?;
+        for color in OCTO_PALETTE {
+            output.write_all(&color)?;
+        }
+
+        // Netscape extension: loop forever
+        output.write_all(&[0x21, 0xFF, 0x0B])?;
+        output.write_all(b"NETSCAPE2.0")?;
+        output.write_all(&[0x03, 0x01, 0x00, 0x00, 0x00])?;
+
+        Ok(Self { output, scale, pending: None, frames_written: 0, centis_written: 0 })
+    }
+
+    // Call once per 60Hz frame with the latched display
+    pub fn observe_frame(&mut self, frame: &DisplayBuffer) -> io::Result<()> {
+        match &mut self.pending {
+            Some((shown, frames)) if shown == frame => {
+                *frames += 1;
+                Ok(())
+            }
+            _ => {
+                self.write_pending()?;
+                self.pending = Some((*frame, 1));
+                Ok(())
+            }
+        }
+    }
+
+    // Write the last frame and the trailer
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_pending()?;
+        self.output.write_all(&[0x3B])?;
+        self.output.flush()?;
+        Ok(self.output)
+    }
+
+    fn write_pending(&mut self) -> io::Result<()> {
+        let Some((frame, frames)) = self.pending.take() else {
+            return Ok(());
+        };
+
+        self.frames_written += frames;
+        let centis: u64 = (self.frames_written * 100 / TIMER_HZ).max(self.centis_written + 1);
+        let delay: u16 = (centis - self.centis_written).min(u16::MAX as u64) as u16;
+        self.centis_written = centis;
+
+        // Graphic control extension: no disposal, the delay, no transparency
+        self.output.write_all(&[0x21, 0xF9, 0x04, 0x00])?;
+        self.output.write_all(&delay.to_le_bytes())?;
+        self.output.write_all(&[0x00, 0x00])?;
+
+        // Image descriptor covering the whole screen, no local color table
+        let (width, height) = (DISPLAY_WIDTH * self.scale, DISPLAY_HEIGHT * self.scale);
+        self.output.write_all(&[0x2C, 0, 0, 0, 0])?;
+        self.output.write_all(&(width as u16).to_le_bytes())?;
+        self.output.write_all(&(height as u16).to_le_bytes())?;
+        self.output.write_all(&[0x00])?;
+
+        let mut indices: Vec<u8> = Vec::with_capacity(width * height);
+        for y in 0..height {
+            indices.extend((0..width).map(|x| frame[y / self.scale][x / self.scale] as u8));
+        }
+
+        self.output.write_all(&[MIN_CODE_SIZE])?;
+        for block in lzw_encode(&indices).chunks(MAX_SUB_BLOCK) {
+            self.output.write_all(&[block.len() as u8])?;
+            self.output.write_all(block)?;
+        }
+        self.output.write_all(&[0x00])
+    }
+}
+
+// ===============================================================
+// LZW encoding
+// ===============================================================
+
+// GIF-flavoured LZW: variable-width codes packed least significant bit first
+fn lzw_encode(indices: &[u8]) -> Vec<u8> {
+    let clear: u16 = 1 << MIN_CODE_SIZE;
+    let end: u16 = clear + 1;
+
+    let mut packer: BitPacker = BitPacker::default();
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next: u16 = end + 1;
+    let mut code_size: u8 = MIN_CODE_SIZE + 1;
+
+    packer.push(clear, code_size);
+
+    let Some((&first, rest)) = indices.split_first() else {
+        packer.push(end, code_size);
+        return packer.finish();
+    };
+
+    let mut prefix: u16 = first as u16;
+    for &index in rest {
+        if let Some(&code) = table.get(&(prefix, index)) {
+            prefix = code;
+            continue;
+        }
+
+        packer.push(prefix, code_size);
+
+        // The decoder widens its codes one entry behind the encoder
+        if next >= 1 << code_size && code_size < 12 {
+            code_size += 1;
+        }
+
+        if next < MAX_CODES {
+            table.insert((prefix, index), next);
+            next += 1;
+        } else {
+            packer.push(clear, code_size);
+            table.clear();
+            next = end + 1;
+            code_size = MIN_CODE_SIZE + 1;
+        }
+
+        prefix = index as u16;
+    }
+
+    packer.push(prefix, code_size);
+    if next >= 1 << code_size && code_size < 12 {
+        code_size += 1;
+    }
+    packer.push(end, code_size);
+    packer.finish()
+}
+
+#[derive(Default)]
+struct BitPacker {
+    bytes: Vec<u8>,
+    bits: u32,
+    count: u8,
+}
+
+impl BitPacker {
+    fn push(&mut self, code: u16, width: u8) {
+        self.bits |= (code as u32) << self.count;
+        self.count += width;
+
+        while self.count >= 8 {
+            self.bytes.push(self.bits as u8);
+            self.bits >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.bits as u8);
+        }
+        self.bytes
+    }
+}
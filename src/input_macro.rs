@@ -0,0 +1,249 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Input Macros
+// Records keypad changes frame by frame and plays them back, so a
+// repetitive sequence (the keys that get a game past its title
+// screen) can be replayed on ROM load or from a hotkey. Pasted
+// text plays the same way, one key press per hex digit.
+//
+// File format: one "<frame> <keys>" line per change, where <frame>
+// counts 60Hz frames from the start of the macro and <keys> is the
+// keypad as a 4-digit hex mask (bit N = key N held). Blank lines
+// and lines starting with '#' are ignored.
+// ───────────────────────────────────────────────────────────────
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use crate::chip8::constants::NUM_KEYS;
+use crate::chip8::error::Chip8Error;
+
+// Frames each typed key is held down, then released before the next
+pub const KEYSTROKE_HOLD_FRAMES: u64 = 4;
+pub const KEYSTROKE_GAP_FRAMES: u64 = 4;
+
+#[derive(Clone, Default)]
+pub struct InputMacro {
+    // Keypad mask from each frame on, in frame order
+    steps: Vec<(u64, u16)>,
+}
+
+impl InputMacro {
+
+    pub fn load(path: &Path) -> Result<Self, Chip8Error> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Chip8Error> {
+        fs::write(path, self.to_text())?;
+        Ok(())
+    }
+
+    pub fn parse(text: &str) -> Result<Self, Chip8Error> {
+        let mut steps: Vec<(u64, u16)> = Vec::new();
+
+        for (index, raw_line) in text.lines().enumerate() {
+            let invalid = |reason: &'static str| Chip8Error::InvalidMacro { line: index + 1, reason };
+
+            let line: &str = raw_line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (frame, keys) = line.split_once(char::is_whitespace).ok_or_else(|| invalid("expected FRAME KEYS"))?;
+            let frame: u64 = frame.parse().map_err(|_| invalid("frame is not a number"))?;
+            let keys: u16 = u16::from_str_radix(keys.trim(), 16).map_err(|_| invalid("keys are not a hex mask"))?;
+
+            if steps.last().is_some_and(|&(last, _)| frame < last) {
+                return Err(invalid("frames out of order"));
+            }
+            steps.push((frame, keys));
+        }
+
+        Ok(Self { steps })
+    }
+
+    // Typing `text` on the keypad: each hex digit is pressed and released in
+    // turn. Anything else (spaces, dashes, newlines) is skipped.
+    pub fn from_keystrokes(text: &str) -> Self {
+        let keys = text.chars().filter_map(|ch| ch.to_digit(16));
+        let mut steps: Vec<(u64, u16)> = Vec::new();
+
+        for (index, key) in keys.enumerate() {
+            let frame: u64 = index as u64 * (KEYSTROKE_HOLD_FRAMES + KEYSTROKE_GAP_FRAMES);
+            steps.push((frame, 1 << key));
+            steps.push((frame + KEYSTROKE_HOLD_FRAMES, 0));
+        }
+
+        Self { steps }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text: String = String::new();
+
+        for &(frame, keys) in &self.steps {
+            let _ = writeln!(text, "{} {:04X}", frame, keys);
+        }
+
+        text
+    }
+}
+
+// ===============================================================
+// Recording
+// ===============================================================
+
+#[derive(Default)]
+pub struct MacroRecorder {
+    recorded: InputMacro,
+    frame: u64,
+    last_keys: u16,
+}
+
+impl MacroRecorder {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Call once per 60Hz frame with the keypad as the ROM sees it
+    pub fn observe_frame(&mut self, keys: &[bool; NUM_KEYS]) {
+        let mask: u16 = key_mask(keys);
+
+        if mask != self.last_keys {
+            self.recorded.steps.push((self.frame, mask));
+            self.last_keys = mask;
+        }
+        self.frame += 1;
+    }
+
+    // The recording so far, ending with every key released
+    pub fn finish(&self) -> InputMacro {
+        let mut recorded: InputMacro = self.recorded.clone();
+        if self.last_keys != 0 {
+            recorded.steps.push((self.frame, 0));
+        }
+        recorded
+    }
+}
+
+// ===============================================================
+// Playback
+// ===============================================================
+
+pub struct MacroPlayer {
+    input: InputMacro,
+
+    // Frames into the current playback, or None while not playing
+    frame: Option<u64>,
+
+    // Index of the next step to apply
+    next: usize,
+
+    // Keypad mask of the last applied step
+    keys: u16,
+}
+
+impl MacroPlayer {
+
+    pub fn new(input: InputMacro) -> Self {
+        Self { input, frame: None, next: 0, keys: 0 }
+    }
+
+    // (Re)start playback from the first step
+    pub fn start(&mut self) {
+        self.frame = Some(0);
+        self.next = 0;
+        self.keys = 0;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.frame.is_some()
+    }
+
+    // Call once per 60Hz frame, after live input has been applied: while
+    // playing, the macro's keypad state replaces it
+    pub fn apply_frame(&mut self, keys: &mut [bool; NUM_KEYS]) {
+        let Some(frame) = self.frame else {
+            return;
+        };
+
+        while let Some(&(at, mask)) = self.input.steps.get(self.next)
+            && at <= frame
+        {
+            self.keys = mask;
+            self.next += 1;
+        }
+
+        for (key, held) in keys.iter_mut().enumerate() {
+            *held = self.keys & (1 << key) != 0;
+        }
+
+        self.frame = (self.next < self.input.steps.len()).then_some(frame + 1);
+    }
+}
+
+fn key_mask(keys: &[bool; NUM_KEYS]) -> u16 {
+    keys.iter().enumerate().filter(|&(_, &held)| held).fold(0, |mask, (key, _)| mask | (1 << key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Keypad masks a player applies over `frames` frames
+    fn play(input: InputMacro, frames: u64) -> Vec<u16> {
+        let mut player: MacroPlayer = MacroPlayer::new(input);
+        player.start();
+
+        let mut keys: [bool; NUM_KEYS] = [false; NUM_KEYS];
+        (0..frames)
+            .map(|_| {
+                player.apply_frame(&mut keys);
+                key_mask(&keys)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn out_of_order_frames_are_an_error() {
+        let parsed = InputMacro::parse("# title skip\n0 0010\n5 0000\n3 0020\n");
+        assert!(matches!(parsed, Err(Chip8Error::InvalidMacro { line: 4, .. })));
+    }
+
+    #[test]
+    fn recording_plays_back_as_recorded() {
+        let mut recorder: MacroRecorder = MacroRecorder::new();
+        let mut keys: [bool; NUM_KEYS] = [false; NUM_KEYS];
+        let mut masks: Vec<u16> = Vec::new();
+        for frame in 0..6 {
+            keys[5] = (1..3).contains(&frame);
+            keys[0xA] = frame >= 4;
+            masks.push(key_mask(&keys));
+            recorder.observe_frame(&keys);
+        }
+
+        // Keys still held when recording stops are released
+        let recorded: InputMacro = recorder.finish();
+        assert_eq!(recorded.to_text(), "1 0020\n3 0000\n4 0400\n6 0000\n");
+        masks.push(0);
+
+        let reloaded: InputMacro = InputMacro::parse(&recorded.to_text()).unwrap();
+        assert_eq!(play(reloaded, 7), masks);
+    }
+
+    #[test]
+    fn keystrokes_are_held_then_released_in_turn() {
+        let hold: usize = KEYSTROKE_HOLD_FRAMES as usize;
+        let gap: usize = KEYSTROKE_GAP_FRAMES as usize;
+
+        let mut expected: Vec<u16> = Vec::new();
+        for key in [0x2, 0xA] {
+            expected.extend([1 << key].repeat(hold));
+            expected.extend([0].repeat(gap));
+        }
+        expected.truncate(expected.len() - gap + 1);
+
+        assert_eq!(play(InputMacro::from_keystrokes("2-a"), expected.len() as u64), expected);
+    }
+}
@@ -0,0 +1,229 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Keyboard Layouts
+// The keypad sits on the 4x4 block of host keys under 1 to 4. A
+// layout names the characters those keys type on a given keyboard,
+// for frontends that read characters. Frontends that read key
+// positions (SDL scancodes, winit physical keys) find the same
+// block on any keyboard and don't need one. A keymap replaces the
+// block with any host keys, and can be remapped while running.
+// ───────────────────────────────────────────────────────────────
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use crate::chip8::constants::NUM_KEYS;
+use crate::chip8::error::Chip8Error;
+
+// CHIP-8 key on each key of the block, row by row, as on the COSMAC VIP
+pub const BLOCK: [[usize; 4]; 4] = [
+    [0x1, 0x2, 0x3, 0xC],
+    [0x4, 0x5, 0x6, 0xD],
+    [0x7, 0x8, 0x9, 0xE],
+    [0xA, 0x0, 0xB, 0xF],
+];
+
+// Named keyboard layouts selectable from the command line
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum KeyLayout {
+    // US and most of the English-speaking world
+    #[default]
+    Qwerty,
+
+    // German and central European
+    Qwertz,
+
+    // French and Belgian
+    Azerty,
+
+    Dvorak,
+
+    Colemak,
+}
+
+impl KeyLayout {
+    pub const ALL: [KeyLayout; 5] =
+        [KeyLayout::Qwerty, KeyLayout::Qwertz, KeyLayout::Azerty, KeyLayout::Dvorak, KeyLayout::Colemak];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyLayout::Qwerty => "QWERTY",
+            KeyLayout::Qwertz => "QWERTZ",
+            KeyLayout::Azerty => "AZERTY",
+            KeyLayout::Dvorak => "Dvorak",
+            KeyLayout::Colemak => "Colemak",
+        }
+    }
+
+    // Unshifted characters typed by the block's keys, row by row
+    fn block(self) -> [[char; 4]; 4] {
+        match self {
+            KeyLayout::Qwerty => [['1', '2', '3', '4'], ['q', 'w', 'e', 'r'], ['a', 's', 'd', 'f'], ['z', 'x', 'c', 'v']],
+            KeyLayout::Qwertz => [['1', '2', '3', '4'], ['q', 'w', 'e', 'r'], ['a', 's', 'd', 'f'], ['y', 'x', 'c', 'v']],
+            KeyLayout::Azerty => [['&', 'é', '"', '\''], ['a', 'z', 'e', 'r'], ['q', 's', 'd', 'f'], ['w', 'x', 'c', 'v']],
+            KeyLayout::Dvorak => [['1', '2', '3', '4'], ['\'', ',', '.', 'p'], ['a', 'o', 'e', 'u'], [';', 'q', 'j', 'k']],
+            KeyLayout::Colemak => [['1', '2', '3', '4'], ['q', 'w', 'f', 'p'], ['a', 'r', 's', 't'], ['z', 'x', 'c', 'v']],
+        }
+    }
+
+    // Keypad on the block of keys under 1 to 4
+    pub fn keymap(self) -> Keymap {
+        let mut keys: [char; NUM_KEYS] = [' '; NUM_KEYS];
+        for (pad_row, block_row) in BLOCK.iter().zip(self.block()) {
+            for (&key, ch) in pad_row.iter().zip(block_row) {
+                keys[key] = ch;
+            }
+        }
+
+        Keymap { keys }
+    }
+}
+
+// Host key for each CHIP-8 key, named by the (lowercase) character it
+// types. Frontends that read key positions take each name as the key
+// in that position on a US keyboard.
+//
+// File format (--keymap): one "<key> = <char>" line per CHIP-8 key,
+// the key as one hex digit. Keys not listed keep their QWERTY binding.
+// Blank lines and lines starting with '#' are ignored.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Keymap {
+    keys: [char; NUM_KEYS],
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        KeyLayout::Qwerty.keymap()
+    }
+}
+
+impl Keymap {
+
+    pub fn load(path: &Path) -> Result<Self, Chip8Error> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Chip8Error> {
+        fs::write(path, self.to_text())?;
+        Ok(())
+    }
+
+    pub fn parse(text: &str) -> Result<Self, Chip8Error> {
+        let mut keymap: Keymap = Keymap::default();
+
+        for (index, raw_line) in text.lines().enumerate() {
+            let invalid = |reason: &'static str| Chip8Error::InvalidKeymap { line: index + 1, reason };
+
+            let line: &str = raw_line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, host) = line.split_once('=').ok_or_else(|| invalid("expected KEY = CHAR"))?;
+            let key: usize = usize::from_str_radix(key.trim(), 16)
+                .ok()
+                .filter(|&key| key < NUM_KEYS)
+                .ok_or_else(|| invalid("key is not a hex digit"))?;
+
+            let mut chars = host.trim().chars();
+            let (Some(ch), None) = (chars.next(), chars.next()) else {
+                return Err(invalid("expected a single character"));
+            };
+            keymap.bind(key, ch);
+        }
+
+        Ok(keymap)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text: String = String::from("# CHIP-8 key = host key\n");
+        for (key, ch) in self.keys.iter().enumerate() {
+            let _ = writeln!(text, "{:X} = {}", key, ch);
+        }
+
+        text
+    }
+
+    // Host character for each CHIP-8 key 0x0..0xF
+    pub fn keys(&self) -> [char; NUM_KEYS] {
+        self.keys
+    }
+
+    // CHIP-8 key typed as `ch`, ignoring case
+    pub fn key_for(&self, ch: char) -> Option<usize> {
+        let ch: char = ch.to_lowercase().next().unwrap_or(ch);
+        self.keys.iter().position(|&mapped| mapped == ch)
+    }
+
+    // Press or release the CHIP-8 key typed as `ch` in `keys`, returning
+    // that key, or None if `ch` is not on the keypad
+    pub fn set_key(&self, keys: &mut [bool; NUM_KEYS], ch: char, pressed: bool) -> Option<usize> {
+        let key: usize = self.key_for(ch)?;
+        keys[key] = pressed;
+        Some(key)
+    }
+
+    // Bind `ch` to `key`. A CHIP-8 key that already had `ch` takes over
+    // `key`'s old binding, so no two keys share a host key.
+    pub fn bind(&mut self, key: usize, ch: char) {
+        let ch: char = ch.to_lowercase().next().unwrap_or(ch);
+        if let Some(previous) = self.key_for(ch) {
+            self.keys[previous] = self.keys[key];
+        }
+        self.keys[key] = ch;
+    }
+}
+
+// Interactive remapping: the CHIP-8 keys in keypad order, each bound
+// in turn to the next host key pressed
+pub struct Remap {
+    keymap: Keymap,
+
+    // Position on the keypad of the key waiting to be bound
+    position: usize,
+}
+
+impl Remap {
+
+    pub fn new(keymap: Keymap) -> Self {
+        Self { keymap, position: 0 }
+    }
+
+    // CHIP-8 key waiting for a host key
+    pub fn current(&self) -> usize {
+        BLOCK[self.position / 4][self.position % 4]
+    }
+
+    // Bindings so far; keys not reached yet keep their old ones
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    // Bind the current key to `ch` and move on; the new keymap once every
+    // key has been bound
+    pub fn press(&mut self, ch: char) -> Option<Keymap> {
+        self.keymap.bind(self.current(), ch);
+        self.position += 1;
+
+        (self.position == NUM_KEYS).then_some(self.keymap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_presses_and_releases_its_keypad_key() {
+        let keymap: Keymap = Keymap::default();
+        let mut keys: [bool; NUM_KEYS] = [false; NUM_KEYS];
+
+        assert_eq!(keymap.set_key(&mut keys, 'V', true), Some(0xF));
+        assert_eq!(keymap.set_key(&mut keys, 'x', true), Some(0x0));
+        assert_eq!(keymap.set_key(&mut keys, 'v', false), Some(0xF));
+        assert_eq!(keymap.set_key(&mut keys, 'p', true), None);
+
+        let held: Vec<usize> = (0..NUM_KEYS).filter(|&key| keys[key]).collect();
+        assert_eq!(held, [0x0]);
+    }
+}
@@ -0,0 +1,69 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Keypad Overlay
+// The 4x4 hex keypad in the COSMAC VIP layout, drawn into the right
+// edge of the shown frame from `chip8.keys` every frame. Held keys
+// are drawn inverted, so a key mapping can be checked while a game
+// runs. F4 toggles it.
+// ───────────────────────────────────────────────────────────────
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH, NUM_KEYS};
+use crate::chip8::display::DisplayBuffer;
+use crate::overlay_font;
+
+// Keys as they sit on the pad, row by row
+const LAYOUT: [[u8; 4]; 4] = [
+    [0x1, 0x2, 0x3, 0xC],
+    [0x4, 0x5, 0x6, 0xD],
+    [0x7, 0x8, 0x9, 0xE],
+    [0xA, 0x0, 0xB, 0xF],
+];
+
+// Distance between key cells; each cell is one pixel smaller to leave a gap
+const CELL_WIDTH: usize = 6;
+const CELL_HEIGHT: usize = 8;
+
+const PANEL_WIDTH: usize = 4 * CELL_WIDTH + 1;
+const PANEL_HEIGHT: usize = 4 * CELL_HEIGHT;
+
+pub struct KeypadOverlay {
+    visible: bool,
+}
+
+impl KeypadOverlay {
+
+    pub fn new(visible: bool) -> Self {
+        Self { visible }
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    // `frame` with the keypad on top, or None while it is hidden
+    pub fn overlay(&self, frame: &DisplayBuffer, keys: &[bool; NUM_KEYS]) -> Option<DisplayBuffer> {
+        if !self.visible {
+            return None;
+        }
+
+        let left: usize = DISPLAY_WIDTH - PANEL_WIDTH;
+        let top: usize = DISPLAY_HEIGHT - PANEL_HEIGHT;
+
+        let mut shown: DisplayBuffer = *frame;
+        overlay_font::fill_box(&mut shown, left, top, PANEL_WIDTH, PANEL_HEIGHT, false);
+
+        for (row, row_keys) in LAYOUT.iter().enumerate() {
+            for (column, &key) in row_keys.iter().enumerate() {
+                let x: usize = left + 1 + column * CELL_WIDTH;
+                let y: usize = top + 1 + row * CELL_HEIGHT;
+                let held: bool = keys[key as usize];
+
+                if held {
+                    overlay_font::fill_box(&mut shown, x, y, CELL_WIDTH - 1, CELL_HEIGHT - 1, true);
+                }
+                overlay_font::draw_text(&mut shown, x + 1, y + 1, &format!("{:X}", key), !held);
+            }
+        }
+
+        Some(shown)
+    }
+}
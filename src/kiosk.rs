@@ -0,0 +1,133 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Kiosk Mode
+// Cycles through a playlist of ROMs for unattended displays. Each
+// ROM runs for a fixed time slot (or until it halts), then a wipe
+// transition covers the screen while the next one is loaded.
+// ───────────────────────────────────────────────────────────────
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH, TIMER_HZ};
+use crate::chip8::cpu::Chip8;
+use crate::chip8::display::DisplayBuffer;
+
+// Frames for the wipe to cover the screen (and again to uncover it)
+const WIPE_FRAMES: usize = DISPLAY_HEIGHT / 2;
+
+// A halted ROM stays on screen this long before the next one starts
+const HALT_LINGER: Duration = Duration::from_secs(3);
+
+enum Phase {
+    // Running the current ROM; `halted_frames` counts frames since it halted
+    Playing { frames: u64, halted_frames: u64 },
+
+    // Lit rows wiping down over the last frame of the outgoing ROM
+    Closing { step: usize, from: Box<DisplayBuffer> },
+
+    // Lit rows retreating from the top to reveal the incoming ROM
+    Opening { step: usize },
+}
+
+pub struct Kiosk {
+    roms: Vec<PathBuf>,
+    current: usize,
+
+    // Frames each ROM gets before the playlist moves on
+    slot_frames: u64,
+
+    phase: Phase,
+}
+
+impl Kiosk {
+
+    // Start at the first of `roms`, which must not be empty
+    pub fn new(roms: Vec<PathBuf>, slot: Duration) -> Self {
+        assert!(!roms.is_empty(), "kiosk playlist is empty");
+
+        Self {
+            roms,
+            current: 0,
+            slot_frames: (slot.as_secs_f64() * TIMER_HZ as f64).ceil() as u64,
+            phase: Phase::Playing { frames: 0, halted_frames: 0 },
+        }
+    }
+
+    pub fn current_rom(&self) -> &Path {
+        &self.roms[self.current]
+    }
+
+    // The machine is paused while a transition plays
+    pub fn in_transition(&self) -> bool {
+        !matches!(self.phase, Phase::Playing { .. })
+    }
+
+    // Call once per 60Hz frame. Returns the next ROM once the screen is
+    // fully covered; the caller loads it while the wipe uncovers again.
+    pub fn observe_frame(&mut self, chip8: &Chip8) -> Option<PathBuf> {
+        let linger_frames: u64 = HALT_LINGER.as_secs() * TIMER_HZ;
+
+        match &mut self.phase {
+            Phase::Playing { frames, halted_frames } => {
+                *frames += 1;
+                *halted_frames = if chip8.is_halted() { *halted_frames + 1 } else { 0 };
+
+                if *frames >= self.slot_frames || *halted_frames >= linger_frames {
+                    self.phase = Phase::Closing { step: 0, from: Box::new(*chip8.frame()) };
+                }
+                None
+            }
+
+            Phase::Closing { step, .. } => {
+                *step += 1;
+                if *step < WIPE_FRAMES {
+                    return None;
+                }
+
+                self.current = (self.current + 1) % self.roms.len();
+                self.phase = Phase::Opening { step: 0 };
+                Some(self.roms[self.current].clone())
+            }
+
+            Phase::Opening { step } => {
+                *step += 1;
+                if *step >= WIPE_FRAMES {
+                    self.phase = Phase::Playing { frames: 0, halted_frames: 0 };
+                }
+                None
+            }
+        }
+    }
+
+    // What to show instead of `frame` while a transition plays
+    pub fn overlay(&self, frame: &DisplayBuffer) -> Option<DisplayBuffer> {
+        let (base, lit) = match &self.phase {
+            Phase::Playing { .. } => return None,
+            Phase::Closing { step, from } => (&**from, 0..(step + 1) * DISPLAY_HEIGHT / WIPE_FRAMES),
+            Phase::Opening { step } => (frame, (step + 1) * DISPLAY_HEIGHT / WIPE_FRAMES..DISPLAY_HEIGHT),
+        };
+
+        let mut shown: DisplayBuffer = *base;
+        for row in &mut shown[lit] {
+            *row = [true; DISPLAY_WIDTH];
+        }
+        Some(shown)
+    }
+}
+
+// Read a playlist: one ROM path per line, relative to the playlist's own
+// directory. Blank lines and lines starting with '#' are ignored.
+pub fn read_playlist(path: &Path) -> io::Result<Vec<PathBuf>> {
+    let base: &Path = path.parent().unwrap_or(Path::new(""));
+
+    let roms: Vec<PathBuf> = fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| base.join(line))
+        .collect();
+
+    Ok(roms)
+}
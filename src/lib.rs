@@ -0,0 +1,34 @@
+// ───────────────────────────────────────────────────────────────
+//  CHIP-8 Emulator — Rust
+//  Library root: exposes the emulator core to the binary and embedders.
+// ───────────────────────────────────────────────────────────────
+
+pub mod chip8;
+pub mod auto_speed;
+pub mod batch;
+pub mod capabilities;
+pub mod compat;
+pub mod conformance;
+pub mod diff;
+pub mod events;
+pub mod frontend;
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
+pub mod gif;
+pub mod input_macro;
+pub mod keymap;
+pub mod keypad_overlay;
+pub mod kiosk;
+pub mod osc;
+pub mod overlay_font;
+pub mod palette;
+pub mod perf_overlay;
+pub mod rom_settings;
+pub mod scheduler;
+pub mod screenshot;
+pub mod session;
+pub mod status_screen;
+pub mod stress;
+pub mod teaching;
+pub mod visual_buzzer;
+pub mod watchdog;
@@ -1,41 +1,1218 @@
-mod chip8;
+mod cli;
 
-use std::time::{Duration, Instant};
-use std::thread;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
-use chip8::cpu::Chip8;
+use chip8_rs::chip8::audit::{self, Divergence};
+use chip8_rs::chip8::banking::{Banking, MAX_BANKED_ROM};
+use chip8_rs::chip8::constants::{CPU_HZ, MEMORY_SIZE, PROGRAM_START, TIMER_HZ};
+use chip8_rs::chip8::cpu::Chip8;
+use chip8_rs::chip8::display::{DirtyRows, DisplayBuffer};
+use chip8_rs::chip8::error::Chip8Error;
+use chip8_rs::chip8::memory_image;
+use chip8_rs::chip8::ports;
+use chip8_rs::chip8::serial::{self, SerialLink};
+use chip8_rs::chip8::sram::Sram;
+use chip8_rs::chip8::instruction::{InstructionSpec, INSTRUCTION_FORMS};
+use chip8_rs::chip8::state_dump;
+use chip8_rs::auto_speed::SpeedTuner;
+use chip8_rs::batch;
+use chip8_rs::capabilities::Capabilities;
+use chip8_rs::compat::{self, CompatResult, Outcome};
+use chip8_rs::conformance;
+use chip8_rs::diff;
+use chip8_rs::events::{Event, EventBus};
+#[cfg(feature = "wgpu")]
+use chip8_rs::frontend::gpu::GpuFrontend;
+#[cfg(feature = "gui")]
+use chip8_rs::frontend::gui::GuiFrontend;
+#[cfg(feature = "minifb")]
+use chip8_rs::frontend::minifb::MinifbFrontend;
+#[cfg(feature = "sdl")]
+use chip8_rs::frontend::sdl::SdlFrontend;
+#[cfg(feature = "terminal")]
+use chip8_rs::frontend::terminal::TerminalFrontend;
+#[cfg(feature = "tui")]
+use chip8_rs::frontend::tui::TuiFrontend;
+#[cfg(any(feature = "sdl", feature = "wgpu", feature = "minifb"))]
+use chip8_rs::frontend::WindowConfig;
+use chip8_rs::frontend::{Frontend, HostAction};
+#[cfg(feature = "gamepad")]
+use chip8_rs::gamepad::Gamepads;
+use chip8_rs::gif::GifRecorder;
+use chip8_rs::input_macro::{InputMacro, MacroPlayer, MacroRecorder};
+use chip8_rs::keymap::Keymap;
+use chip8_rs::keypad_overlay::KeypadOverlay;
+use chip8_rs::kiosk::{self, Kiosk};
+use chip8_rs::osc::OscSender;
+use chip8_rs::perf_overlay::PerfOverlay;
+use chip8_rs::rom_settings::RomSettings;
+use chip8_rs::scheduler::{DueTicks, Scheduler, TickJitter};
+use chip8_rs::screenshot::{self, Image};
+use chip8_rs::session::Session;
+use chip8_rs::status_screen;
+use chip8_rs::stress;
+#[cfg(feature = "tui")]
+use chip8_rs::teaching::Lesson;
+use chip8_rs::visual_buzzer::{BuzzerStyle, VisualBuzzer};
+use chip8_rs::watchdog::Watchdog;
 
-const CPU_HZ: u64 = 700;
-const TIMER_HZ: u64 = 60;
+use cli::{Args, BackendArg, Command, MachineArgs};
+
+// Shots per row of a --montage contact sheet
+const MONTAGE_COLUMNS: usize = 4;
+
+// Pixel size and panel spacing of a --diff-image
+const DIFF_SCALE: usize = 8;
+const DIFF_GAP: usize = 8;
 
 fn main() {
-    let mut chip8: Chip8 = Chip8::new();
+    let (mut args, mut session): (Args, Session) = cli::parse_args();
+
+    // Keep the flame guard alive for the whole run so spans are flushed on exit
+    #[cfg(feature = "tracing")]
+    let _flame_guard = args.trace_flame.as_ref().map(|path| install_flame_layer(path));
+
+    if let Some(command) = args.command {
+        run_command(command);
+        return;
+    }
+
+    let mut kiosk: Option<Kiosk> = args.playlist.as_ref().map(|path| {
+        match kiosk::read_playlist(path) {
+            Ok(roms) if roms.is_empty() => {
+                eprintln!("Playlist {} lists no ROMs", path.display());
+                process::exit(1);
+            }
+            Ok(roms) => Kiosk::new(roms, Duration::from_secs_f64(args.kiosk_seconds)),
+            Err(err) => {
+                eprintln!("Failed to read playlist {}: {}", path.display(), err);
+                process::exit(1);
+            }
+        }
+    });
+
+    // Runs with a window or terminal can start without a ROM, or with one that
+    // fails to load, and show a status screen; the others need the ROM
+    let realtime: bool =
+        args.audit_determinism.is_none() && args.emit_frame_hashes.is_none() && !args.headless && args.shots.is_empty();
 
-    // TODO: Load ROM here
-    // chip8.load_rom(&rom_bytes);
+    let mut rom_path: PathBuf = match &kiosk {
+        Some(kiosk) => kiosk.current_rom().to_path_buf(),
+        None => match args.rom.take() {
+            Some(path) => path,
+            None if realtime => PathBuf::new(),
+            None => {
+                eprintln!("A ROM is required for headless runs");
+                process::exit(1);
+            }
+        },
+    };
 
-    let cpu_interval: Duration = Duration::from_secs_f64(1.0 / CPU_HZ as f64);
-    let timer_interval: Duration = Duration::from_secs_f64(1.0 / TIMER_HZ as f64);
+    // Screen shown instead of the machine's frames while no ROM is running
+    let mut status: Option<DisplayBuffer> = None;
 
-    let mut last_cpu_tick: Instant = Instant::now();
-    let mut last_timer_tick: Instant = Instant::now();
+    let rom: Vec<u8> = if rom_path.as_os_str().is_empty() {
+        status = Some(status_screen::splash());
+        Vec::new()
+    } else if realtime {
+        match read_loadable_rom(&rom_path, args.banked) {
+            Ok(rom) => rom,
+            Err((message, reason)) => {
+                eprintln!("Failed to load ROM {}: {}", rom_path.display(), message);
+                status = Some(status_screen::load_error(reason));
+                Vec::new()
+            }
+        }
+    } else {
+        match read_loadable_rom(&rom_path, args.banked) {
+            Ok(rom) => rom,
+            Err((message, _)) => {
+                eprintln!("Failed to load ROM {}: {}", rom_path.display(), message);
+                process::exit(1);
+            }
+        }
+    };
+    let mut rom_loaded: bool = status.is_none();
 
-    loop {
-        let now: Instant = Instant::now();
+    let mut events: EventBus = EventBus::new();
+    let log_events: bool = args.log_events;
+    events.subscribe(move |event| log_event(event, log_events));
 
-        // CPU execution
-        if now.duration_since(last_cpu_tick) >= cpu_interval {
-            chip8.cycle();
-            last_cpu_tick = now;
+    if rom_loaded {
+        let hash: u64 = Session::hash_rom(&rom);
+        if session.rom_hash.is_some_and(|saved| saved != hash) {
+            eprintln!("Warning: {} has changed since the session was saved", rom_path.display());
         }
 
+        session.rom = Some(rom_path.clone());
+        session.rom_hash = Some(hash);
+    }
+    if let Some(path) = &args.save_session {
+        save_session(&session, path, &mut events);
+    }
+
+    if let Some(frames) = args.audit_determinism {
+        let seed: u64 = args.machine.seed.unwrap_or(0);
+
+        match audit::audit_determinism(&rom, seed, args.machine.quirks(), frames) {
+            Ok(Some(divergence)) => {
+                let Divergence { frame, first_hash, second_hash, first, second } = divergence;
+                eprintln!(
+                    "Nondeterminism detected at frame {}: {:016x} != {:016x}",
+                    frame, first_hash, second_hash
+                );
+                eprintln!();
+                if let Err(err) = diff::write_report(&first, &second, &mut io::stderr().lock()) {
+                    eprintln!("Failed to write divergence report: {}", err);
+                }
+
+                if let Some(path) = &args.diff_image {
+                    write_png(&diff::diff_image(first.frame(), second.frame(), DIFF_SCALE, DIFF_GAP), path);
+                }
+                process::exit(1);
+            }
+            Ok(None) => {
+                println!("Deterministic over {} frames (seed {})", frames, seed);
+                return;
+            }
+            Err(err) => {
+                eprintln!("Failed to audit ROM {}: {}", rom_path.display(), err);
+                process::exit(1);
+            }
+        }
+    }
+
+    let mut banking: Option<Banking> = args.banked.then(Banking::default);
+    let mut chip8: Chip8 = build_machine(&args.machine);
+    if let Err(err) = load_rom(&mut chip8, &rom, &mut banking) {
+        eprintln!("Failed to load ROM {}: {}", rom_path.display(), err);
+        process::exit(1);
+    }
+    if args.host_ports {
+        ports::attach(&mut chip8);
+    }
+    if let Some(path) = &args.serial {
+        let attached = SerialLink::open(path).and_then(|link| serial::attach(&mut chip8, link, args.serial_opcodes));
+        if let Err(err) = attached {
+            eprintln!("Failed to open serial device {}: {}", path.display(), err);
+            process::exit(1);
+        }
+    }
+    if rom_loaded {
+        events.publish(Event::RomLoaded { path: rom_path.clone(), bytes: rom.len() });
+    }
+
+    if let Some(path) = &args.load_memory {
+        match memory_image::load(path) {
+            Ok(memory) => chip8.memory = memory,
+            Err(err) => {
+                eprintln!("Failed to load memory image {}: {}", path.display(), err);
+                process::exit(1);
+            }
+        }
+    }
+
+    let mut sram: Option<Sram> = args.sram.map(|region| {
+        let path: PathBuf = args.sram_file.clone().unwrap_or_else(|| Sram::path_for_rom(&rom_path));
+
+        match Sram::open(region, &path) {
+            Ok(sram) => sram,
+            Err(err) => {
+                eprintln!("Failed to open SRAM file {}: {}", path.display(), err);
+                process::exit(1);
+            }
+        }
+    });
+    if let Some(sram) = &sram {
+        sram.restore(&mut chip8);
+    }
+
+    let mut macro_player: Option<MacroPlayer> = args.input_macro.as_ref().map(|path| {
+        match InputMacro::load(path) {
+            Ok(input) => MacroPlayer::new(input),
+            Err(err) => {
+                eprintln!("Failed to load input macro {}: {}", path.display(), err);
+                process::exit(1);
+            }
+        }
+    });
+    if args.macro_on_load
+        && let Some(player) = &mut macro_player
+    {
+        player.start();
+    }
+    let mut typing: Option<MacroPlayer> = args.paste.as_deref().map(start_typing);
+    let mut macro_recorder: Option<MacroRecorder> = args.record_macro.as_ref().map(|path| {
+        events.publish(Event::RecordingStarted { what: "input macro", path: path.clone() });
+        MacroRecorder::new()
+    });
+
+    let mut gif: Option<(GifRecorder<BufWriter<File>>, &Path)> = args.record_gif.as_deref().map(|path| {
+        let recorder: GifRecorder<BufWriter<File>> = open_gif(path, args.gif_scale);
+        events.publish(Event::RecordingStarted { what: "GIF", path: path.to_path_buf() });
+        (recorder, path)
+    });
+
+    let stop: Arc<AtomicBool> = install_stop_handler();
+
+    let mut watchdog: Option<Watchdog> =
+        args.watchdog.map(|seconds| Watchdog::new(Duration::from_secs_f64(seconds)));
+
+    if args.emit_frame_hashes.is_some() || args.headless || !args.shots.is_empty() {
+        let mut hash_output: Option<BufWriter<Box<dyn Write>>> =
+            args.emit_frame_hashes.as_ref().map(|target| BufWriter::new(open_output(target.as_deref())));
+
+        // Run long enough to reach the last requested shot
+        let frames: u64 = args.shots.iter().map(|&shot| shot + 1).fold(args.frames, u64::max);
+        let rom_name: String =
+            rom_path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+        let mut shots: Vec<Image> = Vec::new();
+
+        for frame in 0..frames {
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+
+            apply_macros(&mut chip8, &mut macro_player, &mut typing, &mut macro_recorder);
+            chip8.run_frame();
+            record_gif_frame(&mut gif, &chip8, &mut events);
+
+            if let Some(output) = &mut hash_output
+                && let Err(err) = writeln!(output, "{} {:016x} {:016x}", frame, chip8.state_hash(), chip8.display_hash())
+            {
+                eprintln!("Failed to write frame hashes: {}", err);
+                process::exit(1);
+            }
+
+            if args.shots.contains(&frame) {
+                let image: Image = Image::from_frame(chip8.frame(), args.shot_scale as usize);
+                write_png(&image, &args.shot_dir.join(format!("{}-{:06}.png", rom_name, frame)));
+                shots.push(image);
+            }
+
+            if let Some(watchdog) = &mut watchdog
+                && watchdog.observe_frame(&chip8)
+            {
+                warn_stuck(watchdog, frame, &mut events);
+            }
+        }
+
+        if let Some(path) = &args.montage {
+            write_png(&screenshot::contact_sheet(&shots, MONTAGE_COLUMNS, args.shot_scale as usize), path);
+        }
+
+        if let Some(output) = &mut hash_output
+            && let Err(err) = output.flush()
+        {
+            eprintln!("Failed to write frame hashes: {}", err);
+            process::exit(1);
+        }
+
+        if let Some(path) = &args.stats_out {
+            write_stats(&chip8, None, path);
+        }
+        if let Some(path) = &args.dump_memory {
+            dump_memory(&chip8, path, &mut events);
+        }
+        if let Some(sram) = &mut sram {
+            save_sram(sram, &chip8, &mut events);
+        }
+        if let (Some(recorder), Some(path)) = (&macro_recorder, &args.record_macro) {
+            save_macro(recorder, path, &mut events);
+        }
+        finish_gif(gif, &mut events);
+        return;
+    }
+
+    if let Some(target) = &args.osc_target {
+        match OscSender::connect(target.as_str()) {
+            Ok(sender) => events.subscribe(move |event| {
+                if let Err(err) = sender.send_event(event) {
+                    eprintln!("Failed to send OSC message: {}", err);
+                }
+            }),
+            Err(err) => {
+                eprintln!("Failed to open OSC target {}: {}", target, err);
+                process::exit(1);
+            }
+        }
+    }
+
+    let cpu_hz: u64 = saved_speed(&rom_path, &mut events).unwrap_or(CPU_HZ);
+    let mut speed_tuner: Option<SpeedTuner> = args.auto_speed.then(|| SpeedTuner::new(cpu_hz));
+
+    let mut frontend: Option<Box<dyn Frontend>> = open_frontend(&args, &rom_path, &mut chip8, load_keymap(&args), cpu_hz);
+    #[cfg(feature = "gamepad")]
+    let mut gamepads: Option<Gamepads> = frontend.as_ref().and_then(|_| open_gamepads(&args, &mut events));
+    let mut perf_overlay: PerfOverlay = PerfOverlay::new(args.perf_overlay);
+    let mut keypad_overlay: KeypadOverlay = KeypadOverlay::new(args.keypad_overlay);
+    let visual_buzzer: Option<VisualBuzzer> = args.visual_buzzer.map(|style| {
+        let style: BuzzerStyle = match style {
+            Some(style) => style.into(),
+            None => frontend.as_ref().map_or(BuzzerStyle::Border, |frontend| frontend.buzzer_style()),
+        };
+        VisualBuzzer::new(style)
+    });
+
+    // Whether the last presented frame had an overlay drawn over it
+    let mut overlaid: bool = false;
+
+    let mut scheduler: Scheduler = Scheduler::new(cpu_hz, TIMER_HZ);
+    let mut was_sounding: bool = false;
+    let mut frame: u64 = 0;
+
+    // Stopped from a frontend menu: no cycles, timers or frames, but input is still read
+    let mut paused: bool = false;
+
+    // A run too long to reach the end of the clock runs until killed
+    let end: Option<Duration> =
+        args.run_for.and_then(|seconds| scheduler.now().checked_add(Duration::from_secs_f64(seconds)));
+
+    while !stop.load(Ordering::Relaxed) && end.is_none_or(|end| scheduler.now() < end) {
+        scheduler.set_idle(paused || chip8.is_idle());
+        let due: DueTicks = scheduler.wait();
+
+        // CPU execution, paused while a kiosk transition plays
+        let running: bool = !paused && status.is_none() && !kiosk.as_ref().is_some_and(Kiosk::in_transition);
+        run_cpu(&mut chip8, &due, running);
+
         // Timer ticking
-        if now.duration_since(last_timer_tick) >= timer_interval {
-            chip8.tick_timers();
-            last_timer_tick = now;
+        if due.timer {
+            if let Some(screen) = &status {
+                chip8.show_frame(screen);
+            } else if !paused {
+                chip8.tick_timers();
+                record_gif_frame(&mut gif, &chip8, &mut events);
+
+                if let Some(watchdog) = &mut watchdog
+                    && watchdog.observe_frame(&chip8)
+                {
+                    warn_stuck(watchdog, frame, &mut events);
+                }
+                if let Some(tuner) = &mut speed_tuner
+                    && let Some(cpu_hz) = tuner.observe_frame(&chip8)
+                {
+                    scheduler.set_cpu_hz(cpu_hz);
+                    events.publish(Event::SpeedChanged(cpu_hz));
+                }
+                frame += 1;
+            }
+
+            // The playlist moves on from a ROM that failed to load too, so an
+            // unattended display never stays on the error screen
+            if !paused
+                && let Some(kiosk) = &mut kiosk
+                && let Some(next) = kiosk.observe_frame(&chip8)
+            {
+                reload(&mut chip8, &next, &mut banking, &mut events, &mut status, &mut rom_loaded);
+                switch_speed(&mut speed_tuner, &mut scheduler, &rom_path, &next, &chip8, &mut events);
+                rom_path = next;
+            }
+
+            if let Some(frontend) = &mut frontend {
+                let action: HostAction = frontend.pump_events(&mut chip8);
+                #[cfg(feature = "gamepad")]
+                if let Some(gamepads) = &mut gamepads {
+                    gamepads.poll(&mut chip8, &mut events);
+                }
+                if action == HostAction::Quit {
+                    break;
+                }
+
+                if action == HostAction::Reset || action == HostAction::HardReset {
+                    // SRAM is battery-backed: carry it across the reset
+                    if let Some(sram) = &mut sram {
+                        sram.capture(&chip8);
+                    }
+
+                    if action == HostAction::Reset {
+                        chip8.reset();
+                        events.publish(Event::Reset { hard: false });
+                        if rom_loaded {
+                            status = None;
+                        }
+                    } else if !rom_path.as_os_str().is_empty() {
+                        events.publish(Event::Reset { hard: true });
+                        reload(&mut chip8, &rom_path, &mut banking, &mut events, &mut status, &mut rom_loaded);
+                    }
+
+                    if let Some(sram) = &sram {
+                        sram.restore(&mut chip8);
+                    }
+
+                    if args.macro_on_load
+                        && let Some(player) = &mut macro_player
+                    {
+                        player.start();
+                    }
+                }
+                if action == HostAction::PlayMacro
+                    && let Some(player) = &mut macro_player
+                {
+                    player.start();
+                }
+                if action == HostAction::TogglePerfOverlay {
+                    perf_overlay.toggle();
+                }
+                if action == HostAction::ToggleKeypadOverlay {
+                    keypad_overlay.toggle();
+                }
+                if action == HostAction::TogglePause {
+                    paused = !paused;
+                }
+                if let HostAction::SetSpeed(cpu_hz) = action {
+                    scheduler.set_cpu_hz(cpu_hz);
+                    events.publish(Event::SpeedChanged(cpu_hz));
+                    if let Some(tuner) = &mut speed_tuner {
+                        tuner.set_cpu_hz(cpu_hz, &chip8);
+                    }
+                }
+                if let Some(text) = frontend.take_paste() {
+                    typing = Some(start_typing(&text));
+                }
+                if let Some(keymap) = frontend.take_keymap()
+                    && let Some(path) = &args.keymap
+                {
+                    save_keymap(&keymap, path, &mut events);
+                }
+                if let Some(path) = frontend.take_opened_rom() {
+                    reload(&mut chip8, &path, &mut banking, &mut events, &mut status, &mut rom_loaded);
+                    if status.is_none() {
+                        switch_speed(&mut speed_tuner, &mut scheduler, &rom_path, &path, &chip8, &mut events);
+                        rom_path = path;
+                    }
+                }
+                frontend.observe(&chip8);
+                if let Some(completed) = chip8.take_frame() {
+                    let completed: DisplayBuffer = *completed;
+                    let shown: DisplayBuffer =
+                        kiosk.as_ref().and_then(|kiosk| kiosk.overlay(&completed)).unwrap_or(completed);
+
+                    perf_overlay.observe_frame(&chip8);
+                    let shown: DisplayBuffer = perf_overlay.overlay(&shown).unwrap_or(shown);
+                    let shown: DisplayBuffer = keypad_overlay.overlay(&shown, &chip8.keys).unwrap_or(shown);
+                    let shown: DisplayBuffer = visual_buzzer
+                        .as_ref()
+                        .and_then(|buzzer| buzzer.overlay(&shown, chip8.sound_timer))
+                        .unwrap_or(shown);
+
+                    // The core only knows which of its own rows changed; an
+                    // overlay appearing, moving or going away needs a full redraw
+                    let dirty: DirtyRows = chip8.take_dirty_rows();
+                    let was_overlaid: bool = std::mem::replace(&mut overlaid, shown != completed);
+                    let dirty: DirtyRows = if overlaid || was_overlaid { DirtyRows::ALL } else { dirty };
+
+                    if let Err(err) = frontend.present(&shown, dirty) {
+                        events.publish(Event::Error(format!("Failed to present frame: {}", err)));
+                    }
+                }
+            }
+
+            if !paused {
+                apply_macros(&mut chip8, &mut macro_player, &mut typing, &mut macro_recorder);
+            }
         }
 
-        // Prevent 100% CPU usage
-        thread::sleep(Duration::from_micros(500));
+        // Sound start/stop notifications
+        let sounding: bool = chip8.sound_timer > 0;
+        if sounding != was_sounding {
+            events.publish(Event::Sound { on: sounding });
+            if let Some(frontend) = &mut frontend {
+                frontend.beep(sounding);
+            }
+            was_sounding = sounding;
+        }
+    }
+
+    // Hand the terminal back before anything else is printed
+    drop(frontend);
+
+    // Don't leave an external synth droning on
+    if was_sounding {
+        events.publish(Event::Sound { on: false });
+    }
+
+    if args.jitter_report
+        && let Err(err) = scheduler.jitter().write_report(&mut io::stderr().lock())
+    {
+        eprintln!("Failed to write jitter report: {}", err);
+    }
+    if let Some(path) = &args.stats_out {
+        write_stats(&chip8, Some(scheduler.jitter()), path);
+    }
+    if let Some(path) = &args.dump_memory {
+        dump_memory(&chip8, path, &mut events);
+    }
+    if let Some(sram) = &mut sram {
+        save_sram(sram, &chip8, &mut events);
+    }
+    if let Some(tuner) = &speed_tuner {
+        save_speed(tuner.cpu_hz(), &rom_path, &mut events);
+    }
+    if let (Some(recorder), Some(path)) = (&macro_recorder, &args.record_macro) {
+        save_macro(recorder, path, &mut events);
+    }
+    finish_gif(gif, &mut events);
+}
+
+// The CPU side of one pass of the real-time loop
+fn run_cpu(chip8: &mut Chip8, due: &DueTicks, running: bool) {
+    if running && due.runs_cycle(chip8) {
+        chip8.cycle();
+    }
+}
+
+// Run a standalone subcommand (one that does not execute a ROM in real time)
+fn run_command(command: Command) {
+    match command {
+        Command::GenStress { kind, seed, output } => {
+            let rom: Vec<u8> = stress::generate(kind.into(), seed);
+
+            if let Err(err) = std::fs::write(&output, rom) {
+                eprintln!("Failed to write {}: {}", output.display(), err);
+                process::exit(1);
+            }
+        }
+
+        Command::Opcodes => print_opcode_table(),
+
+        Command::Capabilities => {
+            if let Err(err) = Capabilities::current().write_json(&mut io::stdout().lock()) {
+                eprintln!("Failed to write capabilities: {}", err);
+                process::exit(1);
+            }
+        }
+
+        Command::Conformance => {
+            if let Err(err) = conformance::write_scorecard(&conformance::run(), &mut io::stdout().lock()) {
+                eprintln!("Failed to write scorecard: {}", err);
+                process::exit(1);
+            }
+        }
+
+        Command::DumpState { rom, machine, frames, format } => {
+            let mut chip8: Chip8 = build_machine(&machine);
+            if let Err(err) = chip8.load_rom(&read_rom(&rom)) {
+                eprintln!("Failed to load ROM {}: {}", rom.display(), err);
+                process::exit(1);
+            }
+
+            for _ in 0..frames {
+                chip8.run_frame();
+            }
+
+            let mut output: io::StdoutLock = io::stdout().lock();
+            if let Err(err) = state_dump::write_state(&chip8, format.into(), &mut output) {
+                eprintln!("Failed to write state: {}", err);
+                process::exit(1);
+            }
+        }
+
+        Command::CompatReport { dir, machine, frames } => {
+            let results: Vec<CompatResult> = run_compat_report(&dir, &machine, frames);
+
+            if let Err(err) = compat::write_report(&results, &mut io::stdout().lock()) {
+                eprintln!("Failed to write report: {}", err);
+                process::exit(1);
+            }
+
+            if results.iter().any(|result| matches!(result.outcome, Outcome::Crashed { .. })) {
+                process::exit(1);
+            }
+        }
+
+        Command::Batch { roms, machine, frames, jobs } => {
+            let paths: Vec<PathBuf> = roms
+                .iter()
+                .flat_map(|path| if path.is_dir() { rom_paths(path) } else { vec![path.clone()] })
+                .collect();
+            let loaded: Vec<(String, Vec<u8>)> =
+                paths.iter().map(|path| (path.display().to_string(), read_rom(path))).collect();
+            let jobs: usize = match jobs {
+                Some(jobs) => jobs as usize,
+                None => std::thread::available_parallelism().map_or(1, |cpus| cpus.get()),
+            };
+
+            let results: Vec<CompatResult> = without_panic_output(|| {
+                batch::run_batch(&loaded, machine.seed.unwrap_or(0), machine.quirks(), frames, jobs)
+            });
+
+            if let Err(err) = batch::write_report(&results, &mut io::stdout().lock()) {
+                eprintln!("Failed to write report: {}", err);
+                process::exit(1);
+            }
+
+            if results.iter().any(|result| matches!(result.outcome, Outcome::Crashed { .. })) {
+                process::exit(1);
+            }
+        }
+    }
+}
+
+// Read a ROM file, exiting with a message if it cannot be read
+fn read_rom(path: &Path) -> Vec<u8> {
+    match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            eprintln!("Failed to read ROM {}: {}", path.display(), err);
+            process::exit(1);
+        }
+    }
+}
+
+// Create a machine configured from the shared CLI options, with no ROM loaded
+fn build_machine(machine: &MachineArgs) -> Chip8 {
+    let mut chip8: Chip8 = match machine.seed {
+        Some(seed) => Chip8::with_seed(seed),
+        None => Chip8::new(),
+    };
+    chip8.quirks = machine.quirks();
+
+    chip8
+}
+
+// Load `rom`, cutting it into banks with --banked
+fn load_rom(chip8: &mut Chip8, rom: &[u8], banking: &mut Option<Banking>) -> Result<(), Chip8Error> {
+    match banking {
+        Some(banking) => banking.load(chip8, rom),
+        None => chip8.load_rom(rom),
+    }
+}
+
+// Run each regular file in `dir` (sorted by name) as a ROM
+fn run_compat_report(dir: &Path, machine: &MachineArgs, frames: u64) -> Vec<CompatResult> {
+    let paths: Vec<PathBuf> = rom_paths(dir);
+
+    without_panic_output(|| {
+        paths
+            .iter()
+            .map(|path| {
+                let name: String =
+                    path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+                compat::run_rom(&name, &read_rom(path), machine.seed.unwrap_or(0), machine.quirks(), frames)
+            })
+            .collect()
+    })
+}
+
+// Regular files in `dir`, sorted by name
+fn rom_paths(dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = match std::fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file())
+            .collect(),
+        Err(err) => {
+            eprintln!("Failed to read directory {}: {}", dir.display(), err);
+            process::exit(1);
+        }
+    };
+    paths.sort();
+
+    paths
+}
+
+// Crashes are reported in the tables; keep panic messages off stderr meanwhile
+fn without_panic_output<T>(run: impl FnOnce() -> T) -> T {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let result: T = run();
+
+    std::panic::set_hook(default_hook);
+
+    result
+}
+
+// Print every instruction form straight from the interpreter's own definitions
+fn print_opcode_table() {
+    println!("{:<8}{:<16}{:<56}QUIRKS", "OPCODE", "MNEMONIC", "DESCRIPTION");
+
+    for form in INSTRUCTION_FORMS.iter() {
+        let spec: &InstructionSpec = form.spec();
+        let line: String = format!(
+            "{:<8}{:<16}{:<56}{}",
+            spec.pattern,
+            spec.mnemonic,
+            spec.description,
+            spec.quirks.join(", ")
+        );
+        println!("{}", line.trim_end());
+    }
+}
+
+// Write the session statistics CSV, reporting (but not failing on) errors
+fn write_stats(chip8: &Chip8, jitter: Option<&TickJitter>, path: &Path) {
+    let result: io::Result<()> = File::create(path).and_then(|file| {
+        let mut output: BufWriter<File> = BufWriter::new(file);
+        chip8.stats.write_csv(&mut output)?;
+        if let Some(jitter) = jitter {
+            jitter.write_csv(&mut output)?;
+        }
+        output.flush()
+    });
+
+    if let Err(err) = result {
+        eprintln!("Failed to write stats to {}: {}", path.display(), err);
+    }
+}
+
+// Open the backend --backend (or a shorthand flag) picks; headless has none
+#[allow(unused_variables)]
+fn open_frontend(
+    args: &Args,
+    rom_path: &Path,
+    chip8: &mut Chip8,
+    keymap: Option<Keymap>,
+    cpu_hz: u64,
+) -> Option<Box<dyn Frontend>> {
+    let opened: Result<Option<Box<dyn Frontend>>, String> = match args.backend() {
+        #[cfg(feature = "tui")]
+        BackendArg::Tui => {
+            let lesson: Option<Lesson> = args.teach.then(|| Lesson::attach(chip8));
+
+            TuiFrontend::new(lesson, args.palette(), keymap.unwrap_or(args.layout().keymap()))
+                .map(|tui| Some(Box::new(tui) as Box<dyn Frontend>))
+                .map_err(|err| format!("Failed to set up terminal: {}", err))
+        }
+
+        #[cfg(feature = "terminal")]
+        BackendArg::Terminal => TerminalFrontend::new(args.terminal_graphics.into(), args.palette(), keymap.unwrap_or(args.layout().keymap()))
+            .map(|terminal| Some(Box::new(terminal) as Box<dyn Frontend>))
+            .map_err(|err| format!("Failed to set up terminal: {}", err)),
+
+        #[cfg(feature = "minifb")]
+        BackendArg::Minifb => MinifbFrontend::new(&window_title(rom_path), &WindowConfig { keymap, ..args.window_config() })
+            .map(|window| Some(Box::new(window) as Box<dyn Frontend>))
+            .map_err(|err| format!("Failed to open window: {}", err)),
+
+        #[cfg(feature = "wgpu")]
+        BackendArg::Gpu => GpuFrontend::new(&window_title(rom_path), &WindowConfig { keymap, ..args.window_config() })
+            .map(|window| Some(Box::new(window) as Box<dyn Frontend>))
+            .map_err(|err| format!("Failed to open window: {}", err)),
+
+        #[cfg(feature = "gui")]
+        BackendArg::Gui => GuiFrontend::new(&window_title(rom_path), &WindowConfig { keymap, ..args.window_config() }, cpu_hz)
+            .map(|window| Some(Box::new(window) as Box<dyn Frontend>))
+            .map_err(|err| format!("Failed to open window: {}", err)),
+
+        #[cfg(feature = "sdl")]
+        BackendArg::Sdl => SdlFrontend::new(&window_title(rom_path), &WindowConfig { keymap, ..args.window_config() })
+            .map(|window| Some(Box::new(window) as Box<dyn Frontend>))
+            .map_err(|err| format!("Failed to open window: {}", err)),
+
+        BackendArg::Headless => Ok(None),
+    };
+
+    match opened {
+        Ok(frontend) => frontend,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
+}
+
+// Watch for game controllers next to the frontend; runs on without them if gilrs can't start
+#[cfg(feature = "gamepad")]
+fn open_gamepads(args: &Args, events: &mut EventBus) -> Option<Gamepads> {
+    match Gamepads::new(args.gamepad_map.clone().unwrap_or_default()) {
+        Ok(gamepads) => {
+            for name in gamepads.connected() {
+                events.publish(Event::Gamepad { name, connected: true });
+            }
+            Some(gamepads)
+        }
+        Err(err) => {
+            events.publish(Event::Error(format!("Gamepads unavailable: {}", err)));
+            None
+        }
+    }
+}
+
+#[cfg(any(feature = "sdl", feature = "wgpu", feature = "minifb"))]
+fn window_title(rom_path: &Path) -> String {
+    match rom_path.file_name() {
+        Some(name) => format!("CHIP-8 — {}", name.to_string_lossy()),
+        None => "CHIP-8".to_string(),
+    }
+}
+
+// Re-read the ROM from disk and restart with it, keeping the old one on failure.
+// Kiosk mode switches ROMs the same way.
+fn hard_reset(
+    chip8: &mut Chip8,
+    rom_path: &Path,
+    banking: &mut Option<Banking>,
+    events: &mut EventBus,
+) -> Result<(), &'static str> {
+    match read_loadable_rom(rom_path, banking.is_some()) {
+        Ok(rom) => match load_rom(chip8, &rom, banking) {
+            Ok(()) => {
+                events.publish(Event::RomLoaded { path: rom_path.to_path_buf(), bytes: rom.len() });
+                Ok(())
+            }
+            Err(err) => {
+                events.publish(Event::Error(format!("Failed to reload ROM {}: {}", rom_path.display(), err)));
+                Err("TOO LARGE")
+            }
+        },
+        Err((message, reason)) => {
+            events.publish(Event::Error(format!("Failed to reload ROM {}: {}", rom_path.display(), message)));
+            Err(reason)
+        }
+    }
+}
+
+// Hard reset with `rom_path`, then show the error screen if that failed, or
+// take down any status screen if it worked
+fn reload(
+    chip8: &mut Chip8,
+    rom_path: &Path,
+    banking: &mut Option<Banking>,
+    events: &mut EventBus,
+    status: &mut Option<DisplayBuffer>,
+    rom_loaded: &mut bool,
+) {
+    match hard_reset(chip8, rom_path, banking, events) {
+        Ok(()) => {
+            *status = None;
+            *rom_loaded = true;
+        }
+        Err(reason) => *status = Some(status_screen::load_error(reason)),
+    }
+}
+
+// A ROM file's bytes, or why it cannot be loaded: the full message, and a
+// few words for the error screen. Banked ROMs may go past memory.
+fn read_loadable_rom(path: &Path, banked: bool) -> Result<Vec<u8>, (String, &'static str)> {
+    match std::fs::read(path) {
+        Ok(rom) if banked && rom.len() > MAX_BANKED_ROM => {
+            Err(("too large for 256 banks".to_string(), "TOO LARGE"))
+        }
+        Ok(rom) if !banked && PROGRAM_START as usize + rom.len() > MEMORY_SIZE => {
+            Err(("too large to fit in memory".to_string(), "TOO LARGE"))
+        }
+        Ok(rom) => Ok(rom),
+        Err(err) => {
+            let reason: &'static str = match err.kind() {
+                io::ErrorKind::NotFound => "NOT FOUND",
+                io::ErrorKind::PermissionDenied => "NO ACCESS",
+                _ => "READ ERROR",
+            };
+            Err((err.to_string(), reason))
+        }
+    }
+}
+
+// Once per frame: let a playing macro or typed keys drive the keypad, then record what the ROM sees
+fn apply_macros(
+    chip8: &mut Chip8,
+    player: &mut Option<MacroPlayer>,
+    typing: &mut Option<MacroPlayer>,
+    recorder: &mut Option<MacroRecorder>,
+) {
+    if let Some(player) = player {
+        player.apply_frame(&mut chip8.keys);
+    }
+    if let Some(typing) = typing {
+        typing.apply_frame(&mut chip8.keys);
+    }
+    if let Some(recorder) = recorder {
+        recorder.observe_frame(&chip8.keys);
+    }
+}
+
+// Play `text` on the keypad as typed hex digits, starting with the next frame
+fn start_typing(text: &str) -> MacroPlayer {
+    let mut player: MacroPlayer = MacroPlayer::new(InputMacro::from_keystrokes(text));
+    player.start();
+    player
+}
+
+fn save_macro(recorder: &MacroRecorder, path: &Path, events: &mut EventBus) {
+    match recorder.finish().save(path) {
+        Ok(()) => events.publish(Event::RecordingFinished { what: "input macro", path: path.to_path_buf() }),
+        Err(err) => events.publish(Event::Error(format!("Failed to write input macro {}: {}", path.display(), err))),
+    }
+}
+
+fn open_gif(path: &Path, scale: u8) -> GifRecorder<BufWriter<File>> {
+    let result: io::Result<GifRecorder<BufWriter<File>>> =
+        File::create(path).and_then(|file| GifRecorder::new(BufWriter::new(file), scale as usize));
+
+    match result {
+        Ok(recorder) => recorder,
+        Err(err) => {
+            eprintln!("Failed to create GIF {}: {}", path.display(), err);
+            process::exit(1);
+        }
+    }
+}
+
+// Add the frame latched at this tick to the clip; a write error ends the recording
+fn record_gif_frame(gif: &mut Option<(GifRecorder<BufWriter<File>>, &Path)>, chip8: &Chip8, events: &mut EventBus) {
+    if let Some((recorder, path)) = gif
+        && let Err(err) = recorder.observe_frame(chip8.frame())
+    {
+        events.publish(Event::Error(format!("Failed to write GIF {}: {}", path.display(), err)));
+        *gif = None;
+    }
+}
+
+fn finish_gif(gif: Option<(GifRecorder<BufWriter<File>>, &Path)>, events: &mut EventBus) {
+    if let Some((recorder, path)) = gif {
+        match recorder.finish() {
+            Ok(_) => events.publish(Event::RecordingFinished { what: "GIF", path: path.to_path_buf() }),
+            Err(err) => events.publish(Event::Error(format!("Failed to write GIF {}: {}", path.display(), err))),
+        }
+    }
+}
+
+fn save_sram(sram: &mut Sram, chip8: &Chip8, events: &mut EventBus) {
+    sram.capture(chip8);
+
+    match sram.save() {
+        Ok(()) => events.publish(Event::StateSaved { what: "SRAM", path: sram.path().to_path_buf() }),
+        Err(err) => {
+            events.publish(Event::Error(format!("Failed to write SRAM file {}: {}", sram.path().display(), err)));
+        }
+    }
+}
+
+// Write a PNG, exiting with a message on failure
+fn write_png(image: &Image, path: &Path) {
+    let result: io::Result<()> = File::create(path).and_then(|file| {
+        let mut output: BufWriter<File> = BufWriter::new(file);
+        image.write_png(&mut output)?;
+        output.flush()
+    });
+
+    if let Err(err) = result {
+        eprintln!("Failed to write {}: {}", path.display(), err);
+        process::exit(1);
+    }
+}
+
+// --keymap bindings, or None for the layout's. A missing file is not an
+// error; it is written when keys are first remapped.
+fn load_keymap(args: &Args) -> Option<Keymap> {
+    let path: &Path = args.keymap.as_deref().filter(|path| path.exists())?;
+
+    match Keymap::load(path) {
+        Ok(keymap) => Some(keymap),
+        Err(err) => {
+            eprintln!("Failed to load keymap {}: {}", path.display(), err);
+            process::exit(1);
+        }
+    }
+}
+
+fn save_keymap(keymap: &Keymap, path: &Path, events: &mut EventBus) {
+    match keymap.save(path) {
+        Ok(()) => events.publish(Event::StateSaved { what: "keymap", path: path.to_path_buf() }),
+        Err(err) => events.publish(Event::Error(format!("Failed to save keymap to {}: {}", path.display(), err))),
+    }
+}
+
+// Speed saved in a ROM's settings, if any; an unreadable file is reported and ignored
+fn saved_speed(rom_path: &Path, events: &mut EventBus) -> Option<u64> {
+    if rom_path.as_os_str().is_empty() {
+        return None;
+    }
+
+    let path: PathBuf = RomSettings::path_for_rom(rom_path);
+    match RomSettings::load(&path) {
+        Ok(settings) => settings.cpu_hz(),
+        Err(err) => {
+            events.publish(Event::Error(format!("Failed to read ROM settings {}: {}", path.display(), err)));
+            None
+        }
+    }
+}
+
+// Remember `cpu_hz` in the ROM's settings when it differs from what the ROM
+// would start at anyway
+fn save_speed(cpu_hz: u64, rom_path: &Path, events: &mut EventBus) {
+    if rom_path.as_os_str().is_empty() {
+        return;
+    }
+
+    let path: PathBuf = RomSettings::path_for_rom(rom_path);
+    let mut settings: RomSettings = match RomSettings::load(&path) {
+        Ok(settings) => settings,
+        Err(err) => {
+            events.publish(Event::Error(format!("Failed to read ROM settings {}: {}", path.display(), err)));
+            return;
+        }
+    };
+    if settings.cpu_hz().unwrap_or(CPU_HZ) == cpu_hz {
+        return;
+    }
+
+    settings.set_cpu_hz(cpu_hz);
+    match settings.save(&path) {
+        Ok(()) => events.publish(Event::StateSaved { what: "ROM settings", path }),
+        Err(err) => events.publish(Event::Error(format!("Failed to save ROM settings to {}: {}", path.display(), err))),
+    }
+}
+
+// Moving from ROM `from` to `to`: keep the speed tuned for `from` and run
+// `to` at its own saved speed
+fn switch_speed(
+    tuner: &mut Option<SpeedTuner>,
+    scheduler: &mut Scheduler,
+    from: &Path,
+    to: &Path,
+    chip8: &Chip8,
+    events: &mut EventBus,
+) {
+    if let Some(tuner) = tuner {
+        save_speed(tuner.cpu_hz(), from, events);
+    }
+
+    let cpu_hz: u64 = saved_speed(to, events).unwrap_or(CPU_HZ);
+    scheduler.set_cpu_hz(cpu_hz);
+    events.publish(Event::SpeedChanged(cpu_hz));
+    if let Some(tuner) = tuner {
+        tuner.set_cpu_hz(cpu_hz, chip8);
+    }
+}
+
+fn save_session(session: &Session, path: &Path, events: &mut EventBus) {
+    match session.save(path) {
+        Ok(()) => events.publish(Event::StateSaved { what: "session", path: path.to_path_buf() }),
+        Err(err) => events.publish(Event::Error(format!("Failed to save session to {}: {}", path.display(), err))),
+    }
+}
+
+fn dump_memory(chip8: &Chip8, path: &Path, events: &mut EventBus) {
+    match memory_image::save(&chip8.memory, path) {
+        Ok(()) => events.publish(Event::StateSaved { what: "memory dump", path: path.to_path_buf() }),
+        Err(err) => events.publish(Event::Error(format!("Failed to dump memory to {}: {}", path.display(), err))),
+    }
+}
+
+// stderr logging: errors always, everything else with --log-events
+fn log_event(event: &Event, verbose: bool) {
+    match event {
+        Event::Error(message) | Event::Warning(message) => eprintln!("{}", message),
+        _ if verbose => eprintln!("[event] {}", event),
+        _ => {}
+    }
+}
+
+// Ask the run loops to finish (and write their outputs) on SIGINT/SIGTERM.
+// A second signal exits immediately in case the loop is stuck.
+fn install_stop_handler() -> Arc<AtomicBool> {
+    let stop: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
+    let flag: Arc<AtomicBool> = Arc::clone(&stop);
+
+    let result: Result<(), ctrlc::Error> = ctrlc::set_handler(move || {
+        if flag.swap(true, Ordering::Relaxed) {
+            process::exit(130);
+        }
+    });
+    if let Err(err) = result {
+        eprintln!("Failed to install signal handler: {}", err);
+    }
+
+    stop
+}
+
+// Open FILE for writing, or stdout when no path is given
+fn open_output(path: Option<&Path>) -> Box<dyn Write> {
+    match path {
+        Some(path) => match File::create(path) {
+            Ok(file) => Box::new(file),
+            Err(err) => {
+                eprintln!("Failed to create {}: {}", path.display(), err);
+                process::exit(1);
+            }
+        },
+        None => Box::new(io::stdout().lock()),
+    }
+}
+
+fn warn_stuck(watchdog: &Watchdog, frame: u64, events: &mut EventBus) {
+    events.publish(Event::Warning(format!(
+        "Watchdog: no display change, sound or key wait for {:.1}s (frame {}); \
+         the ROM may need different quirks",
+        watchdog.idle_time().as_secs_f64(),
+        frame
+    )));
+}
+
+#[cfg(feature = "tracing")]
+fn install_flame_layer(path: &Path) -> tracing_flame::FlushGuard<BufWriter<File>> {
+    use tracing_subscriber::prelude::*;
+
+    let (flame_layer, guard) = match tracing_flame::FlameLayer::with_file(path) {
+        Ok(pair) => pair,
+        Err(err) => {
+            eprintln!("Failed to create {}: {}", path.display(), err);
+            process::exit(1);
+        }
+    };
+
+    tracing_subscriber::registry().with(flame_layer).init();
+
+    guard
+}
+
+#[cfg(test)]
+mod tests {
+    use chip8_rs::scheduler::ManualClock;
+
+    use super::*;
+
+    // The real-time loop's CPU and timer handling for `iterations` passes,
+    // pressing `key` from pass `press_at` on
+    fn run_loop(chip8: &mut Chip8, iterations: usize, key: usize, press_at: usize) {
+        let mut scheduler: Scheduler<ManualClock> = Scheduler::with_clock(ManualClock::new(), CPU_HZ, TIMER_HZ);
+
+        for iteration in 0..iterations {
+            if iteration == press_at {
+                chip8.keys[key] = true;
+            }
+
+            scheduler.set_idle(chip8.is_idle());
+            let due: DueTicks = scheduler.wait();
+            run_cpu(chip8, &due, true);
+            if due.timer {
+                chip8.tick_timers();
+            }
+        }
+    }
+
+    #[test]
+    fn key_press_resumes_a_rom_waiting_in_fx0a() {
+        // F00A: wait for a key into V0; 6107: V1 = 7; 1204: jump to self
+        let mut chip8: Chip8 = Chip8::new();
+        chip8.load_rom(&[0xF0, 0x0A, 0x61, 0x07, 0x12, 0x04]).unwrap();
+
+        run_loop(&mut chip8, 500, 5, 100);
+
+        assert_eq!(chip8.waiting_for_key(), None);
+        assert_eq!(chip8.v[0], 5);
+        assert_eq!(chip8.v[1], 7);
+        assert_eq!(chip8.pc, 0x204);
+    }
+
+    #[test]
+    fn paused_loop_runs_no_cycles() {
+        let mut chip8: Chip8 = Chip8::new();
+        chip8.load_rom(&[0xF0, 0x0A]).unwrap();
+        chip8.keys[5] = true;
+
+        run_cpu(&mut chip8, &DueTicks { cpu: true, timer: true }, false);
+
+        assert_eq!(chip8.stats.cycles, 0);
     }
-}
\ No newline at end of file
+}
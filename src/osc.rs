@@ -0,0 +1,111 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — OSC Output
+// Minimal Open Sound Control sender over UDP, used to notify
+// external music gear when the sound timer starts and stops. It
+// subscribes to the event bus and ignores everything but sound.
+// ───────────────────────────────────────────────────────────────
+
+use std::io;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket};
+
+use crate::events::Event;
+
+// OSC address for sound on/off events (argument: 1 = on, 0 = off)
+pub const SOUND_ADDRESS: &str = "/chip8/sound";
+
+pub struct OscSender {
+    socket: UdpSocket,
+}
+
+impl OscSender {
+
+    // Bind an ephemeral local port of the target's address family and
+    // direct all messages at `target`. A name resolving to several
+    // addresses uses the first one that connects.
+    pub fn connect<A: ToSocketAddrs>(target: A) -> io::Result<Self> {
+        let mut last_err: io::Error = io::Error::new(io::ErrorKind::InvalidInput, "target resolved to no addresses");
+
+        for address in target.to_socket_addrs()? {
+            let local: SocketAddr = match address {
+                SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+                SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+            };
+
+            match UdpSocket::bind(local).and_then(|socket| socket.connect(address).map(|()| socket)) {
+                Ok(socket) => return Ok(Self { socket }),
+                Err(err) => last_err = err,
+            }
+        }
+
+        Err(last_err)
+    }
+
+    // Send a message carrying a single int32 argument
+    pub fn send_int(&self, address: &str, value: i32) -> io::Result<()> {
+        self.socket.send(&encode_int_message(address, value))?;
+        Ok(())
+    }
+
+    // Forward the events external gear cares about; others are ignored
+    pub fn send_event(&self, event: &Event) -> io::Result<()> {
+        match event {
+            Event::Sound { on } => self.send_int(SOUND_ADDRESS, *on as i32),
+            _ => Ok(()),
+        }
+    }
+}
+
+// Encode `address ,i value` as an OSC 1.0 packet
+fn encode_int_message(address: &str, value: i32) -> Vec<u8> {
+    let mut packet: Vec<u8> = Vec::new();
+
+    push_padded_string(&mut packet, address);
+    push_padded_string(&mut packet, ",i");
+    packet.extend_from_slice(&value.to_be_bytes());
+
+    packet
+}
+
+// OSC strings are NUL-terminated and padded to a multiple of 4 bytes
+fn push_padded_string(packet: &mut Vec<u8>, text: &str) {
+    packet.extend_from_slice(text.as_bytes());
+    packet.push(0);
+
+    while !packet.len().is_multiple_of(4) {
+        packet.push(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    // Send a sound event to a receiver bound on `loopback` and return the packet
+    fn send_over(loopback: &str) -> Option<Vec<u8>> {
+        // Hosts without the address family configured can't run the test
+        let receiver: UdpSocket = UdpSocket::bind(loopback).ok()?;
+        receiver.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+
+        let sender: OscSender = OscSender::connect(receiver.local_addr().unwrap()).unwrap();
+        sender.send_event(&Event::Sound { on: true }).unwrap();
+
+        let mut packet: [u8; 64] = [0; 64];
+        let len: usize = receiver.recv(&mut packet).unwrap();
+        Some(packet[..len].to_vec())
+    }
+
+    #[test]
+    fn sends_to_ipv4_targets() {
+        let packet: Vec<u8> = send_over("127.0.0.1:0").expect("no IPv4 loopback");
+        assert_eq!(packet, encode_int_message(SOUND_ADDRESS, 1));
+    }
+
+    #[test]
+    fn sends_to_ipv6_targets() {
+        if let Some(packet) = send_over("[::1]:0") {
+            assert_eq!(packet, encode_int_message(SOUND_ADDRESS, 1));
+        }
+    }
+}
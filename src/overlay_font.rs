@@ -0,0 +1,84 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Overlay Font
+// A 3x5 pixel font for the on-screen overlays and status screens,
+// which draw straight into the display buffer: digits, capital
+// letters and a dash. Unknown characters draw as blanks.
+// ───────────────────────────────────────────────────────────────
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH};
+use crate::chip8::display::DisplayBuffer;
+
+// Glyph cell: 3x5 pixels plus one pixel of spacing
+pub const GLYPH_WIDTH: usize = 4;
+pub const GLYPH_HEIGHT: usize = 6;
+
+// Draw `text` with its top-left corner at (x, y), setting lit glyph pixels
+// to `lit`; text running off the right or bottom edge is cut off
+pub fn draw_text(frame: &mut DisplayBuffer, x: usize, y: usize, text: &str, lit: bool) {
+    for (column, ch) in text.chars().enumerate() {
+        draw_glyph(frame, x + column * GLYPH_WIDTH, y, glyph(ch), lit);
+    }
+}
+
+// Clear (or fill) a `width` x `height` box, clipped to the display
+pub fn fill_box(frame: &mut DisplayBuffer, x: usize, y: usize, width: usize, height: usize, lit: bool) {
+    for row in frame.iter_mut().skip(y).take(height) {
+        for pixel in row.iter_mut().skip(x).take(width) {
+            *pixel = lit;
+        }
+    }
+}
+
+// Rows of a 3x5 glyph, most significant of the low three bits on the left
+fn glyph(ch: char) -> [u8; 5] {
+    match ch {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        _ => [0; 5],
+    }
+}
+
+fn draw_glyph(frame: &mut DisplayBuffer, x: usize, y: usize, rows: [u8; 5], lit: bool) {
+    for (dy, bits) in rows.iter().enumerate() {
+        for dx in 0..3 {
+            if bits & (0b100 >> dx) != 0 && x + dx < DISPLAY_WIDTH && y + dy < DISPLAY_HEIGHT {
+                frame[y + dy][x + dx] = lit;
+            }
+        }
+    }
+}
@@ -0,0 +1,124 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Palettes
+// Display colors shared by every frontend, with a few named
+// presets. Frontends take a Palette when they open instead of
+// hardcoding their own colors.
+// ───────────────────────────────────────────────────────────────
+
+use std::str::FromStr;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    // Packed as 0x00RRGGBB
+    pub fn to_u32(self) -> u32 {
+        (self.r as u32) << 16 | (self.g as u32) << 8 | self.b as u32
+    }
+
+    // Components scaled to 0.0..=1.0
+    pub fn to_f32(self) -> [f32; 3] {
+        [self.r, self.g, self.b].map(|component| component as f32 / 255.0)
+    }
+}
+
+// Parses "RRGGBB" or "#RRGGBB"
+impl FromStr for Rgb {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let hex: &str = text.strip_prefix('#').unwrap_or(text);
+        if hex.len() != 6 {
+            return Err("expected a color as RRGGBB".to_string());
+        }
+
+        let value: u32 = u32::from_str_radix(hex, 16).map_err(|err| err.to_string())?;
+        Ok(Self::new((value >> 16) as u8, (value >> 8) as u8, value as u8))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Palette {
+    // Unlit pixels
+    pub background: Rgb,
+
+    // Lit pixels
+    pub foreground: Rgb,
+}
+
+impl Palette {
+
+    // Color of a pixel at brightness `level`, from background (0) to
+    // foreground (255)
+    pub fn shade(&self, level: u8) -> Rgb {
+        let mix = |background: u8, foreground: u8| {
+            (background as u32 * (255 - level as u32) + foreground as u32 * level as u32).div_ceil(255) as u8
+        };
+
+        Rgb::new(
+            mix(self.background.r, self.foreground.r),
+            mix(self.background.g, self.foreground.g),
+            mix(self.background.b, self.foreground.b),
+        )
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Preset::Gray.palette()
+    }
+}
+
+// Named palettes selectable from the command line
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Preset {
+    // Light gray on near-black
+    Gray,
+
+    // Green phosphor monitor
+    Green,
+
+    // Amber phosphor monitor
+    Amber,
+
+    // Dark pixels on a greenish reflective LCD
+    Lcd,
+
+    // Octo's default yellow on brown
+    Octo,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 5] = [Preset::Gray, Preset::Green, Preset::Amber, Preset::Lcd, Preset::Octo];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Preset::Gray => "Gray",
+            Preset::Green => "Green",
+            Preset::Amber => "Amber",
+            Preset::Lcd => "LCD",
+            Preset::Octo => "Octo",
+        }
+    }
+
+    pub fn palette(self) -> Palette {
+        let (background, foreground) = match self {
+            Preset::Gray => (Rgb::new(0x10, 0x10, 0x10), Rgb::new(0xE0, 0xE0, 0xE0)),
+            Preset::Green => (Rgb::new(0x0A, 0x14, 0x0A), Rgb::new(0x33, 0xFF, 0x66)),
+            Preset::Amber => (Rgb::new(0x14, 0x0C, 0x00), Rgb::new(0xFF, 0xB0, 0x00)),
+            Preset::Lcd => (Rgb::new(0x9B, 0xBC, 0x0F), Rgb::new(0x0F, 0x38, 0x0F)),
+            Preset::Octo => (Rgb::new(0x99, 0x66, 0x00), Rgb::new(0xFF, 0xCC, 0x00)),
+        };
+
+        Palette { background, foreground }
+    }
+}
@@ -0,0 +1,104 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Performance Overlay
+// Frames and instructions per second, measured against the wall
+// clock, plus both timers, stamped into the top-left corner of the
+// shown frame in the 3x5 overlay font. Working at display resolution
+// means every frontend, terminal ones included, shows it for free.
+// F3 toggles it.
+// ───────────────────────────────────────────────────────────────
+
+use std::time::{Duration, Instant};
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH};
+use crate::chip8::cpu::Chip8;
+use crate::chip8::display::DisplayBuffer;
+use crate::overlay_font::{self, GLYPH_HEIGHT, GLYPH_WIDTH};
+
+// How often the rates are recomputed
+const SAMPLE_PERIOD: Duration = Duration::from_secs(1);
+
+pub struct PerfOverlay {
+    visible: bool,
+
+    // Start of the current sample, with the counters as they were then
+    sample_start: Instant,
+    sample_frames: u64,
+    sample_instructions: u64,
+
+    frames: u64,
+    fps: u64,
+    ips: u64,
+    delay_timer: u8,
+    sound_timer: u8,
+}
+
+impl PerfOverlay {
+
+    pub fn new(visible: bool) -> Self {
+        Self {
+            visible,
+            sample_start: Instant::now(),
+            sample_frames: 0,
+            sample_instructions: 0,
+            frames: 0,
+            fps: 0,
+            ips: 0,
+            delay_timer: 0,
+            sound_timer: 0,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    // Call once per shown frame; the rates only change once per SAMPLE_PERIOD
+    pub fn observe_frame(&mut self, chip8: &Chip8) {
+        self.frames += 1;
+        self.delay_timer = chip8.delay_timer;
+        self.sound_timer = chip8.sound_timer;
+
+        let elapsed: Duration = self.sample_start.elapsed();
+        if elapsed < SAMPLE_PERIOD {
+            return;
+        }
+
+        let instructions: u64 = chip8.stats.instructions;
+        let executed: u64 = instructions.saturating_sub(self.sample_instructions);
+        let seconds: f64 = elapsed.as_secs_f64();
+
+        self.fps = ((self.frames - self.sample_frames) as f64 / seconds).round() as u64;
+        self.ips = (executed as f64 / seconds).round() as u64;
+
+        self.sample_start = Instant::now();
+        self.sample_frames = self.frames;
+        self.sample_instructions = instructions;
+    }
+
+    // `frame` with the overlay on top, or None while it is hidden
+    pub fn overlay(&self, frame: &DisplayBuffer) -> Option<DisplayBuffer> {
+        if !self.visible {
+            return None;
+        }
+
+        let lines: [String; 3] = [
+            format!("FPS {}", self.fps),
+            format!("IPS {}", self.ips),
+            format!("DT {} ST {}", self.delay_timer, self.sound_timer),
+        ];
+
+        // Blank a box behind the text so it stays readable over any picture
+        let columns: usize = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+        let width: usize = (columns * GLYPH_WIDTH + 1).min(DISPLAY_WIDTH);
+        let height: usize = (lines.len() * GLYPH_HEIGHT + 1).min(DISPLAY_HEIGHT);
+
+        let mut shown: DisplayBuffer = *frame;
+        overlay_font::fill_box(&mut shown, 0, 0, width, height, false);
+
+        for (index, line) in lines.iter().enumerate() {
+            overlay_font::draw_text(&mut shown, 1, 1 + index * GLYPH_HEIGHT, line, true);
+        }
+
+        Some(shown)
+    }
+}
@@ -0,0 +1,103 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Per-ROM Settings
+// Settings remembered for one ROM between runs, kept next to it
+// like its SRAM file. Only the CPU speed chosen by --auto-speed is
+// stored so far.
+//
+// File format: one "key = value" line per setting. Keys this
+// version doesn't know are kept as they are. Blank lines and lines
+// starting with '#' are ignored.
+// ───────────────────────────────────────────────────────────────
+
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::chip8::error::Chip8Error;
+
+const CPU_HZ_KEY: &str = "cpu-hz";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RomSettings {
+    // Settings in file order
+    entries: Vec<(String, String)>,
+}
+
+impl RomSettings {
+
+    // A missing file holds no settings
+    pub fn load(path: &Path) -> Result<Self, Chip8Error> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Chip8Error> {
+        fs::write(path, self.to_text())?;
+        Ok(())
+    }
+
+    // Default settings file for a ROM: the ROM path with a .settings extension
+    pub fn path_for_rom(rom_path: &Path) -> PathBuf {
+        rom_path.with_extension("settings")
+    }
+
+    pub fn parse(text: &str) -> Result<Self, Chip8Error> {
+        let mut settings: RomSettings = RomSettings::default();
+
+        for (index, raw_line) in text.lines().enumerate() {
+            let invalid = |reason: &'static str| Chip8Error::InvalidRomSettings { line: index + 1, reason };
+
+            let line: &str = raw_line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or_else(|| invalid("expected KEY = VALUE"))?;
+            let (key, value) = (key.trim(), value.trim());
+            if key.is_empty() || key.contains(char::is_whitespace) {
+                return Err(invalid("expected KEY = VALUE"));
+            }
+            if key == CPU_HZ_KEY && !value.parse::<u64>().is_ok_and(|cpu_hz| cpu_hz > 0) {
+                return Err(invalid("cpu-hz is not a positive number"));
+            }
+
+            settings.set(key, value.to_string());
+        }
+
+        Ok(settings)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text: String = String::from("# CHIP-8 ROM settings\n");
+        for (key, value) in &self.entries {
+            let _ = writeln!(text, "{} = {}", key, value);
+        }
+
+        text
+    }
+
+    // Instructions per second to run the ROM at
+    pub fn cpu_hz(&self) -> Option<u64> {
+        self.get(CPU_HZ_KEY).and_then(|value| value.parse().ok())
+    }
+
+    pub fn set_cpu_hz(&mut self, cpu_hz: u64) {
+        self.set(CPU_HZ_KEY, cpu_hz.to_string());
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.entries.iter().find(|(known, _)| known == key).map(|(_, value)| value.as_str())
+    }
+
+    // Replace `key`'s value in place, or add it at the end
+    fn set(&mut self, key: &str, value: String) {
+        match self.entries.iter_mut().find(|(known, _)| known == key) {
+            Some((_, old)) => *old = value,
+            None => self.entries.push((key.to_string(), value)),
+        }
+    }
+}
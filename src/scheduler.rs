@@ -0,0 +1,433 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Real-time Scheduler
+// Paces CPU cycles and 60Hz timer ticks against a clock source.
+// The default clock combines a coarse thread::sleep with a short
+// spin whose length adapts to how much the host's sleep actually
+// overshoots; other clocks (virtual time, browser or hardware
+// timers) plug in through the Clock trait.
+// ───────────────────────────────────────────────────────────────
+
+use std::hint;
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::chip8::cpu::Chip8;
+
+// Number of 1ms sleeps used to measure overshoot at startup
+const CALIBRATION_SAMPLES: u32 = 10;
+
+// Extra spin margin on top of the measured overshoot
+const SPIN_MARGIN: Duration = Duration::from_micros(50);
+
+// Falling further behind than this resynchronises instead of catching up
+const MAX_LAG: Duration = Duration::from_millis(100);
+
+// Resolution of the jitter histograms; lateness beyond MAX_LAG lands in the last bucket
+const JITTER_BUCKET: Duration = Duration::from_micros(10);
+
+// ===============================================================
+// Adaptive sleeper
+// ===============================================================
+
+pub struct AdaptiveSleeper {
+    // Running estimate of how far past its target a sleep wakes up
+    overshoot: Duration,
+}
+
+impl AdaptiveSleeper {
+
+    // Measure the host's sleep overshoot with a few short sleeps
+    pub fn calibrate() -> Self {
+        let mut worst: Duration = Duration::ZERO;
+
+        for _ in 0..CALIBRATION_SAMPLES {
+            let requested: Duration = Duration::from_millis(1);
+            let start: Instant = Instant::now();
+            thread::sleep(requested);
+            worst = worst.max(start.elapsed().saturating_sub(requested));
+        }
+
+        Self { overshoot: worst }
+    }
+
+    pub fn overshoot(&self) -> Duration {
+        self.overshoot
+    }
+
+    // Block until `deadline`: sleep for the bulk of the wait, spin the rest
+    pub fn wait_until(&mut self, deadline: Instant) {
+        let now: Instant = Instant::now();
+        if now >= deadline {
+            return;
+        }
+
+        let spin_window: Duration = self.overshoot + SPIN_MARGIN;
+        let remaining: Duration = deadline - now;
+
+        if remaining > spin_window {
+            let requested: Duration = remaining - spin_window;
+            let start: Instant = Instant::now();
+            thread::sleep(requested);
+
+            // Exponential moving average (1/8 weight) of the observed overshoot
+            let observed: Duration = start.elapsed().saturating_sub(requested);
+            self.overshoot = (self.overshoot * 7 + observed) / 8;
+        }
+
+        while Instant::now() < deadline {
+            hint::spin_loop();
+        }
+    }
+}
+
+// ===============================================================
+// Clock sources
+// ===============================================================
+
+// Time source the scheduler waits on. Times are offsets from the
+// clock's own starting point, so no platform Instant is required.
+pub trait Clock {
+    fn now(&self) -> Duration;
+
+    // Block until `deadline` as precisely as the clock allows
+    fn wait_until(&mut self, deadline: Duration);
+
+    // Block until roughly `deadline`; being a few ms late is fine
+    fn sleep_until(&mut self, deadline: Duration) {
+        self.wait_until(deadline);
+    }
+}
+
+// Host time from std, waited on with the adaptive sleeper
+pub struct SystemClock {
+    start: Instant,
+    sleeper: AdaptiveSleeper,
+}
+
+impl SystemClock {
+
+    pub fn new() -> Self {
+        // Calibrate first so the clock starts after the calibration sleeps
+        let sleeper: AdaptiveSleeper = AdaptiveSleeper::calibrate();
+        Self { start: Instant::now(), sleeper }
+    }
+
+    pub fn sleeper(&self) -> &AdaptiveSleeper {
+        &self.sleeper
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn wait_until(&mut self, deadline: Duration) {
+        self.sleeper.wait_until(self.start + deadline);
+    }
+
+    fn sleep_until(&mut self, deadline: Duration) {
+        let now: Duration = self.now();
+        if deadline > now {
+            thread::sleep(deadline - now);
+        }
+    }
+}
+
+// Virtual time that only moves when waited on or advanced, so a run
+// can be driven deterministically (e.g. from tests)
+#[derive(Default)]
+pub struct ManualClock {
+    now: Duration,
+}
+
+impl ManualClock {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn advance(&mut self, by: Duration) {
+        self.now += by;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now
+    }
+
+    fn wait_until(&mut self, deadline: Duration) {
+        self.now = self.now.max(deadline);
+    }
+}
+
+// ===============================================================
+// Jitter statistics
+// ===============================================================
+
+// Histogram of how late ticks fired relative to their deadline
+pub struct JitterHistogram {
+    buckets: Vec<u64>,
+    count: u64,
+}
+
+impl Default for JitterHistogram {
+    fn default() -> Self {
+        let bucket_count: usize = (MAX_LAG.as_nanos() / JITTER_BUCKET.as_nanos()) as usize + 1;
+        Self { buckets: vec![0; bucket_count], count: 0 }
+    }
+}
+
+impl JitterHistogram {
+
+    pub fn record(&mut self, lateness: Duration) {
+        let bucket: usize = (lateness.as_nanos() / JITTER_BUCKET.as_nanos()) as usize;
+        let last: usize = self.buckets.len() - 1;
+
+        self.buckets[bucket.min(last)] += 1;
+        self.count += 1;
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    // Lateness at or below which `percent` of the ticks fired (upper bucket edge)
+    pub fn percentile(&self, percent: f64) -> Duration {
+        let target: u64 = ((self.count as f64 * percent / 100.0).ceil() as u64).max(1);
+        let mut seen: u64 = 0;
+
+        for (bucket, &count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= target {
+                return JITTER_BUCKET * (bucket as u32 + 1);
+            }
+        }
+
+        Duration::ZERO
+    }
+}
+
+#[derive(Default)]
+pub struct TickJitter {
+    pub cpu: JitterHistogram,
+    pub timer: JitterHistogram,
+}
+
+impl TickJitter {
+
+    // Append `jitter,<tick>_<percentile>_us,value` rows matching the stats CSV
+    pub fn write_csv<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        for (name, histogram) in [("cpu", &self.cpu), ("timer", &self.timer)] {
+            writeln!(output, "jitter,{}_ticks,{}", name, histogram.count())?;
+            writeln!(output, "jitter,{}_p50_us,{}", name, histogram.percentile(50.0).as_micros())?;
+            writeln!(output, "jitter,{}_p99_us,{}", name, histogram.percentile(99.0).as_micros())?;
+        }
+
+        Ok(())
+    }
+
+    pub fn write_report<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        writeln!(output, "Tick jitter (lateness vs. deadline):")?;
+
+        for (name, histogram) in [("CPU", &self.cpu), ("Timer", &self.timer)] {
+            writeln!(
+                output,
+                "  {:<6}{:>9} ticks   p50 {:>8.2?}   p99 {:>8.2?}",
+                name,
+                histogram.count(),
+                histogram.percentile(50.0),
+                histogram.percentile(99.0)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// ===============================================================
+// Tick scheduler
+// ===============================================================
+
+// Which events are due after a wait
+pub struct DueTicks {
+    pub cpu: bool,
+    pub timer: bool,
+}
+
+impl DueTicks {
+
+    // Whether `chip8` should run a cycle now. A machine blocked in FX0A is
+    // idle and gets no CPU ticks, but only a cycle can see the key it waits
+    // for, so it re-checks the keypad once per timer tick instead.
+    pub fn runs_cycle(&self, chip8: &Chip8) -> bool {
+        self.cpu || (self.timer && chip8.waiting_for_key().is_some())
+    }
+}
+
+pub struct Scheduler<C: Clock = SystemClock> {
+    cpu_interval: Duration,
+    timer_interval: Duration,
+    next_cpu_tick: Duration,
+    next_timer_tick: Duration,
+    clock: C,
+
+    // While idle, CPU ticks are suppressed and waits sleep without spinning
+    idle: bool,
+
+    jitter: TickJitter,
+}
+
+impl Scheduler {
+
+    // Pace against host time
+    pub fn new(cpu_hz: u64, timer_hz: u64) -> Self {
+        Self::with_clock(SystemClock::new(), cpu_hz, timer_hz)
+    }
+}
+
+impl<C: Clock> Scheduler<C> {
+
+    pub fn with_clock(clock: C, cpu_hz: u64, timer_hz: u64) -> Self {
+        let now: Duration = clock.now();
+
+        Self {
+            cpu_interval: Duration::from_secs_f64(1.0 / cpu_hz as f64),
+            timer_interval: Duration::from_secs_f64(1.0 / timer_hz as f64),
+            next_cpu_tick: now,
+            next_timer_tick: now,
+            clock,
+            idle: false,
+            jitter: TickJitter::default(),
+        }
+    }
+
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    pub fn clock_mut(&mut self) -> &mut C {
+        &mut self.clock
+    }
+
+    // Current time on the scheduler's clock
+    pub fn now(&self) -> Duration {
+        self.clock.now()
+    }
+
+    pub fn jitter(&self) -> &TickJitter {
+        &self.jitter
+    }
+
+    // Change the CPU rate; the next CPU tick keeps its deadline
+    pub fn set_cpu_hz(&mut self, cpu_hz: u64) {
+        self.cpu_interval = Duration::from_secs_f64(1.0 / cpu_hz as f64);
+    }
+
+    // Enter or leave low-power mode, for a machine that cannot make progress
+    // until input arrives. Leaving it restarts the CPU cadence from now.
+    pub fn set_idle(&mut self, idle: bool) {
+        if self.idle && !idle {
+            self.next_cpu_tick = self.clock.now();
+        }
+        self.idle = idle;
+    }
+
+    // Wait for the next CPU or timer deadline and report which are due.
+    // Deadlines advance by whole intervals so the cadence does not drift.
+    pub fn wait(&mut self) -> DueTicks {
+        if self.idle {
+            return self.wait_idle();
+        }
+
+        let deadline: Duration = self.next_cpu_tick.min(self.next_timer_tick);
+        self.clock.wait_until(deadline);
+
+        let now: Duration = self.clock.now();
+
+        // After a long stall (debugger, suspend) resynchronise rather than burst
+        if now.saturating_sub(deadline) > MAX_LAG {
+            self.next_cpu_tick = now;
+            self.next_timer_tick = now;
+        }
+
+        let cpu: bool = now >= self.next_cpu_tick;
+        if cpu {
+            self.jitter.cpu.record(now - self.next_cpu_tick);
+            self.next_cpu_tick += self.cpu_interval;
+        }
+
+        let timer: bool = now >= self.next_timer_tick;
+        if timer {
+            self.jitter.timer.record(now - self.next_timer_tick);
+            self.next_timer_tick += self.timer_interval;
+        }
+
+        DueTicks { cpu, timer }
+    }
+    // Plain sleep up to the next timer tick; a few ms of jitter is harmless
+    // when nothing but the timers is running
+    fn wait_idle(&mut self) -> DueTicks {
+        self.clock.sleep_until(self.next_timer_tick);
+
+        let now: Duration = self.clock.now();
+        if now.saturating_sub(self.next_timer_tick) > MAX_LAG {
+            self.next_timer_tick = now;
+        }
+        self.jitter.timer.record(now.saturating_sub(self.next_timer_tick));
+        self.next_timer_tick += self.timer_interval;
+
+        DueTicks { cpu: false, timer: true }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // F00A: wait for a key into V0, then 1202: jump to self
+    const KEY_WAIT: [u8; 4] = [0xF0, 0x0A, 0x12, 0x02];
+
+    #[test]
+    fn key_press_ends_an_idle_key_wait() {
+        let mut chip8: Chip8 = Chip8::new();
+        chip8.load_rom(&KEY_WAIT).unwrap();
+        let mut scheduler: Scheduler<ManualClock> = Scheduler::with_clock(ManualClock::new(), 700, 60);
+
+        for iteration in 0..400 {
+            if iteration == 100 {
+                chip8.keys[5] = true;
+            }
+
+            scheduler.set_idle(chip8.waiting_for_key().is_some());
+            let due: DueTicks = scheduler.wait();
+            if due.runs_cycle(&chip8) {
+                chip8.cycle();
+            }
+        }
+
+        assert_eq!(chip8.waiting_for_key(), None);
+        assert_eq!(chip8.v[0], 5);
+        assert_eq!(chip8.pc, 0x202);
+    }
+
+    #[test]
+    fn idle_waits_only_grant_timer_ticks() {
+        let mut scheduler: Scheduler<ManualClock> = Scheduler::with_clock(ManualClock::new(), 700, 60);
+        scheduler.set_idle(true);
+
+        for _ in 0..10 {
+            let due: DueTicks = scheduler.wait();
+            assert!(!due.cpu && due.timer);
+        }
+        assert!(scheduler.now() >= Duration::from_secs_f64(9.0 / 60.0));
+    }
+}
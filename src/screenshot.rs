@@ -0,0 +1,201 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Screenshots
+// Renders display frames to grayscale or palette-colored images,
+// tiles several into a contact sheet and writes them as PNG. The encoder only emits
+// uncompressed (stored) deflate blocks; CHIP-8 frames are small
+// enough that this costs nothing worth a compression dependency.
+// ───────────────────────────────────────────────────────────────
+
+use std::io::{self, Write};
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH};
+use crate::chip8::display::DisplayBuffer;
+use crate::palette::{Palette, Rgb};
+
+const BACKGROUND: u8 = 0x10;
+const FOREGROUND: u8 = 0xE0;
+
+// Fill between the tiles of a contact sheet
+const GUTTER: u8 = 0x40;
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+// Largest payload of a stored deflate block
+const MAX_STORED_BLOCK: usize = 0xFFFF;
+
+// ===============================================================
+// Images
+// ===============================================================
+
+// 8-bit grayscale (1 channel) or RGB (3 channels) image, row-major
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub channels: usize,
+    pub pixels: Vec<u8>,
+}
+
+impl Image {
+
+    pub fn filled(width: usize, height: usize, value: u8) -> Self {
+        Self { width, height, channels: 1, pixels: vec![value; width * height] }
+    }
+
+    // A frame with each CHIP-8 pixel drawn as a `scale` x `scale` square
+    pub fn from_frame(frame: &DisplayBuffer, scale: usize) -> Self {
+        let mut image: Image = Image::filled(DISPLAY_WIDTH * scale, DISPLAY_HEIGHT * scale, BACKGROUND);
+
+        for (y, row) in image.pixels.chunks_exact_mut(DISPLAY_WIDTH * scale).enumerate() {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                if frame[y / scale][x / scale] {
+                    *pixel = FOREGROUND;
+                }
+            }
+        }
+
+        image
+    }
+
+    // Like `from_frame`, in RGB with the palette's colors
+    pub fn from_frame_colored(frame: &DisplayBuffer, scale: usize, palette: &Palette) -> Self {
+        let (width, height) = (DISPLAY_WIDTH * scale, DISPLAY_HEIGHT * scale);
+        let mut pixels: Vec<u8> = Vec::with_capacity(width * height * 3);
+
+        for y in 0..height {
+            for x in 0..width {
+                let color: Rgb = if frame[y / scale][x / scale] { palette.foreground } else { palette.background };
+                pixels.extend_from_slice(&[color.r, color.g, color.b]);
+            }
+        }
+
+        Self { width, height, channels: 3, pixels }
+    }
+
+    // Copy `other` in with its top-left corner at (x, y); it must fit and
+    // have the same number of channels
+    pub fn blit(&mut self, other: &Image, x: usize, y: usize) {
+        let channels: usize = self.channels;
+
+        for (row, source) in other.pixels.chunks_exact(other.width * channels).enumerate() {
+            let start: usize = ((y + row) * self.width + x) * channels;
+            self.pixels[start..start + source.len()].copy_from_slice(source);
+        }
+    }
+
+    pub fn write_png<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        output.write_all(&PNG_SIGNATURE)?;
+
+        // Grayscale (color type 0) or truecolor (2), 8 bits per channel, no interlacing
+        let color_type: u8 = if self.channels == 3 { 2 } else { 0 };
+        let mut header: Vec<u8> = Vec::with_capacity(13);
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        header.extend_from_slice(&[8, color_type, 0, 0, 0]);
+        write_chunk(output, b"IHDR", &header)?;
+
+        // Every scanline starts with filter type 0 (none)
+        let stride: usize = self.width * self.channels;
+        let mut scanlines: Vec<u8> = Vec::with_capacity((stride + 1) * self.height);
+        for row in self.pixels.chunks_exact(stride) {
+            scanlines.push(0);
+            scanlines.extend_from_slice(row);
+        }
+        write_chunk(output, b"IDAT", &zlib_stored(&scanlines))?;
+
+        write_chunk(output, b"IEND", &[])
+    }
+}
+
+// Tile `images` (all the same size) left to right, `columns` per row,
+// with a gutter of `gap` pixels around and between them
+pub fn contact_sheet(images: &[Image], columns: usize, gap: usize) -> Image {
+    let Some(first) = images.first() else {
+        return Image::filled(0, 0, GUTTER);
+    };
+
+    let columns: usize = columns.clamp(1, images.len());
+    let rows: usize = images.len().div_ceil(columns);
+    let (tile_width, tile_height) = (first.width + gap, first.height + gap);
+
+    let mut sheet: Image = Image::filled(columns * tile_width + gap, rows * tile_height + gap, GUTTER);
+    for (index, image) in images.iter().enumerate() {
+        let (column, row) = (index % columns, index / columns);
+        sheet.blit(image, gap + column * tile_width, gap + row * tile_height);
+    }
+
+    sheet
+}
+
+// ===============================================================
+// PNG encoding
+// ===============================================================
+
+fn write_chunk<W: Write>(output: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    output.write_all(&(data.len() as u32).to_be_bytes())?;
+    output.write_all(kind)?;
+    output.write_all(data)?;
+
+    let mut crc: Crc32 = Crc32::new();
+    crc.write(kind);
+    crc.write(data);
+    output.write_all(&crc.finish().to_be_bytes())
+}
+
+// Wrap `data` in a zlib stream made of stored (uncompressed) deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream: Vec<u8> = vec![0x78, 0x01];
+
+    let mut blocks = data.chunks(MAX_STORED_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        stream.extend_from_slice(&[0x01, 0x00, 0x00, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        let last: bool = blocks.peek().is_none();
+        let len: u16 = block.len() as u16;
+
+        stream.push(last as u8);
+        stream.extend_from_slice(&len.to_le_bytes());
+        stream.extend_from_slice(&(!len).to_le_bytes());
+        stream.extend_from_slice(block);
+    }
+
+    stream.extend_from_slice(&adler32(data).to_be_bytes());
+    stream
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MODULUS: u32 = 65521;
+
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % MODULUS;
+        b = (b + a) % MODULUS;
+    }
+
+    (b << 16) | a
+}
+
+// CRC-32 (IEEE, reflected), as used by PNG chunks
+struct Crc32 {
+    state: u32,
+}
+
+impl Crc32 {
+    fn new() -> Self {
+        Self { state: 0xFFFF_FFFF }
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state ^= byte as u32;
+            for _ in 0..8 {
+                let mask: u32 = (self.state & 1).wrapping_neg();
+                self.state = (self.state >> 1) ^ (0xEDB8_8320 & mask);
+            }
+        }
+    }
+
+    fn finish(&self) -> u32 {
+        !self.state
+    }
+}
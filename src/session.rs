@@ -0,0 +1,169 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Session Files
+// A saved setup: the ROM, a hash of its contents and the command
+// line options it ran with (quirks, keyboard layout, palette,
+// window, ...), so a debugging or teaching setup can be shared and
+// started again with --session.
+//
+// File format: one "key = value" line per option, named as on the
+// command line without the dashes, or just "key" for a switch. An
+// option given several values appears once per value. "rom" is the
+// ROM path, relative to the session file unless absolute, and
+// "rom-hash" its FNV-1a hash in hex. Blank lines and lines starting
+// with '#' are ignored.
+// ───────────────────────────────────────────────────────────────
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::chip8::error::Chip8Error;
+use crate::chip8::hash::Fnv1a;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Session {
+    pub rom: Option<PathBuf>,
+
+    // Hash of the ROM when the session was saved, to notice it changing
+    pub rom_hash: Option<u64>,
+
+    // Long option names with their value, None for switches, in file order
+    pub options: Vec<(String, Option<String>)>,
+}
+
+impl Session {
+
+    pub fn load(path: &Path) -> Result<Self, Chip8Error> {
+        let base: &Path = path.parent().unwrap_or(Path::new(""));
+        Self::parse(&fs::read_to_string(path)?, base)
+    }
+
+    // Write to `path`, with the ROM path relative to it where the ROM sits
+    // in the same directory tree
+    pub fn save(&self, path: &Path) -> Result<(), Chip8Error> {
+        let base: PathBuf = path.parent().unwrap_or(Path::new("")).to_path_buf();
+        fs::write(path, self.to_text(&base))?;
+        Ok(())
+    }
+
+    // Relative ROM paths in `text` are taken from `base`
+    pub fn parse(text: &str, base: &Path) -> Result<Self, Chip8Error> {
+        let mut session: Session = Session::default();
+
+        for (index, raw_line) in text.lines().enumerate() {
+            let invalid = |reason: &'static str| Chip8Error::InvalidSession { line: index + 1, reason };
+
+            let line: &str = raw_line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), Some(value.trim())),
+                None => (line, None),
+            };
+            if key.is_empty() || key.starts_with('-') || key.contains(char::is_whitespace) {
+                return Err(invalid("expected KEY or KEY = VALUE"));
+            }
+
+            match (key, value) {
+                ("rom", Some(path)) => session.rom = Some(base.join(path)),
+                ("rom-hash", Some(hash)) => {
+                    let hash: u64 = u64::from_str_radix(hash, 16).map_err(|_| invalid("rom-hash is not a hex number"))?;
+                    session.rom_hash = Some(hash);
+                }
+                ("rom" | "rom-hash", None) => return Err(invalid("expected a value")),
+                (key, value) => session.options.push((key.to_string(), value.map(str::to_string))),
+            }
+        }
+
+        Ok(session)
+    }
+
+    pub fn to_text(&self, base: &Path) -> String {
+        let mut text: String = String::from("# CHIP-8 session\n");
+
+        if let Some(rom) = &self.rom {
+            let absolute: PathBuf = fs::canonicalize(rom).unwrap_or_else(|_| rom.clone());
+            let base: &Path = if base.as_os_str().is_empty() { Path::new(".") } else { base };
+            let base: PathBuf = fs::canonicalize(base).unwrap_or_else(|_| base.to_path_buf());
+            let shown: &Path = absolute.strip_prefix(&base).unwrap_or(&absolute);
+            let _ = writeln!(text, "rom = {}", shown.display());
+        }
+        if let Some(hash) = self.rom_hash {
+            let _ = writeln!(text, "rom-hash = {:016x}", hash);
+        }
+
+        for (key, value) in &self.options {
+            match value {
+                Some(value) => {
+                    let _ = writeln!(text, "{} = {}", key, value);
+                }
+                None => {
+                    let _ = writeln!(text, "{}", key);
+                }
+            }
+        }
+
+        text
+    }
+
+    // The options as command line arguments ("--key=value" or "--key")
+    pub fn to_args(&self) -> Vec<String> {
+        self.options
+            .iter()
+            .map(|(key, value)| match value {
+                Some(value) => format!("--{}={}", key, value),
+                None => format!("--{}", key),
+            })
+            .collect()
+    }
+
+    pub fn hash_rom(rom: &[u8]) -> u64 {
+        let mut hasher: Fnv1a = Fnv1a::new();
+        hasher.write(rom);
+        hasher.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_keeps_the_rom_relative_to_the_session() {
+        let dir: PathBuf = std::env::temp_dir().join(format!("chip8-session-{}", std::process::id()));
+        fs::create_dir_all(dir.join("roms")).unwrap();
+        let rom: PathBuf = dir.join("roms").join("game.ch8");
+        fs::write(&rom, [0x12, 0x00]).unwrap();
+        let dir: PathBuf = fs::canonicalize(&dir).unwrap();
+
+        let session: Session = Session {
+            rom: Some(dir.join("roms").join("game.ch8")),
+            rom_hash: Some(Session::hash_rom(&[0x12, 0x00])),
+            options: vec![
+                ("headless".to_string(), None),
+                ("shots".to_string(), Some("10".to_string())),
+                ("shots".to_string(), Some("20".to_string())),
+            ],
+        };
+        let text: String = session.to_text(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(text.lines().any(|line| line == format!("rom = {}", Path::new("roms").join("game.ch8").display())));
+        assert!(text.lines().any(|line| line == "headless"));
+        assert_eq!(Session::parse(&text, &dir).unwrap(), session);
+        assert_eq!(session.to_args(), ["--headless", "--shots=10", "--shots=20"]);
+    }
+
+    #[test]
+    fn bad_rom_hash_is_an_error() {
+        let parsed = Session::parse("# saved\nrom = game.ch8\nrom-hash = xyz\n", Path::new(""));
+        assert!(matches!(parsed, Err(Chip8Error::InvalidSession { line: 3, .. })));
+    }
+
+    #[test]
+    fn rom_without_a_value_is_an_error() {
+        assert!(matches!(Session::parse("rom\n", Path::new("")), Err(Chip8Error::InvalidSession { line: 1, .. })));
+    }
+}
@@ -0,0 +1,48 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Status Screens
+// Built-in screens drawn into a display buffer with the overlay
+// font: a splash while no ROM is loaded, and an error screen when
+// loading one fails. Shown in place of the machine's frames, so a
+// window is never left black and the terminal frontends report the
+// status inside their usual layout.
+// ───────────────────────────────────────────────────────────────
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH};
+use crate::chip8::display::{DisplayBuffer, BLANK_DISPLAY};
+use crate::overlay_font::{self, GLYPH_HEIGHT, GLYPH_WIDTH};
+
+pub fn splash() -> DisplayBuffer {
+    screen("CHIP-8", &["NO ROM LOADED"])
+}
+
+// `reason` is a few words, such as "NOT FOUND"
+pub fn load_error(reason: &str) -> DisplayBuffer {
+    screen("LOAD FAILED", &[reason])
+}
+
+// An inverted title bar over centred lines of text
+fn screen(title: &str, lines: &[&str]) -> DisplayBuffer {
+    let bar_height: usize = GLYPH_HEIGHT + 3;
+    let line_height: usize = GLYPH_HEIGHT + 2;
+
+    let mut frame: DisplayBuffer = BLANK_DISPLAY;
+
+    overlay_font::fill_box(&mut frame, 0, 0, DISPLAY_WIDTH, bar_height, true);
+    draw_centred(&mut frame, 2, title, false);
+
+    // Centred in the space under the bar; glyphs have a blank bottom row
+    let text_height: usize = (lines.len() * line_height).saturating_sub(line_height - GLYPH_HEIGHT + 1);
+    let top: usize = bar_height + (DISPLAY_HEIGHT - bar_height).saturating_sub(text_height) / 2;
+
+    for (index, line) in lines.iter().enumerate() {
+        draw_centred(&mut frame, top + index * line_height, line, true);
+    }
+
+    frame
+}
+
+// Lines longer than the display are cut off at the right edge
+fn draw_centred(frame: &mut DisplayBuffer, y: usize, text: &str, lit: bool) {
+    let width: usize = (text.len() * GLYPH_WIDTH).saturating_sub(1);
+    overlay_font::draw_text(frame, DISPLAY_WIDTH.saturating_sub(width) / 2, y, text, lit);
+}
@@ -0,0 +1,156 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Synthetic Stress ROMs
+// Generates pathological programs that probe performance and
+// robustness corners of the interpreter.
+// ───────────────────────────────────────────────────────────────
+
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+
+use crate::chip8::constants::{FONT_START, PROGRAM_START, STACK_SIZE};
+
+#[derive(Clone, Copy)]
+pub enum StressKind {
+    // Back-to-back full-height DXYN draws
+    Draws,
+
+    // Call chain that fills the whole stack, then unwinds, forever
+    Calls,
+
+    // Continuous FX55/FX65 register spills and reloads
+    Memory,
+
+    // Random but well-formed opcodes with jump targets inside the program
+    Soup,
+}
+
+// Number of random instructions emitted for the opcode soup
+pub const SOUP_LENGTH: usize = 1024;
+
+// Generate the ROM for `kind`. Only Soup depends on `seed`.
+pub fn generate(kind: StressKind, seed: u64) -> Vec<u8> {
+    let opcodes: Vec<u16> = match kind {
+        StressKind::Draws => draws(),
+        StressKind::Calls => calls(),
+        StressKind::Memory => memory(),
+        StressKind::Soup => soup(seed),
+    };
+
+    opcodes.iter().flat_map(|opcode| opcode.to_be_bytes()).collect()
+}
+
+// Address of the instruction at `index` in the generated program
+fn address_of(index: usize) -> u16 {
+    PROGRAM_START + (index as u16) * 2
+}
+
+fn draws() -> Vec<u16> {
+    // I = font data, V0/V1 = position
+    let mut program: Vec<u16> = vec![0xA000 | FONT_START, 0x6000, 0x6100];
+    let loop_start: u16 = address_of(program.len());
+
+    for _ in 0..64 {
+        program.push(0xD01F); // draw 15 rows at (V0, V1)
+        program.push(0x7005); // V0 += 5
+        program.push(0x7103); // V1 += 3
+    }
+    program.push(0x1000 | loop_start);
+
+    program
+}
+
+fn calls() -> Vec<u16> {
+    // 0x200: call the first link, then start over
+    let mut program: Vec<u16> = vec![0x2000 | address_of(2), 0x1000 | PROGRAM_START];
+
+    // Each link calls the next; the last one just returns
+    for depth in 0..STACK_SIZE {
+        if depth + 1 < STACK_SIZE {
+            program.push(0x2000 | address_of(program.len() + 2));
+        } else {
+            program.push(0x6000); // V0 = 0 (filler, keeps links 2 words long)
+        }
+        program.push(0x00EE);
+    }
+
+    program
+}
+
+fn memory() -> Vec<u16> {
+    vec![
+        0xA300, // I = 0x300
+        0xFF65, // load V0..VF
+        0x7001, // V0 += 1
+        0xFF55, // store V0..VF
+        0x1000 | PROGRAM_START,
+    ]
+}
+
+fn soup(seed: u64) -> Vec<u16> {
+    let mut rng: StdRng = StdRng::seed_from_u64(seed);
+    let mut program: Vec<u16> = Vec::with_capacity(SOUP_LENGTH);
+
+    for _ in 0..SOUP_LENGTH {
+        let x: u16 = (rng.random::<u8>() & 0xF) as u16;
+        let y: u16 = (rng.random::<u8>() & 0xF) as u16;
+        let n: u16 = (rng.random::<u8>() & 0xF) as u16;
+        let nn: u16 = rng.random::<u8>() as u16;
+        let nnn: u16 = rng.random::<u16>() & 0x0FFF;
+        let target: u16 = address_of(rng.random_range(0..SOUP_LENGTH));
+
+        let opcode: u16 = match rng.random_range(0..20) {
+            0 => 0x00E0,
+            1 => 0x00EE,
+            2 => 0x1000 | target,
+            3 => 0x2000 | target,
+            4 => 0x3000 | (x << 8) | nn,
+            5 => 0x4000 | (x << 8) | nn,
+            6 => 0x5000 | (x << 8) | (y << 4),
+            7 => 0x6000 | (x << 8) | nn,
+            8 => 0x7000 | (x << 8) | nn,
+            9 => {
+                let variants: [u16; 9] = [0x0, 0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x7, 0xE];
+                0x8000 | (x << 8) | (y << 4) | variants[rng.random_range(0..variants.len())]
+            }
+            10 => 0x9000 | (x << 8) | (y << 4),
+            11 => 0xA000 | nnn,
+            12 => 0xB000 | target,
+            13 => 0xC000 | (x << 8) | nn,
+            14 => 0xD000 | (x << 8) | (y << 4) | n,
+            15 => 0xE09E | (x << 8),
+            16 => 0xE0A1 | (x << 8),
+            // FX0A is left out: without input it would stall the program for good
+            _ => {
+                let variants: [u16; 8] = [0x07, 0x15, 0x18, 0x1E, 0x29, 0x33, 0x55, 0x65];
+                0xF000 | (x << 8) | variants[rng.random_range(0..variants.len())]
+            }
+        };
+
+        program.push(opcode);
+    }
+
+    // Wrap around instead of running off into zeroed memory
+    program.push(0x1000 | PROGRAM_START);
+
+    program
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chip8::cpu::Chip8;
+
+    // Random opcodes reach every corner of memory through I and PC; none of
+    // them may bring the interpreter down
+    #[test]
+    fn soup_runs_without_panicking() {
+        for seed in 0..32 {
+            let mut chip8: Chip8 = Chip8::new();
+            chip8.load_rom(&generate(StressKind::Soup, seed)).unwrap();
+
+            for _ in 0..600 {
+                chip8.run_frame();
+            }
+        }
+    }
+}
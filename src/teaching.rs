@@ -0,0 +1,202 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Teaching Mode
+// Records every executed instruction together with its description
+// and the concrete registers, memory and display cells it touched,
+// resolved from the typed instruction metadata against the machine
+// state just before it ran. Frontends drain the steps to annotate
+// the live disassembly.
+// ───────────────────────────────────────────────────────────────
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH};
+use crate::chip8::cpu::{wrap, Chip8};
+use crate::chip8::hooks::HookAction;
+use crate::chip8::instruction::{Access, Instruction, Location};
+
+// Steps kept while nobody drains them; older ones are dropped
+const MAX_PENDING: usize = 4096;
+
+// A piece of machine state, with addresses and positions filled in
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Touch {
+    Register(u8),
+    Index,
+    ProgramCounter,
+    Stack,
+    DelayTimer,
+    SoundTimer,
+    Keypad,
+    Memory { start: u16, len: u16 },
+
+    // Display cells (x, y)..(x + width, y + height), wrapping at the edges
+    Display { x: u8, y: u8, width: u8, height: u8 },
+}
+
+impl Touch {
+
+    // Whether this is a display area that includes cell (x, y)
+    pub fn covers_cell(&self, cell_x: usize, cell_y: usize) -> bool {
+        let Touch::Display { x, y, width, height } = *self else {
+            return false;
+        };
+
+        let dx: usize = (cell_x + DISPLAY_WIDTH - x as usize) % DISPLAY_WIDTH;
+        let dy: usize = (cell_y + DISPLAY_HEIGHT - y as usize) % DISPLAY_HEIGHT;
+        dx < width as usize && dy < height as usize
+    }
+}
+
+impl fmt::Display for Touch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Touch::Register(x) => write!(f, "V{:X}", x),
+            Touch::Index => write!(f, "I"),
+            Touch::ProgramCounter => write!(f, "PC"),
+            Touch::Stack => write!(f, "stack"),
+            Touch::DelayTimer => write!(f, "DT"),
+            Touch::SoundTimer => write!(f, "ST"),
+            Touch::Keypad => write!(f, "keypad"),
+            // The end wraps to the start of memory like the core's accesses
+            Touch::Memory { start, len } => {
+                write!(f, "mem[{:03X}..{:03X}]", start, wrap(start as usize + len as usize))
+            }
+            Touch::Display { x, y, width, height } => write!(f, "display {}x{} at ({}, {})", width, height, x, y),
+        }
+    }
+}
+
+// One executed instruction
+#[derive(Clone, Debug)]
+pub struct Step {
+    pub pc: u16,
+    pub opcode: u16,
+    pub disassembly: String,
+    pub description: &'static str,
+    pub reads: Vec<Touch>,
+    pub writes: Vec<Touch>,
+}
+
+// Handle to the steps recorded by the hook `attach` installed; clones
+// share the same recording
+#[derive(Clone)]
+pub struct Lesson {
+    pending: Arc<Mutex<VecDeque<Step>>>,
+}
+
+impl Lesson {
+
+    // Start recording every instruction `chip8` executes. The hook survives
+    // resets and ROM reloads.
+    pub fn attach(chip8: &mut Chip8) -> Self {
+        let pending: Arc<Mutex<VecDeque<Step>>> = Arc::new(Mutex::new(VecDeque::new()));
+        let recorder: Arc<Mutex<VecDeque<Step>>> = Arc::clone(&pending);
+
+        chip8.on_opcode(0, 0, move |chip8, instruction| {
+            let step: Step = record(chip8, instruction);
+
+            if let Ok(mut pending) = recorder.lock() {
+                if pending.len() == MAX_PENDING {
+                    pending.pop_front();
+                }
+                pending.push_back(step);
+            }
+
+            HookAction::Continue
+        });
+
+        Self { pending }
+    }
+
+    // Steps executed since the last call, oldest first
+    pub fn take_steps(&self) -> Vec<Step> {
+        match self.pending.lock() {
+            Ok(mut pending) => pending.drain(..).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+// Called before `instruction` executes; PC already points past it
+fn record(chip8: &Chip8, instruction: Instruction) -> Step {
+    let pc: u16 = chip8.instruction_address();
+    let opcode: u16 = (chip8.memory[pc as usize] as u16) << 8 | chip8.memory[wrap(pc as usize + 1)] as u16;
+    let Access { reads, writes } = instruction.access();
+
+    Step {
+        pc,
+        opcode,
+        disassembly: chip8.disassemble(opcode),
+        description: instruction.spec().description,
+        reads: reads.into_iter().flat_map(|location| resolve(chip8, location)).collect(),
+        writes: writes.into_iter().flat_map(|location| resolve(chip8, location)).collect(),
+    }
+}
+
+fn resolve(chip8: &Chip8, location: Location) -> Vec<Touch> {
+    match location {
+        Location::Register(x) => vec![Touch::Register(x)],
+        Location::Registers { last } => (0..=last).map(Touch::Register).collect(),
+        Location::Index => vec![Touch::Index],
+        Location::ProgramCounter => vec![Touch::ProgramCounter],
+        Location::Stack => vec![Touch::Stack],
+        Location::DelayTimer => vec![Touch::DelayTimer],
+        Location::SoundTimer => vec![Touch::SoundTimer],
+        Location::Keypad => vec![Touch::Keypad],
+        Location::Memory { len } => vec![Touch::Memory { start: wrap(chip8.i as usize) as u16, len: len as u16 }],
+        Location::Sprite { x, y, rows } => vec![Touch::Display {
+            x: (chip8.v[x as usize] as usize % DISPLAY_WIDTH) as u8,
+            y: (chip8.v[y as usize] as usize % DISPLAY_HEIGHT) as u8,
+            width: 8,
+            height: rows.min(DISPLAY_HEIGHT as u8),
+        }],
+        Location::Screen => {
+            vec![Touch::Display { x: 0, y: 0, width: DISPLAY_WIDTH as u8, height: DISPLAY_HEIGHT as u8 }]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn first_step(chip8: &mut Chip8) -> Step {
+        let lesson: Lesson = Lesson::attach(chip8);
+        chip8.cycle();
+        lesson.take_steps().remove(0)
+    }
+
+    #[test]
+    fn instruction_at_end_of_memory_is_recorded() {
+        let mut chip8: Chip8 = Chip8::new();
+        chip8.memory[0xFFE..].copy_from_slice(&[0x60, 0x42]);
+        chip8.pc = 0xFFE;
+
+        let step: Step = first_step(&mut chip8);
+        assert_eq!((step.pc, step.opcode), (0xFFE, 0x6042));
+    }
+
+    #[test]
+    fn instruction_straddling_end_of_memory_is_recorded() {
+        let mut chip8: Chip8 = Chip8::new();
+        chip8.memory[0xFFF] = 0x61;
+        chip8.memory[0] = 0x07;
+        chip8.pc = 0xFFF;
+
+        let step: Step = first_step(&mut chip8);
+        assert_eq!((step.pc, step.opcode), (0xFFF, 0x6107));
+    }
+
+    #[test]
+    fn memory_touched_past_end_of_memory_wraps() {
+        // F255: store V0..V2 at I
+        let mut chip8: Chip8 = Chip8::new();
+        chip8.load_rom(&[0xF2, 0x55]).unwrap();
+        chip8.i = 0xFFFF;
+
+        let step: Step = first_step(&mut chip8);
+        assert_eq!(step.writes[0].to_string(), "mem[FFF..002]");
+    }
+}
@@ -0,0 +1,80 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Visual Buzzer
+// Shows the buzzer on screen while the sound timer runs, so games
+// that signal events by sound stay playable muted or by players who
+// cannot hear it. Either the display's outer ring of pixels flips,
+// or a speaker icon appears in the top-right corner.
+// ───────────────────────────────────────────────────────────────
+
+use crate::chip8::constants::{DISPLAY_HEIGHT, DISPLAY_WIDTH};
+use crate::chip8::display::DisplayBuffer;
+use crate::overlay_font;
+
+// Speaker with sound waves, drawn unlit on a lit box
+const ICON: [u8; 5] = [
+    0b001010,
+    0b011001,
+    0b111001,
+    0b011001,
+    0b001010,
+];
+const ICON_WIDTH: usize = 6;
+
+const BOX_WIDTH: usize = ICON_WIDTH + 2;
+const BOX_HEIGHT: usize = ICON.len() + 2;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BuzzerStyle {
+    // Invert the outermost pixels of the display
+    Border,
+
+    // Speaker icon in the top-right corner
+    Corner,
+}
+
+pub struct VisualBuzzer {
+    style: BuzzerStyle,
+}
+
+impl VisualBuzzer {
+
+    pub fn new(style: BuzzerStyle) -> Self {
+        Self { style }
+    }
+
+    // `frame` with the buzzer shown, or None while the sound timer is 0
+    pub fn overlay(&self, frame: &DisplayBuffer, sound_timer: u8) -> Option<DisplayBuffer> {
+        if sound_timer == 0 {
+            return None;
+        }
+
+        let mut shown: DisplayBuffer = *frame;
+
+        match self.style {
+            BuzzerStyle::Border => {
+                for (y, row) in shown.iter_mut().enumerate() {
+                    for (x, pixel) in row.iter_mut().enumerate() {
+                        if x == 0 || y == 0 || x == DISPLAY_WIDTH - 1 || y == DISPLAY_HEIGHT - 1 {
+                            *pixel = !*pixel;
+                        }
+                    }
+                }
+            }
+
+            BuzzerStyle::Corner => {
+                let left: usize = DISPLAY_WIDTH - BOX_WIDTH;
+                overlay_font::fill_box(&mut shown, left, 0, BOX_WIDTH, BOX_HEIGHT, true);
+
+                for (row, bits) in ICON.iter().enumerate() {
+                    for column in 0..ICON_WIDTH {
+                        if bits & (1 << (ICON_WIDTH - 1 - column)) != 0 {
+                            shown[1 + row][left + 1 + column] = false;
+                        }
+                    }
+                }
+            }
+        }
+
+        Some(shown)
+    }
+}
@@ -0,0 +1,67 @@
+// ───────────────────────────────────────────────────────────────
+// CHIP-8 Emulator — Stuck-ROM Watchdog
+// Flags a machine that keeps executing with no visible output:
+// no display change, no sound and no key wait for a whole timeout.
+// That is a common symptom of a quirk mismatch.
+// ───────────────────────────────────────────────────────────────
+
+use std::time::Duration;
+
+use crate::chip8::constants::TIMER_HZ;
+use crate::chip8::cpu::Chip8;
+
+pub struct Watchdog {
+    // Idle frames tolerated before the machine counts as stuck
+    limit_frames: u64,
+
+    // Consecutive frames without any activity
+    idle_frames: u64,
+
+    last_display_hash: u64,
+
+    // Set once tripped, cleared again as soon as activity resumes
+    tripped: bool,
+}
+
+impl Watchdog {
+
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            limit_frames: (timeout.as_secs_f64() * TIMER_HZ as f64).ceil() as u64,
+            idle_frames: 0,
+            last_display_hash: 0,
+            tripped: false,
+        }
+    }
+
+    // Call once per 60Hz frame. Returns true on the frame the machine is
+    // first considered stuck; it re-arms once activity resumes.
+    pub fn observe_frame(&mut self, chip8: &Chip8) -> bool {
+        let display_hash: u64 = chip8.display_hash();
+
+        let active: bool = display_hash != self.last_display_hash
+            || chip8.sound_timer > 0
+            || chip8.waiting_for_key().is_some();
+
+        self.last_display_hash = display_hash;
+
+        if active {
+            self.idle_frames = 0;
+            self.tripped = false;
+            return false;
+        }
+
+        self.idle_frames += 1;
+
+        if !self.tripped && self.idle_frames >= self.limit_frames {
+            self.tripped = true;
+            return true;
+        }
+
+        false
+    }
+
+    pub fn idle_time(&self) -> Duration {
+        Duration::from_secs_f64(self.idle_frames as f64 / TIMER_HZ as f64)
+    }
+}